@@ -0,0 +1,135 @@
+//! Builds paths and URLs for images hosted by community asset mirrors, such as operator avatars and item icons.
+//! These images are not part of the game data repositories, so they have to be fetched separately.
+//!
+//! ```
+//! # use ak_data::assets::AssetMirror;
+//! // https://raw.githubusercontent.com/Aceship/Arknight-Images/main/avatars/char_002_amiya.png
+//! let url = AssetMirror::aceship().avatar_url("char_002_amiya");
+//! ```
+
+use crate::game_data::{Item, OperatorSkill, OperatorSkin};
+
+/// The kind of image an asset ID refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AssetKind {
+  /// A square operator avatar, from [`OperatorSkin::avatar_id`].
+  Avatar,
+  /// A cropped operator portrait, as shown on the squad screen, from [`OperatorSkin::portrait_id`].
+  Portrait,
+  /// A full operator illustration, from [`OperatorSkin::portrait_id`].
+  /// (Mirrors name illustrations after portraits, rather than after [`OperatorSkin::illustration_id`])
+  Illustration,
+  /// An item icon, from [`Item::icon_id`].
+  ItemIcon,
+  /// A skill icon, from [`OperatorSkill::icon_id`].
+  SkillIcon
+}
+
+/// How a mirror arranges its images into folders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetLayout {
+  /// The layout of [Aceship/Arknight-Images](https://github.com/Aceship/Arknight-Images).
+  Aceship,
+  /// The layout of [yuanyan3060/ArknightsGameResource](https://github.com/yuanyan3060/ArknightsGameResource).
+  ArknightsGameResource
+}
+
+impl AssetLayout {
+  /// Returns the path of an image relative to the root of a mirror with this layout.
+  pub fn path(self, kind: AssetKind, id: &str) -> String {
+    match (self, kind) {
+      (AssetLayout::Aceship, AssetKind::Avatar) => format!("avatars/{id}.png"),
+      (AssetLayout::Aceship, AssetKind::Portrait) => format!("portraits/{id}.png"),
+      (AssetLayout::Aceship, AssetKind::Illustration) => format!("characters/{id}.png"),
+      (AssetLayout::Aceship, AssetKind::ItemIcon) => format!("items/{id}.png"),
+      (AssetLayout::Aceship, AssetKind::SkillIcon) => format!("skills/skill_icon_{id}.png"),
+      (AssetLayout::ArknightsGameResource, AssetKind::Avatar) => format!("avatar/{id}.png"),
+      (AssetLayout::ArknightsGameResource, AssetKind::Portrait) => format!("portrait/{id}.png"),
+      (AssetLayout::ArknightsGameResource, AssetKind::Illustration) => format!("skin/{id}b.png"),
+      (AssetLayout::ArknightsGameResource, AssetKind::ItemIcon) => format!("item/{id}.png"),
+      (AssetLayout::ArknightsGameResource, AssetKind::SkillIcon) => format!("skill/skill_icon_{id}.png")
+    }
+  }
+}
+
+/// A mirror of the game's images, which builds URLs from the asset IDs found in a [`GameData`][crate::GameData].
+///
+/// Mirrors are maintained by the community and may lag behind the game, so not every URL is guaranteed to exist.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssetMirror {
+  /// The URL of the root of the mirror, without a trailing slash.
+  pub base_url: String,
+  pub layout: AssetLayout
+}
+
+impl AssetMirror {
+  /// Creates a mirror with the given base URL and layout. Trailing slashes are removed from the base URL.
+  pub fn new(base_url: impl Into<String>, layout: AssetLayout) -> Self {
+    let mut base_url = base_url.into();
+    base_url.truncate(base_url.trim_end_matches('/').len());
+    AssetMirror { base_url, layout }
+  }
+
+  /// The [Aceship/Arknight-Images](https://github.com/Aceship/Arknight-Images) repository, served by GitHub.
+  pub fn aceship() -> Self {
+    AssetMirror::new("https://raw.githubusercontent.com/Aceship/Arknight-Images/main", AssetLayout::Aceship)
+  }
+
+  /// The [yuanyan3060/ArknightsGameResource](https://github.com/yuanyan3060/ArknightsGameResource) repository, served by GitHub.
+  pub fn arknights_game_resource() -> Self {
+    AssetMirror::new("https://raw.githubusercontent.com/yuanyan3060/ArknightsGameResource/main", AssetLayout::ArknightsGameResource)
+  }
+
+  /// Returns the URL of an image on this mirror.
+  /// Characters that are not allowed in URL paths, such as the `#` in some skin IDs, are percent-encoded.
+  pub fn url(&self, kind: AssetKind, id: &str) -> String {
+    format!("{}/{}", self.base_url, encode_path(&self.layout.path(kind, id)))
+  }
+
+  /// Returns the URL of an operator avatar, given an [`OperatorSkin::avatar_id`].
+  pub fn avatar_url(&self, avatar_id: &str) -> String {
+    self.url(AssetKind::Avatar, avatar_id)
+  }
+
+  /// Returns the URL of an operator portrait, given an [`OperatorSkin::portrait_id`].
+  pub fn portrait_url(&self, portrait_id: &str) -> String {
+    self.url(AssetKind::Portrait, portrait_id)
+  }
+
+  /// Returns the URL of the avatar, portrait and full illustration of a skin, in that order.
+  pub fn skin_urls(&self, skin: &OperatorSkin) -> [String; 3] {
+    [
+      self.url(AssetKind::Avatar, &skin.avatar_id),
+      self.url(AssetKind::Portrait, &skin.portrait_id),
+      self.url(AssetKind::Illustration, &skin.portrait_id)
+    ]
+  }
+
+  /// Returns the URL of an item's icon.
+  pub fn item_icon_url(&self, item: &Item) -> String {
+    self.url(AssetKind::ItemIcon, &item.icon_id)
+  }
+
+  /// Returns the URL of a skill's icon.
+  pub fn skill_icon_url(&self, skill: &OperatorSkill) -> String {
+    self.url(AssetKind::SkillIcon, &skill.icon_id)
+  }
+}
+
+impl Default for AssetMirror {
+  fn default() -> Self {
+    AssetMirror::aceship()
+  }
+}
+
+fn encode_path(path: &str) -> String {
+  let mut encoded = String::with_capacity(path.len());
+  for byte in path.bytes() {
+    match byte {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b'@' => encoded.push(byte as char),
+      _ => encoded.push_str(&format!("%{byte:02X}"))
+    };
+  };
+
+  encoded
+}
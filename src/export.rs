@@ -0,0 +1,7 @@
+//! Exports a [`GameData`][crate::GameData] to formats that can be used without writing any Rust.
+
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+pub mod svg;
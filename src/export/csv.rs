@@ -0,0 +1,202 @@
+//! Writes parts of a [`GameData`] as flat CSV tables, one row per record, with a header row.
+//!
+//! ```no_run
+//! # use ak_data::game_data::{GameData, Promotion};
+//! # fn f(game_data: &GameData) -> Result<(), ak_data::Error> {
+//! let file = std::fs::File::create("operators.csv")?;
+//! ak_data::export::csv::write_operators(game_data, Promotion::Elite2.with_level(u32::MAX), file)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use chrono::{DateTime, Utc};
+
+use std::io::Write;
+
+use crate::game_data::{GameData, HeadhuntingBannerType, ItemClass, Position, Promotion, PromotionAndLevel};
+use crate::options::Region;
+
+/// Writes every operator along with their stats at the given promotion and level,
+/// which is clamped to what each operator can reach. (Trust bonuses are not included)
+pub fn write_operators<W: Write>(game_data: &GameData, promotion_and_level: PromotionAndLevel, writer: W) -> Result<(), crate::Error> {
+  let mut writer = ::csv::Writer::from_writer(writer);
+  for operator in game_data.operators.values() {
+    let promotion_and_level = promotion_and_level.clamp_for(operator);
+    let attributes = match operator.promotions.get_attributes(promotion_and_level) {
+      Some(attributes) => attributes,
+      None => continue
+    };
+
+    writer.serialize(OperatorRow {
+      id: &operator.id,
+      name: &operator.name,
+      rarity: operator.rarity.get(),
+      profession: operator.profession.name(),
+      sub_profession: operator.sub_profession.name(),
+      position: operator.position,
+      promotion: promotion_and_level.promotion,
+      level: promotion_and_level.level,
+      max_hp: attributes.max_hp,
+      atk: attributes.atk,
+      def: attributes.def,
+      magic_resistance: attributes.magic_resistance,
+      deployment_cost: attributes.deployment_cost,
+      block_count: attributes.block_count,
+      base_attack_time: attributes.base_attack_time,
+      redeploy_time: attributes.redeploy_time
+    })?;
+  };
+
+  writer.flush()?;
+  Ok(())
+}
+
+/// Writes every item.
+pub fn write_items<W: Write>(game_data: &GameData, writer: W) -> Result<(), crate::Error> {
+  let mut writer = ::csv::Writer::from_writer(writer);
+  for item in game_data.items.values() {
+    writer.serialize(ItemRow {
+      id: &item.id,
+      name: &item.name,
+      rarity: item.rarity,
+      item_class: item.item_class,
+      item_type: &item.item_type,
+      sort_id: item.sort_id
+    })?;
+  };
+
+  writer.flush()?;
+  Ok(())
+}
+
+/// Writes every headhunting banner, from oldest to newest.
+/// Limited operators are separated by semicolons.
+pub fn write_banners<W: Write>(game_data: &GameData, writer: W) -> Result<(), crate::Error> {
+  let mut writer = ::csv::Writer::from_writer(writer);
+  for banner in game_data.headhunting_banners.iter() {
+    writer.serialize(BannerRow {
+      id: &banner.id,
+      name: &banner.name,
+      banner_type: banner.banner_type,
+      open_time: banner.open_time,
+      close_time: banner.close_time,
+      event_id: banner.event_id.as_deref(),
+      limited_operator_ids: banner.rules.limited_operator_ids.iter()
+        .map(|operator_id| operator_id.as_str())
+        .collect::<Vec<&str>>()
+        .join(";")
+    })?;
+  };
+
+  writer.flush()?;
+  Ok(())
+}
+
+/// Writes the items needed for every promotion of every operator, one row per item.
+/// Item names are left empty for items that are not in [`GameData::items`].
+pub fn write_promotion_costs<W: Write>(game_data: &GameData, writer: W) -> Result<(), crate::Error> {
+  let mut writer = ::csv::Writer::from_writer(writer);
+  for operator in game_data.operators.values() {
+    for promotion in [Promotion::Elite1, Promotion::Elite2] {
+      let operator_promotion = match operator.promotions.get(promotion) {
+        Some(operator_promotion) => operator_promotion,
+        None => continue
+      };
+
+      for (item_id, &count) in operator_promotion.upgrade_cost.iter() {
+        writer.serialize(PromotionCostRow {
+          operator_id: &operator.id,
+          operator_name: &operator.name,
+          promotion,
+          item_id,
+          item_name: game_data.items.get(item_id).map(|item| item.name.as_str()),
+          count
+        })?;
+      };
+    };
+  };
+
+  writer.flush()?;
+  Ok(())
+}
+
+/// Writes where the game data came from as a single row, see [`GameData::provenance`].
+/// Write this alongside the other tables, so that exported data can be attributed to its source.
+pub fn write_provenance<W: Write>(game_data: &GameData, writer: W) -> Result<(), crate::Error> {
+  let mut writer = ::csv::Writer::from_writer(writer);
+  let provenance = &game_data.provenance;
+  writer.serialize(ProvenanceRow {
+    host: provenance.host.as_deref(),
+    repository: provenance.repository.as_ref().map(|(owner, repository)| format!("{owner}/{repository}")),
+    branch: provenance.branch.as_deref(),
+    commit: provenance.commit.as_deref(),
+    region: provenance.region,
+    fetched_at: provenance.fetched_at,
+    attribution: provenance.attribution()
+  })?;
+
+  writer.flush()?;
+  Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct OperatorRow<'a> {
+  id: &'a str,
+  name: &'a str,
+  rarity: u8,
+  profession: &'a str,
+  sub_profession: &'a str,
+  position: Position,
+  promotion: Promotion,
+  level: u32,
+  max_hp: u32,
+  atk: u32,
+  def: u32,
+  magic_resistance: f32,
+  deployment_cost: u32,
+  block_count: u8,
+  base_attack_time: f32,
+  redeploy_time: u32
+}
+
+#[derive(Debug, Serialize)]
+struct ItemRow<'a> {
+  id: &'a str,
+  name: &'a str,
+  rarity: u32,
+  item_class: ItemClass,
+  item_type: &'a str,
+  sort_id: i32
+}
+
+#[derive(Debug, Serialize)]
+struct BannerRow<'a> {
+  id: &'a str,
+  name: &'a str,
+  banner_type: HeadhuntingBannerType,
+  open_time: DateTime<Utc>,
+  close_time: DateTime<Utc>,
+  event_id: Option<&'a str>,
+  limited_operator_ids: String
+}
+
+#[derive(Debug, Serialize)]
+struct PromotionCostRow<'a> {
+  operator_id: &'a str,
+  operator_name: &'a str,
+  promotion: Promotion,
+  item_id: &'a str,
+  item_name: Option<&'a str>,
+  count: u32
+}
+
+#[derive(Debug, Serialize)]
+struct ProvenanceRow<'a> {
+  host: Option<&'a str>,
+  repository: Option<String>,
+  branch: Option<&'a str>,
+  commit: Option<&'a str>,
+  region: Option<Region>,
+  fetched_at: DateTime<Utc>,
+  attribution: String
+}
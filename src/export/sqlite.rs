@@ -0,0 +1,253 @@
+use rusqlite::{params, Connection};
+
+use std::path::Path;
+
+use crate::game_data::{GameData, ItemsCost, Operator, Promotion};
+use crate::options::Region;
+
+// times are stored as RFC 3339 text, and enums by their variant names
+const SCHEMA: &str = "
+DROP TABLE IF EXISTS operators;
+DROP TABLE IF EXISTS skills;
+DROP TABLE IF EXISTS skill_levels;
+DROP TABLE IF EXISTS items;
+DROP TABLE IF EXISTS costs;
+DROP TABLE IF EXISTS banners;
+DROP TABLE IF EXISTS banner_operators;
+DROP TABLE IF EXISTS events;
+DROP TABLE IF EXISTS provenance;
+
+CREATE TABLE operators (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL,
+  rarity INTEGER NOT NULL,
+  profession TEXT NOT NULL,
+  sub_profession TEXT NOT NULL,
+  position TEXT NOT NULL,
+  display_number TEXT NOT NULL,
+  nation_id TEXT,
+  group_id TEXT,
+  team_id TEXT,
+  potential_item_id TEXT
+);
+
+CREATE TABLE skills (
+  operator_id TEXT NOT NULL REFERENCES operators (id),
+  skill_index INTEGER NOT NULL,
+  id TEXT NOT NULL,
+  name TEXT NOT NULL,
+  PRIMARY KEY (operator_id, skill_index)
+);
+
+CREATE TABLE skill_levels (
+  operator_id TEXT NOT NULL,
+  skill_index INTEGER NOT NULL,
+  level INTEGER NOT NULL,
+  description TEXT,
+  sp_cost INTEGER NOT NULL,
+  initial_sp INTEGER NOT NULL,
+  duration REAL NOT NULL,
+  PRIMARY KEY (operator_id, skill_index, level),
+  FOREIGN KEY (operator_id, skill_index) REFERENCES skills (operator_id, skill_index)
+);
+
+CREATE TABLE items (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL,
+  rarity INTEGER NOT NULL,
+  item_class TEXT NOT NULL,
+  item_type TEXT NOT NULL,
+  sort_id INTEGER NOT NULL
+);
+
+CREATE TABLE costs (
+  operator_id TEXT NOT NULL REFERENCES operators (id),
+  kind TEXT NOT NULL,
+  skill_index INTEGER,
+  target TEXT NOT NULL,
+  item_id TEXT NOT NULL,
+  count INTEGER NOT NULL
+);
+
+CREATE TABLE banners (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL,
+  banner_type TEXT NOT NULL,
+  open_time TEXT NOT NULL,
+  close_time TEXT NOT NULL,
+  item_id TEXT,
+  event_id TEXT
+);
+
+CREATE TABLE banner_operators (
+  banner_id TEXT NOT NULL REFERENCES banners (id),
+  operator_id TEXT NOT NULL
+);
+
+CREATE TABLE events (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL,
+  event_type TEXT NOT NULL,
+  open_time TEXT NOT NULL,
+  close_time TEXT NOT NULL,
+  close_time_rewards TEXT NOT NULL,
+  is_rerun INTEGER NOT NULL
+);
+
+CREATE TABLE provenance (
+  host TEXT,
+  repository TEXT,
+  branch TEXT,
+  commit_sha TEXT,
+  region TEXT,
+  fetched_at TEXT NOT NULL,
+  attribution TEXT NOT NULL
+);
+";
+
+impl GameData {
+  /// Writes this [`GameData`] to a SQLite database at the given path, creating it if it does not exist.
+  /// Any tables from an earlier export are replaced.
+  ///
+  /// The database has the tables `operators`, `skills`, `skill_levels` (with mastery levels numbered 8 to 10),
+  /// `items`, `costs`, `banners`, `banner_operators`, `events` and `provenance`, which holds a single row describing
+  /// where the game data came from (see [`GameData::provenance`]). Every row of `costs` is one item needed
+  /// for an operator's upgrade, where `kind` is one of `promotion`, `skill_level`, `mastery` or `module`,
+  /// and `target` is the promotion, skill level, mastery level or module ID being upgraded to.
+  pub fn export_sqlite<P: AsRef<Path>>(&self, path: P) -> Result<(), crate::Error> {
+    let mut connection = Connection::open(path)?;
+    let transaction = connection.transaction()?;
+    transaction.execute_batch(SCHEMA)?;
+    insert_game_data(&transaction, self)?;
+    transaction.commit()?;
+    Ok(())
+  }
+}
+
+fn insert_game_data(connection: &Connection, game_data: &GameData) -> rusqlite::Result<()> {
+  let mut insert_operator = connection.prepare("INSERT INTO operators VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")?;
+  let mut insert_skill = connection.prepare("INSERT INTO skills VALUES (?, ?, ?, ?)")?;
+  let mut insert_skill_level = connection.prepare("INSERT INTO skill_levels VALUES (?, ?, ?, ?, ?, ?, ?)")?;
+  let mut insert_cost = connection.prepare("INSERT INTO costs VALUES (?, ?, ?, ?, ?, ?)")?;
+  for operator in game_data.operators.values() {
+    insert_operator.execute(params![
+      operator.id.as_str(),
+      operator.name,
+      operator.rarity.get(),
+      operator.profession.name(),
+      operator.sub_profession.name(),
+      format!("{:?}", operator.position),
+      operator.display_number,
+      operator.nation_id,
+      operator.group_id,
+      operator.team_id,
+      operator.potential_item_id.as_deref()
+    ])?;
+
+    for (skill_index, skill) in operator.skills.iter().enumerate() {
+      insert_skill.execute(params![operator.id.as_str(), skill_index, skill.id.as_str(), skill.name])?;
+      for (level, skill_level) in (1usize..).zip(skill.iter_levels()) {
+        insert_skill_level.execute(params![
+          operator.id.as_str(),
+          skill_index,
+          level,
+          skill_level.description,
+          skill_level.sp_cost,
+          skill_level.initial_sp,
+          skill_level.duration
+        ])?;
+      };
+    };
+
+    for_each_cost(operator, |kind, skill_index, target, cost| {
+      for (item_id, &count) in cost.iter() {
+        insert_cost.execute(params![operator.id.as_str(), kind, skill_index, target, item_id.as_str(), count])?;
+      };
+
+      Ok(())
+    })?;
+  };
+
+  let mut insert_item = connection.prepare("INSERT INTO items VALUES (?, ?, ?, ?, ?, ?)")?;
+  for item in game_data.items.values() {
+    insert_item.execute(params![
+      item.id.as_str(),
+      item.name,
+      item.rarity,
+      format!("{:?}", item.item_class),
+      item.item_type,
+      item.sort_id
+    ])?;
+  };
+
+  let mut insert_banner = connection.prepare("INSERT INTO banners VALUES (?, ?, ?, ?, ?, ?, ?)")?;
+  let mut insert_banner_operator = connection.prepare("INSERT INTO banner_operators VALUES (?, ?)")?;
+  for banner in game_data.headhunting_banners.iter() {
+    insert_banner.execute(params![
+      banner.id,
+      banner.name,
+      format!("{:?}", banner.banner_type),
+      banner.open_time.to_rfc3339(),
+      banner.close_time.to_rfc3339(),
+      banner.item_id.as_deref(),
+      banner.event_id
+    ])?;
+
+    for operator_id in banner.rules.limited_operator_ids.iter() {
+      insert_banner_operator.execute(params![banner.id, operator_id.as_str()])?;
+    };
+  };
+
+  let mut insert_event = connection.prepare("INSERT INTO events VALUES (?, ?, ?, ?, ?, ?, ?)")?;
+  for event in game_data.events.iter() {
+    insert_event.execute(params![
+      event.id,
+      event.name,
+      format!("{:?}", event.event_type),
+      event.open_time.to_rfc3339(),
+      event.close_time.to_rfc3339(),
+      event.close_time_rewards.to_rfc3339(),
+      event.is_rerun
+    ])?;
+  };
+
+  let provenance = &game_data.provenance;
+  connection.execute("INSERT INTO provenance VALUES (?, ?, ?, ?, ?, ?, ?)", params![
+    provenance.host,
+    provenance.repository.as_ref().map(|(owner, repository)| format!("{owner}/{repository}")),
+    provenance.branch,
+    provenance.commit,
+    provenance.region.map(Region::to_str),
+    provenance.fetched_at.to_rfc3339(),
+    provenance.attribution()
+  ])?;
+
+  Ok(())
+}
+
+/// Calls `f` with the kind, skill index (for masteries), target and cost of every upgrade the operator has.
+fn for_each_cost<F>(operator: &Operator, mut f: F) -> rusqlite::Result<()>
+where F: FnMut(&str, Option<usize>, String, &ItemsCost) -> rusqlite::Result<()> {
+  for promotion in [Promotion::Elite1, Promotion::Elite2] {
+    if let Some(operator_promotion) = operator.promotions.get(promotion) {
+      f("promotion", None, format!("{promotion:?}"), &operator_promotion.upgrade_cost)?;
+    };
+  };
+
+  // the first upgrade is from skill level 1 to 2
+  for (skill_level, upgrade) in (2..).zip(operator.skill_level_upgrades.iter()) {
+    f("skill_level", None, format!("{skill_level}"), &upgrade.upgrade_cost)?;
+  };
+
+  for (skill_index, skill) in operator.skills.iter().enumerate() {
+    for (mastery, skill_mastery) in (1..).zip(skill.mastery.iter().flatten()) {
+      f("mastery", Some(skill_index), format!("{mastery}"), &skill_mastery.upgrade_cost)?;
+    };
+  };
+
+  for module in operator.modules.iter() {
+    f("module", None, module.id.to_string(), &module.upgrade_cost)?;
+  };
+
+  Ok(())
+}
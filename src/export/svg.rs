@@ -0,0 +1,133 @@
+//! Renders [`AttackRange`]s as small SVG diagrams, similar to the range previews shown in game.
+//!
+//! ```
+//! # use ak_data::game_data::AttackRange;
+//! # use ak_data::export::svg::{Facing, SvgOptions};
+//! # fn f(range: &AttackRange) {
+//! let options = SvgOptions { facing: Some(Facing::Up), ..SvgOptions::default() };
+//! let svg = ak_data::export::svg::render_range(range, &options);
+//! # }
+//! ```
+
+use mint::Point2;
+
+use std::fmt::Write;
+
+use crate::game_data::AttackRange;
+
+/// The direction an operator is facing when deployed.
+/// Ranges in the game data face [`Facing::Right`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Facing {
+  Up,
+  Down,
+  Left,
+  #[default]
+  Right
+}
+
+impl Facing {
+  /// Rotates a grid tile of a range facing right so that it faces this direction.
+  /// Rows are counted downwards, as they are drawn.
+  pub fn rotate(self, point: Point2<i32>) -> Point2<i32> {
+    let Point2 { x, y } = point;
+    match self {
+      Facing::Up => Point2 { x: y, y: -x },
+      Facing::Down => Point2 { x: -y, y: x },
+      Facing::Left => Point2 { x: -x, y: -y },
+      Facing::Right => Point2 { x, y }
+    }
+  }
+}
+
+/// Controls how [`render_range`] draws a range.
+/// Colors may be anything SVG accepts, such as `#27a6f3` or `white`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SvgOptions {
+  /// The width and height of each grid tile, in pixels.
+  pub tile_size: u32,
+  /// The space between grid tiles, in pixels.
+  pub gap: u32,
+  /// The fill color of tiles in range.
+  pub range_color: String,
+  /// The outline color of tiles out of range, or `None` to leave them blank.
+  pub empty_color: Option<String>,
+  /// The fill color of the operator's own tile, or `None` to draw it like any other tile.
+  pub operator_color: Option<String>,
+  /// The direction to rotate the range towards, drawing an arrow on the operator's tile.
+  /// If `None`, the range is drawn facing right without an arrow.
+  pub facing: Option<Facing>
+}
+
+impl Default for SvgOptions {
+  fn default() -> Self {
+    SvgOptions {
+      tile_size: 16,
+      gap: 2,
+      range_color: "#27a6f3".to_owned(),
+      empty_color: Some("#808080".to_owned()),
+      operator_color: Some("#ffcc00".to_owned()),
+      facing: None
+    }
+  }
+}
+
+/// Renders an attack range as a standalone SVG document, covering the range's bounding box.
+pub fn render_range(range: &AttackRange, options: &SvgOptions) -> String {
+  let facing = options.facing.unwrap_or_default();
+  let rotated = AttackRange { points: range.iter().map(|&point| facing.rotate(point)).collect() };
+  let (min, max) = rotated.bounding_box();
+
+  let step = options.tile_size + options.gap;
+  let width = rotated.width() * step - options.gap;
+  let height = rotated.height() * step - options.gap;
+  let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">");
+  for y in min.y..=max.y {
+    for x in min.x..=max.x {
+      let left = (x - min.x) as u32 * step;
+      let top = (y - min.y) as u32 * step;
+      let size = options.tile_size;
+
+      let is_origin = x == 0 && y == 0;
+      let fill = match &options.operator_color {
+        Some(operator_color) if is_origin => Some(operator_color),
+        _ if rotated.contains([x, y]) => Some(&options.range_color),
+        _ => None
+      };
+
+      if let Some(fill) = fill {
+        write!(svg, "<rect x=\"{left}\" y=\"{top}\" width=\"{size}\" height=\"{size}\" fill=\"{fill}\"/>").unwrap();
+      } else if let Some(stroke) = &options.empty_color {
+        // inset by half of the stroke, so that the outline stays within the tile
+        let (left, top, size) = (left as f32 + 0.5, top as f32 + 0.5, size as f32 - 1.0);
+        write!(svg, "<rect x=\"{left}\" y=\"{top}\" width=\"{size}\" height=\"{size}\" fill=\"none\" stroke=\"{stroke}\"/>").unwrap();
+      };
+    };
+  };
+
+  if let Some(facing) = options.facing {
+    let left = (-min.x) as u32 * step;
+    let top = (-min.y) as u32 * step;
+    svg.push_str(&render_arrow(facing, left as f32, top as f32, options.tile_size as f32));
+  };
+
+  svg.push_str("</svg>");
+  svg
+}
+
+/// Draws a triangle in the given tile, pointing towards `facing`.
+fn render_arrow(facing: Facing, left: f32, top: f32, size: f32) -> String {
+  // the triangle for facing right, relative to the center of the tile
+  let points = [(0.3, 0.0), (-0.2, -0.25), (-0.2, 0.25)].map(|(x, y): (f32, f32)| {
+    let (x, y) = match facing {
+      Facing::Up => (y, -x),
+      Facing::Down => (-y, x),
+      Facing::Left => (-x, -y),
+      Facing::Right => (x, y)
+    };
+
+    format!("{},{}", left + (x + 0.5) * size, top + (y + 0.5) * size)
+  });
+
+  format!("<polygon points=\"{}\" fill=\"black\"/>", points.join(" "))
+}
@@ -1,33 +1,43 @@
 mod activity_table;
+mod battle_equip_table;
 mod building_data;
 mod character_meta_table;
 mod character_table;
 mod equip_table;
 mod gacha_table;
+mod gamedata_const;
 mod handbook_info_table;
 mod item_table;
 mod range_table;
 mod skill_table;
 mod skin_table;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use once_cell::sync::Lazy;
 use regex::{Regex, Captures};
 use serde::de::{Deserialize, DeserializeOwned, Deserializer};
 
 use self::activity_table::ActivityTable;
+use self::battle_equip_table::BattleEquipTable;
 use self::building_data::BuildingData;
 use self::character_meta_table::CharacterMetaTable;
 use self::character_table::CharacterTable;
 use self::equip_table::EquipTable;
 use self::gacha_table::GachaTable;
+use self::gamedata_const::GamedataConst;
 use self::handbook_info_table::HandbookInfoTable;
 use self::item_table::ItemTable;
 use self::range_table::RangeTable;
-use self::skill_table::SkillTable;
+use self::skill_table::{SkillTable, SkillTableMapped};
 use self::skin_table::SkinTable;
-use crate::game_data::{GameData, Promotion, PromotionAndLevel};
-use crate::options::Options;
+use crate::game_data::{
+  ConversionReport, Event, GameData, GameDataIndex, HeadhuntingBanner, ItemId, ItemsCost, LoadWarning, Operator,
+  OperatorId, Profession, Promotion, PromotionAndLevel, Provenance, SchemaReport, SkipReason, SubProfession,
+  TableSchemaReport, TableSelection, UnknownField
+};
+#[cfg(feature = "async")]
+use crate::options::DataSource;
+use crate::TableError;
 
 use std::borrow::Cow;
 use std::collections::HashMap;
@@ -44,12 +54,77 @@ macro_rules! datafiles {
     }
 
     impl $Ident {
-      $sv async fn from_local(gamedata_dir: &Path) -> Result<Self, $crate::Error> {
-        Ok($Ident { $($field: $crate::options::get_data_file_local::<$Field>(gamedata_dir).await?,)* })
+      /// The locations of every data file, relative to the `gamedata` folder.
+      $sv const LOCATIONS: &'static [&'static str] = &[$(<$Field as DataFile>::LOCATION),*];
+      /// The identifiers of every data file, in the same order as `LOCATIONS`.
+      $sv const IDENTIFIERS: &'static [&'static str] = &[$(<$Field as DataFile>::IDENTIFIER),*];
+
+      // tables that are not selected are left empty instead of being loaded
+      #[cfg(feature = "async")]
+      $sv async fn from_local(gamedata_dir: &Path, tables: &TableSelection) -> Result<Self, $crate::Error> {
+        // every selected data file is read and parsed on its own blocking thread
+        let ($($field,)*) = tokio::try_join!($(async {
+          if tables.includes_table(<$Field as DataFile>::IDENTIFIER) {
+            $crate::options::get_data_file_local::<$Field>(gamedata_dir).await
+          } else {
+            Ok(<$Field>::default())
+          }
+        }),*)?;
+
+        Ok($Ident { $($field,)* })
       }
 
-      $sv async fn from_remote(options: &Options) -> Result<Self, $crate::Error> {
-        Ok($Ident { $($field: $crate::options::get_data_file_remote::<$Field>(options).await?,)* })
+      #[cfg(feature = "async")]
+      $sv async fn from_source<S: DataSource>(source: &S, tables: &TableSelection) -> Result<Self, $crate::Error> {
+        let total = Self::IDENTIFIERS.iter().filter(|identifier| tables.includes_table(identifier)).count();
+        let tracker = $crate::options::ProgressTracker::new(source.progress(), total);
+        let semaphore = tokio::sync::Semaphore::new(source.max_concurrent_requests().max(1));
+        // every selected data file is fetched at once, limited by the semaphore
+        let ($($field,)*) = tokio::try_join!($(async {
+          if tables.includes_table(<$Field as DataFile>::IDENTIFIER) {
+            $crate::options::get_data_file_from_source::<$Field, S>(source, &tracker, &semaphore).await
+          } else {
+            Ok(<$Field>::default())
+          }
+        }),*)?;
+
+        Ok($Ident { $($field,)* })
+      }
+
+      // `read` is given the location of each selected data file, and should return its contents
+      $sv fn from_read_fn<F, B>(tables: &TableSelection, mut read: F) -> Result<Self, $crate::Error>
+      where F: FnMut(&str) -> Result<B, $crate::Error>, B: AsRef<[u8]> {
+        Ok($Ident { $($field: if tables.includes_table(<$Field as DataFile>::IDENTIFIER) {
+          let bytes = read(<$Field as DataFile>::LOCATION)
+            .map_err(|error| $crate::format::fetch_error(<$Field as DataFile>::IDENTIFIER, error))?;
+          $crate::format::parse_data_file(bytes.as_ref())?
+        } else {
+          <$Field>::default()
+        },)* })
+      }
+
+      // `read` is given the location of every data file, and should return its contents
+      $sv fn check_schema<F, B>(mut read: F) -> Result<SchemaReport, $crate::Error>
+      where F: FnMut(&str) -> Result<B, $crate::Error>, B: AsRef<[u8]> {
+        let mut tables = $crate::Map::new();
+        $(let bytes = read(<$Field as DataFile>::LOCATION)
+          .map_err(|error| $crate::format::fetch_error(<$Field as DataFile>::IDENTIFIER, error))?;
+        tables.insert(<$Field as DataFile>::IDENTIFIER.to_owned(), <$Field as DataFile>::check_schema(bytes.as_ref()));)*
+
+        Ok(SchemaReport { tables })
+      }
+
+      $sv fn from_local_sync(gamedata_dir: &Path, tables: &TableSelection) -> Result<Self, $crate::Error> {
+        // every selected data file is read and parsed on its own thread
+        std::thread::scope(|scope| {
+          $(let $field = scope.spawn(|| if tables.includes_table(<$Field as DataFile>::IDENTIFIER) {
+            $crate::options::get_data_file_local_sync::<$Field>(gamedata_dir)
+          } else {
+            Ok(<$Field>::default())
+          });)*
+
+          Ok($Ident { $($field: $field.join().unwrap_or_else(|payload| std::panic::resume_unwind(payload))?,)* })
+        })
       }
     }
   };
@@ -59,11 +134,13 @@ datafiles! {
   #[derive(Debug)]
   pub(crate) struct DataFiles {
     activity_table: ActivityTable,
+    battle_equip_table: BattleEquipTable,
     building_data: BuildingData,
     character_meta_table: CharacterMetaTable,
     character_table: CharacterTable,
     equip_table: EquipTable,
     gacha_table: GachaTable,
+    gamedata_const: GamedataConst,
     handbook_info_table: HandbookInfoTable,
     item_table: ItemTable,
     range_table: RangeTable,
@@ -73,46 +150,207 @@ datafiles! {
 }
 
 impl DataFiles {
-  pub(crate) fn into_game_data(mut self, last_updated: Option<DateTime<Utc>>) -> GameData {
+  /// Malformed entries fail the whole conversion, unless `lenient` is true,
+  /// in which case they are skipped and listed in [`GameData::load_warnings`].
+  /// Every skipped character is listed in [`GameData::conversion_report`].
+  /// `tables` is the selection these files were loaded with, deselected tables are not treated as missing entries.
+  pub(crate) fn into_game_data(
+    mut self, tables: &TableSelection, last_updated: Option<DateTime<Utc>>, provenance: Provenance, lenient: bool
+  ) -> Result<GameData, crate::Error> {
+    let mut load_warnings = Vec::new();
+    let mut conversion_report = ConversionReport::default();
+    for (id, error) in self.character_table.take_malformed() {
+      if !lenient { return Err(data_file_error(CharacterTable::IDENTIFIER, Some(&id), error)) };
+      let message = format!("{} at {}", error.inner(), error.path());
+      load_warnings.push(LoadWarning::new(CharacterTable::IDENTIFIER, &id, &message));
+      conversion_report.skipped.insert(OperatorId::new(id), SkipReason::Malformed(message));
+    };
+
+    for id in self.character_table.take_unobtainable() {
+      conversion_report.skipped.insert(OperatorId::new(id), SkipReason::Unobtainable);
+    };
+
     let alters = self.character_meta_table.into_alters();
     let mut skin_table_mapped = self.skin_table.into_skin_table_mapped();
-    let operators = recollect_filter(self.character_table, |(id, character)| {
-      Some((id.clone(), {
-        character.into_operator(id, self::character_table::AdditionalData {
-          building_data: &self.building_data,
-          equip_table: &mut self.equip_table,
-          handbook_info_table: &mut self.handbook_info_table,
-          skill_table: &self.skill_table,
-          skin_table: &mut skin_table_mapped
-        })?
-      }))
+    let skill_uses = self::character_table::count_skill_uses(&self.character_table);
+    let mut skill_table_mapped = SkillTableMapped::new(self.skill_table, skill_uses);
+    let operators: crate::Map<OperatorId, Operator> = recollect_filter(self.character_table.into_characters(), |(id, character)| {
+      let operator = character.into_operator(id.clone(), self::character_table::AdditionalData {
+        building_data: &self.building_data,
+        battle_equip_table: &mut self.battle_equip_table,
+        equip_table: &mut self.equip_table,
+        handbook_info_table: &mut self.handbook_info_table,
+        operator_files: tables.includes_table(HandbookInfoTable::IDENTIFIER),
+        skill_table: &mut skill_table_mapped,
+        skin_table: &mut skin_table_mapped
+      });
+
+      match operator {
+        Ok(operator) => Some((OperatorId::new(id), operator)),
+        Err(reason) => {
+          if !reason.is_expected() {
+            load_warnings.push(LoadWarning::new(CharacterTable::IDENTIFIER, &id, reason.to_string()));
+          };
+
+          conversion_report.skipped.insert(OperatorId::new(id), reason);
+          None
+        }
+      }
     });
 
-    let items = self.item_table.into_items();
-    let buildings = self.building_data.into_buildings();
+    let mut operators_by_profession = crate::Map::<Profession, Vec<OperatorId>>::new();
+    let mut operators_by_sub_profession = crate::Map::<SubProfession, Vec<OperatorId>>::new();
+    for operator in operators.values() {
+      operators_by_profession.entry(operator.profession).or_default().push(operator.id.clone());
+      operators_by_sub_profession.entry(operator.sub_profession.clone()).or_default().push(operator.id.clone());
+    };
+
+    let items = self.item_table.into_items(&mut load_warnings);
+    let constants = self.gamedata_const.into_game_constants();
+    let (buildings, furniture) = self.building_data.into_buildings_and_furniture(&mut load_warnings);
     let ranges = recollect_map(self.range_table, |entry| entry.into_attack_range());
-    let (recruitment_tags, mut headhunting_banners) = self.gacha_table.into_tags_and_banners();
+    let recruitable_operator_names = match tables.recruitment {
+      true => self.gacha_table.recruitable_operator_names(&mut load_warnings),
+      false => Vec::new()
+    };
+    let recruitment_time_brackets = self.gacha_table.recruitment_time_brackets();
+    let recruitable_operators = recollect_filter(operators.values(), |operator| {
+      recruitable_operator_names.contains(&operator.name).then(|| operator.id.clone())
+    });
+    let (recruitment_tags, mut headhunting_banners, beginner_banners, special_pools) =
+      self.gacha_table.into_tags_and_banners(&mut load_warnings);
+    let mut operators_by_recruitment_tag = crate::Map::<u32, Vec<OperatorId>>::new();
+    for operator in operators.values() {
+      for tag_id in operator.iter_recruitment_tags(&recruitment_tags) {
+        operators_by_recruitment_tag.entry(tag_id).or_default().push(operator.id.clone());
+      };
+    };
+
     let mut events = self.activity_table.into_events();
     headhunting_banners.sort_unstable_by_key(|banner| banner.open_time);
     events.sort_unstable_by_key(|event| event.open_time);
+    link_banners_to_events(&mut headhunting_banners, &events);
 
-    GameData {
+    let mut game_data = GameData {
       last_updated,
+      provenance,
+      data_version: None,
       alters,
       operators,
+      operators_by_profession,
+      operators_by_sub_profession,
       items,
       buildings,
+      furniture,
       ranges,
       recruitment_tags,
+      operators_by_recruitment_tag,
+      recruitable_operators,
+      recruitment_time_brackets,
       headhunting_banners,
-      events
-    }
+      beginner_banners,
+      special_pools,
+      events,
+      constants,
+      index: GameDataIndex::default(),
+      load_warnings,
+      conversion_report
+    };
+
+    game_data.rebuild_index();
+    Ok(game_data)
   }
 }
 
-pub(crate) trait DataFile: DeserializeOwned {
+// banners usually open within a few hours of the event they run alongside
+fn link_banners_to_events(headhunting_banners: &mut [HeadhuntingBanner], events: &[Event]) {
+  let tolerance = Duration::days(1).num_seconds();
+  for banner in headhunting_banners.iter_mut() {
+    banner.event_id = events.iter()
+      .map(|event| (event, (event.open_time - banner.open_time).num_seconds().abs()))
+      .filter(|&(_, distance)| distance <= tolerance)
+      .min_by_key(|&(_, distance)| distance)
+      .map(|(event, _)| event.id.clone());
+  };
+}
+
+pub(crate) trait DataFile: DeserializeOwned + Default {
   const LOCATION: &'static str;
   const IDENTIFIER: &'static str;
+
+  /// Compares the contents of this data file against the fields the crate declares for it.
+  fn check_schema(bytes: &[u8]) -> TableSchemaReport {
+    check_data_file_schema::<Self>(bytes)
+  }
+}
+
+/// Deserializes the contents of a data file, with simd-json if the `simd-json` feature is enabled.
+/// Errors include the identifier of the data file and the path to the value that could not be parsed.
+#[cfg(not(feature = "simd-json"))]
+pub(crate) fn parse_data_file<T: DataFile>(bytes: &[u8]) -> Result<T, crate::Error> {
+  let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+  let value = serde_path_to_error::deserialize(&mut deserializer)
+    .map_err(|error| data_file_error(T::IDENTIFIER, None, error))?;
+  deserializer.end().map_err(|error| decode_error(T::IDENTIFIER, ".".to_owned(), error))?;
+  Ok(value)
+}
+
+/// Deserializes the contents of a data file, with simd-json if the `simd-json` feature is enabled.
+/// Errors include the identifier of the data file and the path to the value that could not be parsed.
+#[cfg(feature = "simd-json")]
+pub(crate) fn parse_data_file<T: DataFile>(bytes: &[u8]) -> Result<T, crate::Error> {
+  // simd-json parses in place, so it needs its own copy of the contents
+  let mut bytes = bytes.to_vec();
+  let mut deserializer = simd_json::Deserializer::from_slice(&mut bytes)
+    .map_err(|error| decode_error(T::IDENTIFIER, ".".to_owned(), error))?;
+  serde_path_to_error::deserialize(&mut deserializer)
+    .map_err(|error| data_file_error(T::IDENTIFIER, None, error))
+}
+
+/// Deserializes a data file as `T` with serde_json, recording every field that `T` does not declare.
+/// Unlike [`parse_data_file`], errors are recorded in the report instead of being returned.
+fn check_data_file_schema<T: DeserializeOwned>(bytes: &[u8]) -> TableSchemaReport {
+  let mut unknown_fields = crate::Map::<String, UnknownField>::new();
+  let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+  let result = serde_path_to_error::deserialize::<_, T>(serde_ignored::Deserializer::new(&mut deserializer, |path| {
+    // unknown fields are grouped by name, since their full paths include the ID of every entry they appear in
+    let field_name = match &path {
+      serde_ignored::Path::Map { key, .. } => key.clone(),
+      _ => path.to_string()
+    };
+
+    unknown_fields.entry(field_name)
+      .or_insert_with(|| UnknownField { occurrences: 0, example_path: path.to_string() })
+      .occurrences += 1;
+  }));
+
+  let error = result.err().map(|error| format!("{} at {}", error.inner(), error.path()));
+  TableSchemaReport { unknown_fields, error }
+}
+
+/// Converts a deserialization error into a [`TableError::Decode`].
+/// If the error came from within a single entry, `entry` should be the ID of that entry,
+/// and the error is converted into a [`TableError::Convert`] instead.
+fn data_file_error<E>(identifier: &'static str, entry: Option<&str>, error: serde_path_to_error::Error<E>) -> crate::Error
+where E: std::error::Error + Send + Sync + 'static {
+  let path = error.path().to_string();
+  match entry {
+    Some(entry) => {
+      let source = TableError::Convert { entry: entry.to_owned(), path, source: Box::new(error.into_inner()) };
+      crate::Error::Table { table: identifier, source }
+    },
+    None => decode_error(identifier, path, error.into_inner())
+  }
+}
+
+fn decode_error<E>(identifier: &'static str, path: String, error: E) -> crate::Error
+where E: std::error::Error + Send + Sync + 'static {
+  crate::Error::Table { table: identifier, source: TableError::Decode { path, source: Box::new(error) } }
+}
+
+/// Converts an error from reading or downloading a data file into a [`TableError::Fetch`].
+pub(crate) fn fetch_error(identifier: &'static str, error: crate::Error) -> crate::Error {
+  crate::Error::Table { table: identifier, source: TableError::Fetch(Box::new(error)) }
 }
 
 // array::zip is not stabilized :(
@@ -162,6 +400,54 @@ where T: Deserialize<'de> {
   })
 }
 
+/// Deserializes a map one entry at a time, converting each entry with `f` and dropping entries it returns `None` for.
+/// Unlike deserializing the whole map first, the unconverted entries are never all held in memory at once.
+fn deserialize_map_filtered<'de, D, V, U, F>(deserializer: D, f: F) -> Result<HashMap<String, U>, D::Error>
+where D: Deserializer<'de>, V: Deserialize<'de>, F: FnMut(&str, V) -> Option<U> {
+  struct MapFilteredVisitor<V, F> {
+    f: F,
+    marker: std::marker::PhantomData<V>
+  }
+
+  impl<'de, V, U, F> serde::de::Visitor<'de> for MapFilteredVisitor<V, F>
+  where V: Deserialize<'de>, F: FnMut(&str, V) -> Option<U> {
+    type Value = HashMap<String, U>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+      f.write_str("a map")
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(mut self, mut map: A) -> Result<Self::Value, A::Error> {
+      let mut entries = HashMap::with_capacity(map.size_hint().unwrap_or(0));
+      while let Some((key, value)) = map.next_entry::<String, V>()? {
+        if let Some(value) = (self.f)(&key, value) {
+          entries.insert(key, value);
+        };
+      };
+
+      Ok(entries)
+    }
+  }
+
+  deserializer.deserialize_map(MapFilteredVisitor { f, marker: std::marker::PhantomData })
+}
+
+/// Implemented by raw enums with an `Other` variant for values they do not recognize,
+/// so that a value added upstream does not fail the whole data file. See [`deserialize_or_other`].
+trait OtherVariant: DeserializeOwned {
+  fn other(value: String) -> Self;
+}
+
+/// Deserializes a string as an [`OtherVariant`], falling back to its `Other` variant if the string is not recognized.
+fn deserialize_or_other<'de, D: Deserializer<'de>, T: OtherVariant>(deserializer: D) -> Result<T, D::Error> {
+  use serde::de::IntoDeserializer;
+  use serde::de::value::{Error as ValueError, StrDeserializer};
+
+  let value = String::deserialize(deserializer)?;
+  let value_deserializer: StrDeserializer<ValueError> = value.as_str().into_deserializer();
+  Ok(T::deserialize(value_deserializer).unwrap_or_else(|_| T::other(value)))
+}
+
 #[inline]
 fn deserialize_negative_int<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u32>, D::Error> {
   let value = i32::deserialize(deserializer)?;
@@ -169,19 +455,21 @@ fn deserialize_negative_int<'de, D: Deserializer<'de>>(deserializer: D) -> Resul
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct ItemCost {
   #[serde(rename = "id")]
-  item_id: String,
+  item_id: ItemId,
   count: u32
 }
 
 impl ItemCost {
-  fn convert(item_cost: Vec<Self>) -> crate::Map<String, u32> {
+  fn convert(item_cost: Vec<Self>) -> ItemsCost {
     recollect(item_cost, |item| (item.item_id, item.count))
   }
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct CharCondition {
   phase: CharPhase,
   level: u32
@@ -318,6 +606,11 @@ where I: IntoIterator<Item = T>, C: FromIterator<U>, F: FnMut(T) -> Option<U> {
   recollect(i, f)
 }
 
+fn recollect_result<T, U, E, I, C, F>(i: I, f: F) -> Result<C, E>
+where I: IntoIterator<Item = T>, C: FromIterator<U>, F: FnMut(T) -> Result<U, E> {
+  recollect(i, f)
+}
+
 fn recollect_filter<T, U, I, C, F>(i: I, f: F) -> C
 where I: IntoIterator<Item = T>, C: FromIterator<U>, F: FnMut(T) -> Option<U> {
   i.into_iter().filter_map(f).collect()
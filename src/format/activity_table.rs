@@ -10,7 +10,8 @@ impl DataFile for ActivityTable {
   const IDENTIFIER: &'static str = "activity_table";
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub(super) struct ActivityTable {
   #[serde(rename = "basicInfo")]
   basic_info: HashMap<String, ActivityTableBasicInfoEntry>
@@ -18,14 +19,18 @@ pub(super) struct ActivityTable {
 
 impl ActivityTable {
   pub(super) fn into_events(self) -> Vec<Event> {
-    recollect_filter(self.basic_info, |(_, basic_info_entry)| basic_info_entry.into_event())
+    recollect(self.basic_info, |(_, basic_info_entry)| basic_info_entry.into_event())
   }
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct ActivityTableBasicInfoEntry {
   id: String,
+  #[serde(rename = "type")]
+  activity_type: String,
   #[serde(rename = "displayType")]
+  #[serde(default)]
   kind: Option<ActivityTableBasicInfoKind>,
   name: String,
   #[serde(rename = "startTime")]
@@ -42,16 +47,33 @@ struct ActivityTableBasicInfoEntry {
 }
 
 impl ActivityTableBasicInfoEntry {
-  fn into_event(self) -> Option<Event> {
-    Some(Event {
+  fn into_event(self) -> Event {
+    let event_type = match self.kind.and_then(ActivityTableBasicInfoKind::into_event_type) {
+      Some(event_type) => event_type,
+      None => classify_activity_type(&self.id, &self.activity_type)
+    };
+
+    Event {
       id: self.id,
       name: self.name,
-      event_type: self.kind?.into_event_type(),
+      event_type,
+      activity_type: self.activity_type,
       open_time: self.start_time,
       close_time: self.end_time,
       close_time_rewards: self.end_time_rewards,
       is_rerun: self.is_rerun
-    })
+    }
+  }
+}
+
+// events without a story display type are classified based on their activity type
+fn classify_activity_type(id: &str, activity_type: &str) -> EventType {
+  match activity_type {
+    "LOGIN_ONLY" => EventType::Login,
+    t if t.starts_with("CHECKIN") => EventType::Checkin,
+    t if t.contains("LINKAGE") => EventType::Collab,
+    t if t.contains("APRIL_FOOL") || t.ends_with("FUN") || id.ends_with("fun") => EventType::AprilFools,
+    _ => EventType::Other
   }
 }
 
@@ -65,15 +87,18 @@ enum ActivityTableBasicInfoKind {
   SideStory,
   // Also known as "Vignettes".
   #[serde(rename = "MINISTORY")]
-  MiniStory
+  MiniStory,
+  #[serde(other)]
+  Other
 }
 
 impl ActivityTableBasicInfoKind {
-  fn into_event_type(self) -> EventType {
+  fn into_event_type(self) -> Option<EventType> {
     match self {
-      ActivityTableBasicInfoKind::Branchline => EventType::Intermezzi,
-      ActivityTableBasicInfoKind::SideStory => EventType::SideStory,
-      ActivityTableBasicInfoKind::MiniStory => EventType::Vignette
+      ActivityTableBasicInfoKind::Branchline => Some(EventType::Intermezzi),
+      ActivityTableBasicInfoKind::SideStory => Some(EventType::SideStory),
+      ActivityTableBasicInfoKind::MiniStory => Some(EventType::Vignette),
+      ActivityTableBasicInfoKind::Other => None
     }
   }
 }
@@ -0,0 +1,101 @@
+use crate::format::*;
+use crate::game_data::OperatorModuleStage;
+
+use std::collections::HashMap;
+
+impl DataFile for BattleEquipTable {
+  const LOCATION: &'static str = "excel/battle_equip_table.json";
+  const IDENTIFIER: &'static str = "battle_equip_table";
+}
+
+pub(super) type BattleEquipTable = HashMap<String, BattleEquipTableEquip>;
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub(super) struct BattleEquipTableEquip {
+  phases: Vec<BattleEquipTablePhase>
+}
+
+impl BattleEquipTableEquip {
+  pub(super) fn into_operator_module_stages(self) -> Vec<OperatorModuleStage> {
+    recollect(self.phases, BattleEquipTablePhase::into_operator_module_stage)
+  }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct BattleEquipTablePhase {
+  #[serde(rename = "equipLevel")]
+  level: u32,
+  // omitted fields: attributeBlackboard, tokenAttributeBlackboard
+  parts: Vec<BattleEquipTablePart>
+}
+
+impl BattleEquipTablePhase {
+  fn into_operator_module_stage(self) -> OperatorModuleStage {
+    // only the candidate for the lowest potential is used, the same way skill descriptions ignore potential
+    let candidate = self.parts.into_iter()
+      .find_map(|part| part.override_trait.candidates.into_iter().next());
+    let (trait_addition, trait_override) = match candidate {
+      Some(candidate) => candidate.into_descriptions(),
+      None => (None, None)
+    };
+
+    OperatorModuleStage {
+      level: self.level,
+      trait_addition,
+      trait_override
+    }
+  }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct BattleEquipTablePart {
+  // omitted fields: resKey, target, isToken, addOrOverrideTalentDataBundle
+  #[serde(rename = "overrideTraitDataBundle")]
+  override_trait: BattleEquipTableTraitBundle
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct BattleEquipTableTraitBundle {
+  #[serde(deserialize_with = "deserialize_or_default")]
+  candidates: Vec<BattleEquipTableTraitCandidate>
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct BattleEquipTableTraitCandidate {
+  #[serde(rename = "additionalDescription")]
+  #[serde(default)]
+  additional_description: Option<String>,
+  // the typo is present in the game data
+  #[serde(rename = "overrideDescripton")]
+  #[serde(default)]
+  override_description: Option<String>,
+  #[serde(deserialize_with = "deserialize_or_default")]
+  blackboard: Vec<BattleEquipTableBlackboard>
+}
+
+impl BattleEquipTableTraitCandidate {
+  /// Returns the added trait text and the overriding trait text, with templates filled in.
+  fn into_descriptions(self) -> (Option<String>, Option<String>) {
+    let blackboard = self.blackboard.into_iter()
+      .map(|item| (item.key.to_lowercase(), item.value))
+      .collect::<HashMap<String, f32>>();
+    let apply = |description: Option<String>| {
+      description.filter(|description| !description.is_empty())
+        .map(|description| apply_templates(&description, blackboard.clone()))
+    };
+
+    (apply(self.additional_description), apply(self.override_description))
+  }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct BattleEquipTableBlackboard {
+  key: String,
+  value: f32
+}
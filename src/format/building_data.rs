@@ -1,6 +1,6 @@
 use crate::format::*;
 use crate::game_data::{
-  Building, BuildingType, BuildingUpgrade,
+  BaseSkillEffect, Building, BuildingType, BuildingUpgrade, Furniture, LoadWarning,
   OperatorBaseSkill, OperatorBaseSkillCategory, OperatorBaseSkillPhase
 };
 
@@ -11,20 +11,44 @@ impl DataFile for BuildingData {
   const IDENTIFIER: &'static str = "building_data";
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub(super) struct BuildingData {
   rooms: HashMap<String, BuildingDataRoom>,
   chars: HashMap<String, BuildingDataChar>,
-  buffs: HashMap<String, BuildingDataBuff>
+  buffs: HashMap<String, BuildingDataBuff>,
+  #[serde(rename = "customData")]
+  custom_data: BuildingDataCustomData
 }
 
 impl BuildingData {
-  pub(super) fn into_buildings(self) -> crate::Map<BuildingType, Building> {
-    self.rooms.into_values()
-      .map(|building_data_room| {
-        (building_data_room.id.into_building_type(), building_data_room.into_building())
-      })
-      .collect()
+  /// Rooms, and base skill phases, with a room type or buff category that is not recognized are left out.
+  pub(super) fn into_buildings_and_furniture(
+    self, load_warnings: &mut Vec<LoadWarning>
+  ) -> (crate::Map<BuildingType, Building>, crate::Map<String, Furniture>) {
+    for (id, building_data_buff) in self.buffs.iter() {
+      if let BuildingDataRoomId::Other(room_type) = &building_data_buff.room_type {
+        let message = format!("unknown room type {room_type}, base skill phases using it were skipped");
+        load_warnings.push(LoadWarning::new(BuildingData::IDENTIFIER, id, message));
+      };
+
+      if let BuildingDataBuffCategory::Other(category) = &building_data_buff.category {
+        let message = format!("unknown buff category {category}, base skill phases using it were skipped");
+        load_warnings.push(LoadWarning::new(BuildingData::IDENTIFIER, id, message));
+      };
+    };
+
+    let buildings = recollect_filter(self.rooms, |(id, building_data_room)| match building_data_room.into_building() {
+      Ok(building) => Some((building.building_type, building)),
+      Err(room_type) => {
+        let message = format!("unknown room type {room_type}");
+        load_warnings.push(LoadWarning::new(BuildingData::IDENTIFIER, id, message));
+        None
+      }
+    });
+
+    let furniture = recollect_map(self.custom_data.furniture, BuildingDataFurniture::into_furniture);
+    (buildings, furniture)
   }
 
   pub(super) fn get_operator_base_skill(&self, id: &str) -> Vec<OperatorBaseSkill> {
@@ -36,7 +60,9 @@ impl BuildingData {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct BuildingDataRoom {
+  #[serde(deserialize_with = "deserialize_or_other")]
   id: BuildingDataRoomId,
   name: String,
   description: Option<String>,
@@ -49,20 +75,22 @@ struct BuildingDataRoom {
 }
 
 impl BuildingDataRoom {
-  fn into_building(self) -> Building {
-    Building {
-      building_type: self.id.into_building_type(),
+  /// Fails with the room type if it is not recognized.
+  fn into_building(self) -> Result<Building, String> {
+    Ok(Building {
+      building_type: self.id.into_building_type()?,
       name: self.name,
       description: self.description,
       max_count: self.max_count,
       category: self.category,
       size: self.size.into(),
       upgrades: recollect(self.phases, BuildingDataRoomPhase::into_building_upgrade)
-    }
+    })
   }
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct BuildingDataRoomPhase {
   #[serde(rename = "unlockCondId")]
   unlock_condition: String,
@@ -89,12 +117,49 @@ impl BuildingDataRoomPhase {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct BuildingDataBuildCost {
   items: Vec<ItemCost>,
   labor: u32
 }
 
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct BuildingDataCustomData {
+  // omitted fields: themes, groups, decorates, ...
+  #[serde(rename = "furnitures")]
+  furniture: HashMap<String, BuildingDataFurniture>
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct BuildingDataFurniture {
+  id: String,
+  name: String,
+  description: Option<String>,
+  usage: Option<String>,
+  rarity: u32,
+  #[serde(rename = "themeId")]
+  theme_id: Option<String>,
+  comfort: u32
+}
+
+impl BuildingDataFurniture {
+  fn into_furniture(self) -> Furniture {
+    Furniture {
+      id: self.id,
+      name: self.name,
+      description: self.description,
+      usage: self.usage,
+      rarity: self.rarity,
+      theme_id: self.theme_id.filter(|theme_id| !theme_id.is_empty()),
+      ambience: self.comfort
+    }
+  }
+}
+
 #[derive(Debug, Clone, Copy, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct BuildingDataRoomSize {
   row: u32,
   col: u32
@@ -107,7 +172,7 @@ impl From<BuildingDataRoomSize> for (u32, u32) {
   }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 enum BuildingDataRoomId {
   #[serde(rename = "CONTROL")]
   ControlCenter,
@@ -130,12 +195,22 @@ enum BuildingDataRoomId {
   #[serde(rename = "ELEVATOR")]
   Elevator,
   #[serde(rename = "CORRIDOR")]
-  Corridor
+  Corridor,
+  // any room type added to the game after this crate was last updated
+  #[serde(skip)]
+  Other(String)
+}
+
+impl OtherVariant for BuildingDataRoomId {
+  fn other(value: String) -> Self {
+    BuildingDataRoomId::Other(value)
+  }
 }
 
 impl BuildingDataRoomId {
-  fn into_building_type(self) -> BuildingType {
-    match self {
+  /// Fails with the room type if it is not recognized.
+  fn into_building_type(self) -> Result<BuildingType, String> {
+    Ok(match self {
       BuildingDataRoomId::ControlCenter => BuildingType::ControlCenter,
       BuildingDataRoomId::PowerPlant => BuildingType::PowerPlant,
       BuildingDataRoomId::Factory => BuildingType::Factory,
@@ -146,14 +221,16 @@ impl BuildingDataRoomId {
       BuildingDataRoomId::TrainingRoom => BuildingType::TrainingRoom,
       BuildingDataRoomId::ReceptionRoom => BuildingType::ReceptionRoom,
       BuildingDataRoomId::Elevator => BuildingType::Elevator,
-      BuildingDataRoomId::Corridor => BuildingType::Corridor
-    }
+      BuildingDataRoomId::Corridor => BuildingType::Corridor,
+      BuildingDataRoomId::Other(room_type) => return Err(room_type)
+    })
   }
 }
 
 
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct BuildingDataChar {
   // omitted fields: charId
   #[serde(rename = "buffChar")]
@@ -162,6 +239,7 @@ struct BuildingDataChar {
 
 #[repr(transparent)]
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct BuildingDataCharBuff {
   #[serde(rename = "buffData")]
   phases: Vec<BuildingDataCharBuffPhase>
@@ -169,15 +247,16 @@ struct BuildingDataCharBuff {
 
 impl BuildingDataCharBuff {
   fn to_operator_base_skill(&self, building_data: &BuildingData) -> Option<OperatorBaseSkill> {
-    if self.phases.is_empty() { return None };
     let phases = self.phases.iter()
-      .map(|phase| phase.to_operator_base_skill_phase(building_data))
-      .collect();
+      .filter_map(|phase| phase.to_operator_base_skill_phase(building_data))
+      .collect::<Vec<OperatorBaseSkillPhase>>();
+    if phases.is_empty() { return None };
     Some(OperatorBaseSkill { phases })
   }
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct BuildingDataCharBuffPhase {
   #[serde(rename = "buffId")]
   id: String,
@@ -186,13 +265,14 @@ struct BuildingDataCharBuffPhase {
 }
 
 impl BuildingDataCharBuffPhase {
-  fn to_operator_base_skill_phase(&self, building_data: &BuildingData) -> OperatorBaseSkillPhase {
+  fn to_operator_base_skill_phase(&self, building_data: &BuildingData) -> Option<OperatorBaseSkillPhase> {
     let BuildingDataCharBuffPhase { id, condition } = self;
     building_data.buffs[id].to_operator_base_skill_phase(condition.clone())
   }
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct BuildingDataBuff {
   // omitted fields: buffId
   #[serde(rename = "buffName")]
@@ -200,39 +280,59 @@ struct BuildingDataBuff {
   #[serde(rename = "sortId")]
   sort: u32,
   #[serde(rename = "buffCategory")]
+  #[serde(deserialize_with = "deserialize_or_other")]
   category: BuildingDataBuffCategory,
   #[serde(rename = "roomType")]
-  room_type: BuildingDataRoomId
+  #[serde(deserialize_with = "deserialize_or_other")]
+  room_type: BuildingDataRoomId,
+  #[serde(default)]
+  description: Option<String>
 }
 
 impl BuildingDataBuff {
-  fn to_operator_base_skill_phase(&self, condition: CharCondition) -> OperatorBaseSkillPhase {
-    OperatorBaseSkillPhase {
+  // unrecognized room types and buff categories are reported by `BuildingData::into_buildings_and_furniture`
+  fn to_operator_base_skill_phase(&self, condition: CharCondition) -> Option<OperatorBaseSkillPhase> {
+    let description = self.description.as_deref()
+      .map(|description| strip_tags(description).into_owned())
+      .filter(|description| !description.is_empty());
+    Some(OperatorBaseSkillPhase {
       name: self.name.clone(),
+      effect: description.as_deref().and_then(BaseSkillEffect::from_description),
+      description,
       condition: condition.into_promotion_and_level(),
       sort: self.sort,
-      category: self.category.into_operator_base_skill_category(),
-      building_type: self.room_type.into_building_type()
-    }
+      category: self.category.to_operator_base_skill_category()?,
+      building_type: self.room_type.clone().into_building_type().ok()?
+    })
   }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 enum BuildingDataBuffCategory {
   #[serde(rename = "FUNCTION")]
   Function,
   #[serde(rename = "RECOVERY")]
   Recovery,
   #[serde(rename = "OUTPUT")]
-  Output
+  Output,
+  // any buff category added to the game after this crate was last updated
+  #[serde(skip)]
+  Other(String)
+}
+
+impl OtherVariant for BuildingDataBuffCategory {
+  fn other(value: String) -> Self {
+    BuildingDataBuffCategory::Other(value)
+  }
 }
 
 impl BuildingDataBuffCategory {
-  fn into_operator_base_skill_category(self) -> OperatorBaseSkillCategory {
+  fn to_operator_base_skill_category(&self) -> Option<OperatorBaseSkillCategory> {
     match self {
-      BuildingDataBuffCategory::Function => OperatorBaseSkillCategory::Function,
-      BuildingDataBuffCategory::Recovery => OperatorBaseSkillCategory::Recovery,
-      BuildingDataBuffCategory::Output => OperatorBaseSkillCategory::Output
+      BuildingDataBuffCategory::Function => Some(OperatorBaseSkillCategory::Function),
+      BuildingDataBuffCategory::Recovery => Some(OperatorBaseSkillCategory::Recovery),
+      BuildingDataBuffCategory::Output => Some(OperatorBaseSkillCategory::Output),
+      BuildingDataBuffCategory::Other(_) => None
     }
   }
 }
@@ -1,6 +1,5 @@
-use uord::UOrd;
-
-use crate::format::DataFile;
+use crate::format::*;
+use crate::game_data::{AlterGroup, OperatorId};
 
 use std::collections::HashMap;
 
@@ -9,17 +8,22 @@ impl DataFile for CharacterMetaTable {
   const IDENTIFIER: &'static str = "char_meta_table";
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub(super) struct CharacterMetaTable {
   #[serde(rename = "spCharGroups")]
-  sp_char_groups: HashMap<String, Vec<String>>
+  sp_char_groups: HashMap<String, Vec<OperatorId>>
 }
 
 impl CharacterMetaTable {
-  pub(super) fn into_alters(self) -> Vec<UOrd<String>> {
-    self.sp_char_groups.into_values()
-      .filter_map(|value| <[String; 2]>::try_from(value).ok())
-      .map(|[a, b]| UOrd::new(a, b))
-      .collect()
+  pub(super) fn into_alters(self) -> Vec<AlterGroup> {
+    let mut alters: Vec<AlterGroup> = recollect_filter(self.sp_char_groups, |(id, members)| {
+      // a group with only one member has no alters
+      if members.len() < 2 { return None };
+      Some(AlterGroup { id: OperatorId::new(id), members })
+    });
+
+    alters.sort_unstable_by(|a, b| a.id.cmp(&b.id));
+    alters
   }
 }
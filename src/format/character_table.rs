@@ -1,5 +1,7 @@
+use serde::de::{Deserialize, Deserializer};
+
 use crate::format::*;
-use crate::format::skill_table::SkillTableLevel;
+use crate::format::skill_table::{SkillTableLevel, SkillTableMapped};
 use crate::format::skin_table::SkinTableMapped;
 use crate::game_data::*;
 
@@ -9,20 +11,90 @@ use std::num::NonZeroU8;
 impl DataFile for CharacterTable {
   const LOCATION: &'static str = "excel/character_table.json";
   const IDENTIFIER: &'static str = "character_table";
+
+  // entries are normally held as json values first, which would hide their unknown fields
+  fn check_schema(bytes: &[u8]) -> TableSchemaReport {
+    check_data_file_schema::<HashMap<String, CharacterTableEntry>>(bytes)
+  }
+}
+
+/// The character table, without unobtainable characters (such as tokens and traps),
+/// which are dropped as soon as they are deserialized.
+///
+/// Entries that fail to deserialize are set aside along with their errors instead of failing the whole table,
+/// so that [`DataFiles::into_game_data`] can decide whether or not to skip them.
+#[derive(Debug, Default)]
+pub(super) struct CharacterTable {
+  characters: HashMap<String, CharacterTableEntry>,
+  malformed: Vec<(String, serde_path_to_error::Error<serde_json::Error>)>,
+  unobtainable: Vec<String>
 }
 
-pub(super) type CharacterTable = HashMap<String, CharacterTableEntry>;
+impl CharacterTable {
+  pub(super) fn into_characters(self) -> HashMap<String, CharacterTableEntry> {
+    self.characters
+  }
+
+  /// Takes the IDs of every entry that failed to deserialize, along with their errors.
+  pub(super) fn take_malformed(&mut self) -> Vec<(String, serde_path_to_error::Error<serde_json::Error>)> {
+    std::mem::take(&mut self.malformed)
+  }
+
+  /// Takes the IDs of every unobtainable character that was dropped.
+  pub(super) fn take_unobtainable(&mut self) -> Vec<String> {
+    std::mem::take(&mut self.unobtainable)
+  }
+}
+
+impl<'de> Deserialize<'de> for CharacterTable {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let mut malformed = Vec::new();
+    let mut unobtainable = Vec::new();
+    // each entry is held as a json value first, so that a malformed entry does not stop the rest from being read
+    let characters = deserialize_map_filtered(deserializer, |id, value: serde_json::Value| {
+      match serde_path_to_error::deserialize::<_, CharacterTableEntry>(value) {
+        Ok(character) if character.is_unobtainable => {
+          unobtainable.push(id.to_owned());
+          None
+        },
+        Ok(character) => Some(character),
+        Err(error) => {
+          malformed.push((id.to_owned(), error));
+          None
+        }
+      }
+    })?;
+
+    Ok(CharacterTable { characters, malformed, unobtainable })
+  }
+}
+
+/// Counts how many operators use each skill, see [`SkillTableMapped`].
+pub(super) fn count_skill_uses(character_table: &CharacterTable) -> HashMap<String, usize> {
+  let mut uses = HashMap::<String, usize>::new();
+  for character in character_table.characters.values() {
+    for id in character.skills.iter().filter_map(|skill| skill.id.as_ref()) {
+      *uses.entry(id.clone()).or_default() += 1;
+    };
+  };
+
+  uses
+}
 
 #[derive(Debug)]
 pub(super) struct AdditionalData<'a> {
+  pub(super) battle_equip_table: &'a mut BattleEquipTable,
   pub(super) building_data: &'a BuildingData,
   pub(super) equip_table: &'a mut EquipTable,
   pub(super) handbook_info_table: &'a mut HandbookInfoTable,
-  pub(super) skill_table: &'a SkillTable,
+  /// Whether the handbook table was selected, see [`TableSelection::operator_files`].
+  pub(super) operator_files: bool,
+  pub(super) skill_table: &'a mut SkillTableMapped,
   pub(super) skin_table: &'a mut SkinTableMapped
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub(super) struct CharacterTableEntry {
   name: String,
   #[serde(rename = "potentialItemId")]
@@ -36,8 +108,23 @@ pub(super) struct CharacterTableEntry {
   team_id: Option<String>,
   #[serde(rename = "displayNumber")]
   display_number: Option<String>,
+  #[serde(rename = "sortIndex")]
+  #[serde(default)]
+  sort_index: Option<i32>,
+  #[serde(default)]
+  description: Option<String>,
+  #[serde(rename = "itemUsage")]
+  #[serde(default)]
+  item_usage: Option<String>,
+  #[serde(rename = "itemDesc")]
+  #[serde(default)]
+  item_description: Option<String>,
+  #[serde(rename = "trait")]
+  #[serde(default)]
+  trait_info: Option<CharacterTableTrait>,
   #[serde(deserialize_with = "deserialize_maybe_empty_str")]
   appellation: Option<String>,
+  #[serde(deserialize_with = "deserialize_or_other")]
   position: CharacterTablePosition,
   #[serde(rename = "tagList")]
   #[serde(deserialize_with = "deserialize_or_default")]
@@ -46,11 +133,17 @@ pub(super) struct CharacterTableEntry {
   is_unobtainable: bool,
   // omitted fields: isSpChar
   rarity: u8,
+  #[serde(deserialize_with = "deserialize_or_other")]
   profession: CharacterTableProfession,
   #[serde(rename = "subProfessionId")]
+  #[serde(deserialize_with = "deserialize_or_other")]
   sub_profession: CharacterTableSubProfession,
   phases: Vec<CharacterTablePhase>,
   skills: Vec<CharacterTableSkill>,
+  #[serde(rename = "allSkillLvlup")]
+  #[serde(default)]
+  #[serde(deserialize_with = "deserialize_or_default")]
+  all_skill_level_up: Vec<CharacterTableSkillLevelUp>,
   #[serde(deserialize_with = "deserialize_or_default")]
   talents: Vec<CharacterTableTalent>,
   #[serde(rename = "potentialRanks")]
@@ -61,52 +154,74 @@ pub(super) struct CharacterTableEntry {
 }
 
 impl CharacterTableEntry {
-  pub(super) fn into_operator(self, id: String, data: AdditionalData) -> Option<Operator> {
-    if self.is_unobtainable { return None };
-    let display_number = self.display_number?;
+  pub(super) fn into_operator(self, id: String, data: AdditionalData) -> Result<Operator, SkipReason> {
+    if self.is_unobtainable { return Err(SkipReason::Unobtainable) };
     let profession = self.profession.into_profession()?;
-    let sub_profession = self.sub_profession.into_sub_profession()?;
+    let sub_profession = self.sub_profession.into_sub_profession().ok_or(SkipReason::NotAnOperator)?;
+    let display_number = self.display_number.ok_or(SkipReason::MissingDisplayNumber)?;
     let position = self.position.into_position()?;
 
-    let skin_table_entry = data.skin_table.take_character_entry(&id)?;
+    let skin_table_entry = data.skin_table.take_character_entry(&id).ok_or(SkipReason::MissingSkins)?;
 
     let mut promotions = self.phases.into_iter()
       .zip(skin_table_entry.default_skins.into_iter())
       .map(|(phase, skin)| phase.into_operator_promotion(skin));
-    let promotion_none = promotions.next()?;
+    let promotion_none = promotions.next().ok_or(SkipReason::MissingPromotions)?;
     let promotion_elite1 = promotions.next();
     let promotion_elite2 = promotions.next();
 
     let potential = recollect(self.potential_ranks, CharacterTablePotentialRank::into_operator_potential);
-    let skills = recollect_maybe(self.skills, |character_table_skill| {
-      character_table_skill.into_operator_skill(data.skill_table)
+    let skills = recollect_result(self.skills, |character_table_skill| {
+      let skill_id = character_table_skill.id.clone().unwrap_or_default();
+      character_table_skill.into_operator_skill(data.skill_table).ok_or(SkipReason::InvalidSkill(skill_id))
     })?;
-    let talents = recollect_maybe(self.talents, CharacterTableTalent::into_operator_talent)?;
-    let modules = data.equip_table.take_operator_modules(&id).unwrap_or_default();
+    let talents = recollect_maybe(self.talents, CharacterTableTalent::into_operator_talent)
+      .ok_or(SkipReason::InvalidTalent)?;
+    // the trait as shown before any promotions, so it is filled in with the values of the first trait phase
+    let description = self.description.as_deref().map(|description| {
+      let blackboard = self.trait_info.as_ref()
+        .and_then(|trait_info| trait_info.candidates.first())
+        .map_or_else(HashMap::new, CharacterTableTraitCandidate::blackboard);
+      apply_templates(description, blackboard)
+    });
+    let traits = match (self.description.as_deref(), self.trait_info) {
+      (Some(description), Some(trait_info)) => trait_info.into_operator_traits(description),
+      (_, _) => Vec::new()
+    };
+    let modules = data.equip_table.take_operator_modules(&id, data.battle_equip_table).unwrap_or_default();
     let base_skills = data.building_data.get_operator_base_skill(&id);
-    let file = data.handbook_info_table.take_operator_file(&id)?;
+    let file = match data.operator_files {
+      true => data.handbook_info_table.take_operator_file(&id).ok_or(SkipReason::MissingHandbookEntry)?,
+      false => OperatorFile::empty(&id)
+    };
 
-    Some(Operator {
-      id,
+    Ok(Operator {
+      id: OperatorId::new(id),
       name: self.name,
       nation_id: self.nation_id,
       group_id: self.group_id,
       team_id: self.team_id,
       display_number,
+      sort_index: self.sort_index,
       position,
       appellation: self.appellation,
+      description,
+      traits,
+      item_usage: self.item_usage,
+      item_description: self.item_description,
       recruitment_tags: self.recruitment_tags,
       rarity: NonZeroU8::new(self.rarity + 1).unwrap(),
       profession,
       sub_profession,
-      promotions: OperatorPromotions {
+      promotions: Box::new(OperatorPromotions {
         none: promotion_none,
         elite1: promotion_elite1,
         elite2: promotion_elite2
-      },
-      potential_item_id: self.potential_item_id,
+      }),
+      potential_item_id: self.potential_item_id.map(ItemId::new),
       potential,
       skills,
+      skill_level_upgrades: recollect(self.all_skill_level_up, CharacterTableSkillLevelUp::into_operator_skill_level_upgrade),
       talents,
       modules,
       base_skills,
@@ -115,15 +230,16 @@ impl CharacterTableEntry {
         Some([_, keyframe]) => keyframe.into_operator_trust_attributes(),
         None => OperatorTrustAttributes::default()
       },
-      file
+      file: Box::new(file)
     })
   }
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct CharacterTablePhase {
   #[serde(rename = "rangeId")]
-  range_id: Option<String>,
+  range_id: Option<RangeId>,
   #[serde(rename = "maxLevel")]
   max_level: u32,
   #[serde(rename = "attributesKeyFrames")]
@@ -134,7 +250,7 @@ struct CharacterTablePhase {
 }
 
 impl CharacterTablePhase {
-  fn into_operator_promotion(self, skin_id: Option<String>) -> OperatorPromotion {
+  fn into_operator_promotion(self, skin_id: Option<SkinId>) -> OperatorPromotion {
     let [min_attributes, max_attributes] = self.attributes_key_frames;
     OperatorPromotion {
       attack_range_id: self.range_id,
@@ -148,6 +264,7 @@ impl CharacterTablePhase {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct CharacterTableKeyFrame {
   level: u32,
   data: CharacterTableKeyFrameData
@@ -189,6 +306,7 @@ impl CharacterTableKeyFrame {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct CharacterTableKeyFrameData {
   #[serde(rename = "maxHp")]
   max_hp: u32,
@@ -229,7 +347,7 @@ struct CharacterTableKeyFrameData {
 }
 
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 enum CharacterTablePosition {
   #[serde(rename = "MELEE")]
   Melee,
@@ -238,21 +356,32 @@ enum CharacterTablePosition {
   #[serde(rename = "ALL")]
   All,
   #[serde(rename = "NONE")]
-  None
+  None,
+  // any position added to the game after this crate was last updated
+  #[serde(skip)]
+  Other(String)
+}
+
+impl OtherVariant for CharacterTablePosition {
+  fn other(value: String) -> Self {
+    CharacterTablePosition::Other(value)
+  }
 }
 
 impl CharacterTablePosition {
-  fn into_position(self) -> Option<Position> {
+  fn into_position(self) -> Result<Position, SkipReason> {
     match self {
-      CharacterTablePosition::Melee => Some(Position::Melee),
-      CharacterTablePosition::Ranged => Some(Position::Ranged),
-      CharacterTablePosition::All => None,
-      CharacterTablePosition::None => None
+      CharacterTablePosition::Melee => Ok(Position::Melee),
+      CharacterTablePosition::Ranged => Ok(Position::Ranged),
+      CharacterTablePosition::All => Ok(Position::Any),
+      CharacterTablePosition::None => Err(SkipReason::MissingPosition),
+      CharacterTablePosition::Other(value) => Err(SkipReason::UnknownValue { field: "position".to_owned(), value })
     }
   }
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct CharacterTableSkill {
   #[serde(rename = "skillId")]
   id: Option<String>,
@@ -267,19 +396,22 @@ struct CharacterTableSkill {
 }
 
 impl CharacterTableSkill {
-  fn into_operator_skill(self, skill_table: &SkillTable) -> Option<OperatorSkill> {
+  fn into_operator_skill(self, skill_table: &mut SkillTableMapped) -> Option<OperatorSkill> {
     let id = self.id?;
-    let skill_table_entry = skill_table.get(&id)?;
+    let skill_table_entry = skill_table.take_skill(&id)?;
     let (name, activation, recovery) = skill_table_entry.name_activation_recovery()?;
-    let (skill_table_levels7, skill_table_levels3) = skill_table_entry.split_levels()?;
-    let levels = skill_table_levels7.clone().map(SkillTableLevel::into_skill_level);
+    // skills without an icon of their own use their ID instead
+    let icon_id = skill_table_entry.icon_id().unwrap_or(&id).to_owned();
+    let (skill_table_levels7, skill_table_levels3) = skill_table_entry.into_split_levels()?;
+    let levels = skill_table_levels7.map(SkillTableLevel::into_skill_level);
     let mastery = self.mastery_upgrades.zip(skill_table_levels3).map(|(mastery_upgrades, skill_table_levels)| {
-      zip_map(mastery_upgrades, skill_table_levels.clone(), CharacterTableSkillMastery::into_operator_skill_mastery)
+      zip_map(mastery_upgrades, skill_table_levels, CharacterTableSkillMastery::into_operator_skill_mastery)
     });
 
     Some(OperatorSkill {
-      id,
+      id: SkillId::new(id),
       name,
+      icon_id,
       prefab_key: self.override_prefab_key,
       condition: self.unlock_condition.into_promotion_and_level(),
       activation,
@@ -291,6 +423,26 @@ impl CharacterTableSkill {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct CharacterTableSkillLevelUp {
+  #[serde(rename = "unlockCond")]
+  unlock_condition: CharCondition,
+  #[serde(rename = "lvlUpCost")]
+  #[serde(deserialize_with = "deserialize_or_default")]
+  level_up_cost: Vec<ItemCost>
+}
+
+impl CharacterTableSkillLevelUp {
+  fn into_operator_skill_level_upgrade(self) -> OperatorSkillLevelUpgrade {
+    OperatorSkillLevelUpgrade {
+      condition: self.unlock_condition.into_promotion_and_level(),
+      upgrade_cost: ItemCost::convert(self.level_up_cost)
+    }
+  }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct CharacterTableSkillMastery {
   #[serde(rename = "unlockCond")]
   unlock_condition: CharCondition,
@@ -314,6 +466,7 @@ impl CharacterTableSkillMastery {
 
 #[repr(transparent)]
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct CharacterTableTalent {
   #[serde(rename = "candidates")]
   #[serde(deserialize_with = "deserialize_or_default")]
@@ -329,6 +482,7 @@ impl CharacterTableTalent {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct CharacterTableTalentCandidate {
   #[serde(rename = "unlockCondition")]
   unlock_condition: CharCondition,
@@ -339,7 +493,7 @@ struct CharacterTableTalentCandidate {
   name: Option<String>,
   description: Option<String>,
   #[serde(rename = "rangeId")]
-  range_id: Option<String>,
+  range_id: Option<RangeId>,
   blackboard: Vec<CharacterTableTalentBlackboard>
 }
 
@@ -358,6 +512,7 @@ impl CharacterTableTalentCandidate {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct CharacterTableTalentBlackboard {
   key: String,
   value: f32
@@ -370,6 +525,57 @@ impl CharacterTableTalentBlackboard {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct CharacterTableTrait {
+  #[serde(deserialize_with = "deserialize_or_default")]
+  candidates: Vec<CharacterTableTraitCandidate>
+}
+
+impl CharacterTableTrait {
+  fn into_operator_traits(self, description: &str) -> Vec<OperatorTraitPhase> {
+    recollect(self.candidates, |candidate| candidate.into_operator_trait_phase(description))
+  }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct CharacterTableTraitCandidate {
+  #[serde(rename = "unlockCondition")]
+  unlock_condition: CharCondition,
+  #[serde(rename = "requiredPotentialRank")]
+  required_potential_rank: u8,
+  // the typo is present in the game data
+  #[serde(rename = "overrideDescripton")]
+  #[serde(default)]
+  override_description: Option<String>,
+  #[serde(rename = "rangeId")]
+  #[serde(default)]
+  range_id: Option<RangeId>,
+  #[serde(deserialize_with = "deserialize_or_default")]
+  blackboard: Vec<CharacterTableTalentBlackboard>
+}
+
+impl CharacterTableTraitCandidate {
+  fn blackboard(&self) -> HashMap<String, f32> {
+    self.blackboard.iter()
+      .map(|item| (item.key.to_lowercase(), item.value))
+      .collect()
+  }
+
+  fn into_operator_trait_phase(self, description: &str) -> OperatorTraitPhase {
+    let description = self.override_description.as_deref().unwrap_or(description);
+    OperatorTraitPhase {
+      description: apply_templates(description, self.blackboard()),
+      condition: self.unlock_condition.into_promotion_and_level(),
+      required_potential: self.required_potential_rank,
+      attack_range_id: self.range_id,
+      effects: CharacterTableTalentBlackboard::convert(self.blackboard)
+    }
+  }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct CharacterTablePotentialRank {
   #[serde(rename = "type")]
   potential_type: u32,
@@ -384,7 +590,7 @@ impl CharacterTablePotentialRank {
   }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 enum CharacterTableProfession {
   #[serde(rename = "CASTER")]
   Caster,
@@ -405,26 +611,36 @@ enum CharacterTableProfession {
   #[serde(rename = "TOKEN")]
   Token,
   #[serde(rename = "TRAP")]
-  Trap
+  Trap,
+  // any profession added to the game after this crate was last updated
+  #[serde(skip)]
+  Other(String)
+}
+
+impl OtherVariant for CharacterTableProfession {
+  fn other(value: String) -> Self {
+    CharacterTableProfession::Other(value)
+  }
 }
 
 impl CharacterTableProfession {
-  fn into_profession(self) -> Option<Profession> {
+  fn into_profession(self) -> Result<Profession, SkipReason> {
     match self {
-      CharacterTableProfession::Caster => Some(Profession::Caster),
-      CharacterTableProfession::Medic => Some(Profession::Medic),
-      CharacterTableProfession::Vanguard => Some(Profession::Vanguard),
-      CharacterTableProfession::Sniper => Some(Profession::Sniper),
-      CharacterTableProfession::Specialist => Some(Profession::Specialist),
-      CharacterTableProfession::Support => Some(Profession::Support),
-      CharacterTableProfession::Tank => Some(Profession::Tank),
-      CharacterTableProfession::Guard => Some(Profession::Guard),
-      _ => None
+      CharacterTableProfession::Caster => Ok(Profession::Caster),
+      CharacterTableProfession::Medic => Ok(Profession::Medic),
+      CharacterTableProfession::Vanguard => Ok(Profession::Vanguard),
+      CharacterTableProfession::Sniper => Ok(Profession::Sniper),
+      CharacterTableProfession::Specialist => Ok(Profession::Specialist),
+      CharacterTableProfession::Support => Ok(Profession::Support),
+      CharacterTableProfession::Tank => Ok(Profession::Tank),
+      CharacterTableProfession::Guard => Ok(Profession::Guard),
+      CharacterTableProfession::Token | CharacterTableProfession::Trap => Err(SkipReason::NotAnOperator),
+      CharacterTableProfession::Other(value) => Err(SkipReason::UnknownValue { field: "profession".to_owned(), value })
     }
   }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 enum CharacterTableSubProfession {
   // Casters
   #[serde(rename = "blastcaster")]
@@ -546,7 +762,16 @@ enum CharacterTableSubProfession {
   #[serde(rename = "notchar1")]
   NotChar1,
   #[serde(rename = "notchar2")]
-  NotChar2
+  NotChar2,
+  // any sub-profession added to the game after this crate was last updated
+  #[serde(skip)]
+  Other(String)
+}
+
+impl OtherVariant for CharacterTableSubProfession {
+  fn other(value: String) -> Self {
+    CharacterTableSubProfession::Other(value)
+  }
 }
 
 impl CharacterTableSubProfession {
@@ -604,6 +829,7 @@ impl CharacterTableSubProfession {
       CharacterTableSubProfession::Musha => Some(SubProfession::Musha),
       CharacterTableSubProfession::Reaper => Some(SubProfession::Reaper),
       CharacterTableSubProfession::Swordmaster => Some(SubProfession::Swordmaster),
+      CharacterTableSubProfession::Other(game_id) => Some(SubProfession::Other(game_id)),
       _ => None
     }
   }
@@ -1,5 +1,6 @@
 use crate::format::*;
-use crate::game_data::{OperatorModule, OperatorModuleMission};
+use crate::format::battle_equip_table::BattleEquipTableEquip;
+use crate::game_data::{ModuleId, OperatorModule, OperatorModuleMission};
 
 use std::collections::HashMap;
 
@@ -8,7 +9,8 @@ impl DataFile for EquipTable {
   const IDENTIFIER: &'static str = "uniequip_table";
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub(super) struct EquipTable {
   #[serde(rename = "equipDict")]
   equip_list: HashMap<String, EquipTableEquip>,
@@ -19,24 +21,31 @@ pub(super) struct EquipTable {
 }
 
 impl EquipTable {
-  pub(super) fn take_operator_modules(&mut self, id: &str) -> Option<Vec<OperatorModule>> {
+  pub(super) fn take_operator_modules(&mut self, id: &str, battle_equip_table: &mut BattleEquipTable) -> Option<Vec<OperatorModule>> {
     let character_equip_list = self.character_equip_list.remove(id)?;
-    recollect_maybe(character_equip_list.iter().skip(1).cloned(), |character_equip_id| {
+    recollect_maybe(character_equip_list.into_iter().skip(1), |character_equip_id| {
       self.equip_list.remove(&character_equip_id).and_then(|equip_table_equip| {
-        equip_table_equip.into_operator_module(&self.mission_list)
+        equip_table_equip.into_operator_module(&mut self.mission_list, battle_equip_table)
       })
     })
   }
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct EquipTableEquip {
   #[serde(rename = "uniEquipId")]
-  id: String,
+  id: ModuleId,
   #[serde(rename = "uniEquipName")]
   name: String,
   #[serde(rename = "uniEquipDesc")]
   description: String,
+  #[serde(rename = "typeIcon")]
+  type_icon: String,
+  #[serde(rename = "typeName1")]
+  type_name: String,
+  #[serde(rename = "typeName2")]
+  type_variant: Option<String>,
   #[serde(rename = "unlockEvolvePhase")]
   unlock_phase: CharPhase,
   #[serde(rename = "unlockLevel")]
@@ -50,27 +59,41 @@ struct EquipTableEquip {
 }
 
 impl EquipTableEquip {
-  fn into_operator_module(self, mission_list: &HashMap<String, EquipTableMission>) -> Option<OperatorModule> {
+  fn into_operator_module(
+    self,
+    mission_list: &mut HashMap<String, EquipTableMission>,
+    battle_equip_table: &mut BattleEquipTable
+  ) -> Option<OperatorModule> {
+    // every mission belongs to a single module, so they can be moved out instead of cloned
     let missions = recollect_maybe(self.mission_list, |id| {
-      mission_list.get(&id).map(|mission| (id, mission.clone().into_operator_module_mission()))
+      mission_list.remove(&id).map(|mission| (id, mission.into_operator_module_mission()))
     })?;
 
+    // every stage belongs to a single module, so they can be moved out as well
+    let stages = battle_equip_table.remove(self.id.as_str())
+      .map_or_else(Vec::new, BattleEquipTableEquip::into_operator_module_stages);
+
     Some(OperatorModule {
       id: self.id,
       name: self.name,
       description: self.description,
+      type_name: self.type_name,
+      type_variant: self.type_variant,
+      icon_id: self.type_icon,
       condition: PromotionAndLevel {
         promotion: self.unlock_phase.into_promotion(),
         level: self.unlock_level
       },
       required_trust: trust_points_to_percent(self.unlock_trust_points),
       upgrade_cost: ItemCost::convert(self.item_cost.unwrap_or_default()),
-      missions
+      missions,
+      stages
     })
   }
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct EquipTableMission {
   #[serde(rename = "desc")]
   description: String,
@@ -1,30 +1,121 @@
 use chrono::{DateTime, Utc};
 
 use crate::format::*;
-use crate::game_data::{HeadhuntingBanner, HeadhuntingBannerType};
+use crate::game_data::{
+  BannerRules, BeginnerBanner, HeadhuntingBanner, HeadhuntingBannerType, ItemId, LoadWarning, OperatorId,
+  RecruitmentTimeBracket, SpecialRecruitmentDuration, SpecialRecruitmentPool
+};
+
+use std::collections::HashMap;
 
 impl DataFile for GachaTable {
   const LOCATION: &'static str = "excel/gacha_table.json";
   const IDENTIFIER: &'static str = "gacha_table";
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub(super) struct GachaTable {
   #[serde(rename = "gachaTags")]
   recruit_tags: Vec<GachaTableRecruitTag>,
   #[serde(rename = "gachaPoolClient")]
-  gacha_table_client: Vec<GachaTableGachaPool>
+  gacha_table_client: Vec<GachaTableGachaPool>,
+  #[serde(rename = "newbeeGachaPoolClient")]
+  #[serde(default)]
+  newbee_gacha_pool_client: Vec<GachaTableNewbeeGachaPool>,
+  #[serde(rename = "specialRecruitPool")]
+  #[serde(default)]
+  special_recruit_pool: Vec<GachaTableSpecialRecruitPool>,
+  #[serde(rename = "recruitDetail")]
+  #[serde(default)]
+  recruit_detail: String,
+  #[serde(rename = "recruitRarityTable")]
+  #[serde(default)]
+  recruit_rarity_table: HashMap<String, GachaTableRecruitRange>
 }
 
 impl GachaTable {
-  pub(super) fn into_tags_and_banners(self) -> (crate::Map<String, u32>, Vec<HeadhuntingBanner>) {
+  pub(super) fn into_tags_and_banners(self, load_warnings: &mut Vec<LoadWarning>)
+  -> (crate::Map<String, u32>, Vec<HeadhuntingBanner>, Vec<BeginnerBanner>, Vec<SpecialRecruitmentPool>) {
     let recruitment_tags = recollect(self.recruit_tags, GachaTableRecruitTag::into_entry);
-    let headhunting_banners = recollect(self.gacha_table_client, GachaTableGachaPool::into_headhunting_banner);
-    (recruitment_tags, headhunting_banners)
+    let headhunting_banners = recollect(self.gacha_table_client, |gacha_pool| {
+      if let GachaTableGachaRuleType::Other(rule_type) = &gacha_pool.gacha_rule_type {
+        let message = format!("unknown rule type {rule_type}, treated as normal");
+        load_warnings.push(LoadWarning::unrecognized(GachaTable::IDENTIFIER, &gacha_pool.gacha_pool_id, message));
+      };
+
+      gacha_pool.into_headhunting_banner()
+    });
+    let beginner_banners = recollect(self.newbee_gacha_pool_client, GachaTableNewbeeGachaPool::into_beginner_banner);
+    let mut special_pools: Vec<SpecialRecruitmentPool> =
+      recollect(self.special_recruit_pool, GachaTableSpecialRecruitPool::into_special_recruitment_pool);
+    special_pools.sort_unstable_by_key(|pool| pool.open_time);
+    (recruitment_tags, headhunting_banners, beginner_banners, special_pools)
+  }
+
+  /// Lists the names of every operator in the recruitment pool, as they appear in the recruitment details page.
+  /// If no names can be read, a warning is added and no operators will be marked as recruitable.
+  pub(super) fn recruitable_operator_names(&self, load_warnings: &mut Vec<LoadWarning>) -> Vec<String> {
+    let names = parse_recruitable_operator_names(&self.recruit_detail);
+    if names.is_empty() {
+      let message = "no operator names found, no operators are marked as recruitable";
+      load_warnings.push(LoadWarning::unrecognized(GachaTable::IDENTIFIER, "recruitDetail", message));
+    };
+
+    names
+  }
+
+  /// Lists the rarities each recruitment duration can yield, sorted by duration.
+  pub(super) fn recruitment_time_brackets(&self) -> Vec<RecruitmentTimeBracket> {
+    // brackets are keyed by the number of hours a recruitment must last for them to apply
+    let mut brackets = recollect_filter(self.recruit_rarity_table.iter(), |(hours, range)| {
+      hours.parse::<u32>().ok().map(|min_hours| range.into_recruitment_time_bracket(min_hours))
+    });
+    brackets.sort_unstable_by_key(|bracket| bracket.min_hours);
+    brackets
+  }
+}
+
+// the pool comes after the recruitment rules, one section per rarity, each starting with a line of stars
+// followed by the names of its operators separated by slashes, and ending with a line of dashes
+fn parse_recruitable_operator_names(recruit_detail: &str) -> Vec<String> {
+  // the line break after the stars is escaped in the game data
+  let recruit_detail = strip_tags(recruit_detail).replace("\\n", "\n");
+  let mut names = Vec::new();
+  let mut in_pool = false;
+  for line in recruit_detail.lines().map(str::trim) {
+    if !line.is_empty() && line.chars().all(|c| c == '★') {
+      in_pool = true;
+    } else if in_pool && !line.chars().all(|c| c == '-') {
+      names.extend(line.split('/').map(str::trim).filter(|name| !name.is_empty()).map(str::to_owned));
+    };
+  };
+
+  names
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct GachaTableRecruitRange {
+  #[serde(rename = "rarityStart")]
+  rarity_start: u8,
+  #[serde(rename = "rarityEnd")]
+  rarity_end: u8
+}
+
+impl GachaTableRecruitRange {
+  fn into_recruitment_time_bracket(self, min_hours: u32) -> RecruitmentTimeBracket {
+    // rarities are zero-indexed in the game files
+    RecruitmentTimeBracket {
+      min_hours,
+      min_rarity: self.rarity_start + 1,
+      max_rarity: self.rarity_end + 1
+    }
   }
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct GachaTableRecruitTag {
   #[serde(rename = "tagId")] id: u32,
   #[serde(rename = "tagName")] name: String
@@ -37,6 +128,7 @@ impl GachaTableRecruitTag {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct GachaTableGachaPool {
   #[serde(rename = "gachaPoolId")]
   gacha_pool_id: String,
@@ -53,13 +145,27 @@ struct GachaTableGachaPool {
   #[serde(rename = "gachaPoolSummary")]
   gacha_pool_summary: String,
   #[serde(rename = "LMTGSID")]
-  data_contract_item_id: Option<String>,
+  data_contract_item_id: Option<ItemId>,
   #[serde(rename = "gachaRuleType")]
-  gacha_rule_type: GachaTableGachaRuleType
+  #[serde(deserialize_with = "deserialize_or_other")]
+  gacha_rule_type: GachaTableGachaRuleType,
+  #[serde(rename = "guarantee5Avail")]
+  #[serde(default)]
+  guarantee_5_avail: u32,
+  #[serde(rename = "guarantee5Count")]
+  #[serde(default)]
+  guarantee_5_count: u32,
+  #[serde(rename = "limitParam")]
+  #[serde(default)]
+  limit_param: Option<GachaTableLimitParam>,
+  #[serde(rename = "linkageParam")]
+  #[serde(default)]
+  linkage_param: Option<GachaTableLinkageParam>
 }
 
 impl GachaTableGachaPool {
-  fn into_headhunting_banner(self) -> HeadhuntingBanner {
+  fn into_headhunting_banner(mut self) -> HeadhuntingBanner {
+    let rules = self.banner_rules();
     HeadhuntingBanner {
       id: self.gacha_pool_id,
       name: self.gacha_pool_name,
@@ -68,12 +174,150 @@ impl GachaTableGachaPool {
       open_time: self.open_time,
       close_time: self.end_time,
       item_id: self.data_contract_item_id,
-      banner_type: self.gacha_rule_type.into_headhunting_banner_type()
+      banner_type: self.gacha_rule_type.into_headhunting_banner_type(),
+      event_id: None,
+      rules
+    }
+  }
+
+  fn banner_rules(&mut self) -> BannerRules {
+    let limit_param = self.limit_param.take().unwrap_or_default();
+    let linkage_param = self.linkage_param.take().unwrap_or_default();
+    BannerRules {
+      guaranteed_5_star_within: (self.guarantee_5_avail != 0 && self.guarantee_5_count != 0)
+        .then(|| self.guarantee_5_count),
+      guaranteed_6_star_within: linkage_param.guarantee_target_6_count,
+      // limited banners allow exchanging for the limited operator after 300 pulls
+      spark_pulls: matches!(self.gacha_rule_type, GachaTableGachaRuleType::Limited).then(|| 300),
+      free_pulls: limit_param.free_count.filter(|&free_count| free_count != 0),
+      limited_operator_ids: limit_param.limited_char_id.map_or_else(Vec::new, OneOrMany::into_vec)
+    }
+  }
+}
+
+// beginner pools have no open or close times, so they are kept apart from the other pools
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct GachaTableNewbeeGachaPool {
+  #[serde(rename = "gachaPoolId")]
+  gacha_pool_id: String,
+  #[serde(rename = "gachaIndex")]
+  gacha_index: u32,
+  #[serde(rename = "gachaPoolName")]
+  gacha_pool_name: String,
+  #[serde(rename = "gachaPoolDetail")]
+  #[serde(default)]
+  gacha_pool_detail: Option<String>,
+  #[serde(rename = "gachaPrice")]
+  #[serde(default)]
+  gacha_price: u32,
+  #[serde(rename = "gachaTimes")]
+  #[serde(default)]
+  gacha_times: u32
+}
+
+impl GachaTableNewbeeGachaPool {
+  fn into_beginner_banner(self) -> BeginnerBanner {
+    BeginnerBanner {
+      id: self.gacha_pool_id,
+      name: self.gacha_pool_name,
+      description: self.gacha_pool_detail.filter(|detail| !detail.is_empty()),
+      index: self.gacha_index,
+      pull_cost: self.gacha_price,
+      max_pulls: self.gacha_times
+    }
+  }
+}
+
+// special recruitment pools change the durations and costs of recruitment for a while, and have no operators of their own
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct GachaTableSpecialRecruitPool {
+  #[serde(rename = "recruitId")]
+  recruit_id: String,
+  order: u32,
+  #[serde(rename = "startDateTime")]
+  #[serde(with = "chrono::serde::ts_seconds")]
+  start_date_time: DateTime<Utc>,
+  #[serde(rename = "endDateTime")]
+  #[serde(with = "chrono::serde::ts_seconds")]
+  end_date_time: DateTime<Utc>,
+  #[serde(rename = "recruitTimeTable")]
+  #[serde(default)]
+  recruit_time_table: Vec<GachaTableSpecialRecruitCost>
+}
+
+impl GachaTableSpecialRecruitPool {
+  fn into_special_recruitment_pool(self) -> SpecialRecruitmentPool {
+    SpecialRecruitmentPool {
+      id: self.recruit_id,
+      index: self.order,
+      open_time: self.start_date_time,
+      close_time: self.end_date_time,
+      durations: recollect(self.recruit_time_table, GachaTableSpecialRecruitCost::into_special_recruitment_duration)
+    }
+  }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct GachaTableSpecialRecruitCost {
+  #[serde(rename = "timeLength")]
+  time_length: u32,
+  #[serde(rename = "recruitPrice")]
+  #[serde(default)]
+  recruit_price: u32,
+  #[serde(rename = "itemCosts")]
+  #[serde(default)]
+  item_costs: Option<ItemCost>
+}
+
+impl GachaTableSpecialRecruitCost {
+  fn into_special_recruitment_duration(self) -> SpecialRecruitmentDuration {
+    // durations are given in seconds
+    SpecialRecruitmentDuration {
+      hours: self.time_length / 3600,
+      price: self.recruit_price,
+      item_cost: ItemCost::convert(self.item_costs.into_iter().collect())
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct GachaTableLimitParam {
+  #[serde(rename = "limitedCharId")]
+  #[serde(default)]
+  limited_char_id: Option<OneOrMany<OperatorId>>,
+  #[serde(rename = "freeCount")]
+  #[serde(default)]
+  free_count: Option<u32>
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct GachaTableLinkageParam {
+  #[serde(rename = "guaranteeTarget6Count")]
+  #[serde(default)]
+  guarantee_target_6_count: Option<u32>
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+  One(T),
+  Many(Vec<T>)
+}
+
+impl<T> OneOrMany<T> {
+  fn into_vec(self) -> Vec<T> {
+    match self {
+      OneOrMany::One(value) => vec![value],
+      OneOrMany::Many(values) => values
     }
   }
 }
 
-#[repr(u8)]
 #[derive(Debug, Clone, Deserialize)]
 enum GachaTableGachaRuleType {
   #[serde(rename = "NORMAL")]
@@ -83,7 +327,16 @@ enum GachaTableGachaRuleType {
   #[serde(rename = "LINKAGE")]
   Linkage,
   #[serde(rename = "ATTAIN")]
-  Attain
+  Attain,
+  // any rule type added to the game after this crate was last updated
+  #[serde(skip)]
+  Other(String)
+}
+
+impl OtherVariant for GachaTableGachaRuleType {
+  fn other(value: String) -> Self {
+    GachaTableGachaRuleType::Other(value)
+  }
 }
 
 impl GachaTableGachaRuleType {
@@ -92,7 +345,48 @@ impl GachaTableGachaRuleType {
       GachaTableGachaRuleType::Normal => HeadhuntingBannerType::Normal,
       GachaTableGachaRuleType::Limited => HeadhuntingBannerType::Limited,
       GachaTableGachaRuleType::Linkage => HeadhuntingBannerType::Special,
-      GachaTableGachaRuleType::Attain => HeadhuntingBannerType::Special
+      GachaTableGachaRuleType::Attain => HeadhuntingBannerType::Special,
+      GachaTableGachaRuleType::Other(_) => HeadhuntingBannerType::Normal
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // the start of `recruitDetail` from the en_US gacha table, cut off after the 3★ operators
+  const RECRUIT_DETAIL: &str = "<@rc.title>Recruitment Rules</>\n\n\
+    <@rc.em>※Special Rules※</>\n\
+    <@rc.em>When the Top Operator tag is selected, a 6★ Operator is guaranteed.</>\n\
+    <@rc.em>When the Senior Operator tag is selected, a 5★ Operator is guaranteed.</>\n\n\
+    <@rc.subtitle>※All Possible Operators※</>\n\
+    <@rc.eml>Operators displayed in green can only be obtained through Recruitment.</>\n\n\
+    --------------------\n\
+    ★\\n<@rc.eml>Lancet-2</> / <@rc.eml>Castle-3</> / <@rc.eml>THRM-EX</> / <@rc.eml>Justice Knight</>\n\
+    --------------------\n\
+    ★★\\nYato / Noir Corne / Rangers / Durin / 12F\n\
+    --------------------\n\
+    ★★★\\nAdnachiel / Kroos / Fang / Steward / Orchid / Lava / Hibiscus / Ansel / Beagle / Melantha / Plume / \
+    Vanilla / Cardigan / Catapult / Midnight / Popukar / Spot / <@rc.eml>Jackie</>\n";
+
+  #[test]
+  fn recruitable_operator_names() {
+    let gacha_table = GachaTable { recruit_detail: RECRUIT_DETAIL.to_owned(), ..GachaTable::default() };
+    let mut load_warnings = Vec::new();
+    let names = gacha_table.recruitable_operator_names(&mut load_warnings);
+    assert!(load_warnings.is_empty());
+    assert_eq!(names.len(), 27);
+    assert_eq!(&names[..5], ["Lancet-2", "Castle-3", "THRM-EX", "Justice Knight", "Yato"]);
+    assert_eq!(names.last().map(String::as_str), Some("Jackie"));
+  }
+
+  #[test]
+  fn recruitable_operator_names_missing() {
+    let gacha_table = GachaTable { recruit_detail: "<@rc.title>Recruitment Rules</>\n".to_owned(), ..GachaTable::default() };
+    let mut load_warnings = Vec::new();
+    assert!(gacha_table.recruitable_operator_names(&mut load_warnings).is_empty());
+    assert_eq!(load_warnings.len(), 1);
+    assert_eq!(load_warnings[0].id, "recruitDetail");
+  }
+}
@@ -0,0 +1,37 @@
+use crate::format::*;
+use crate::game_data::GameConstants;
+
+impl DataFile for GamedataConst {
+  const LOCATION: &'static str = "excel/gamedata_const.json";
+  const IDENTIFIER: &'static str = "gamedata_const";
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub(super) struct GamedataConst {
+  #[serde(rename = "maxLevel")]
+  max_level: Vec<Vec<u32>>,
+  #[serde(rename = "characterExpMap")]
+  character_exp_map: Vec<Vec<i32>>,
+  #[serde(rename = "characterUpgradeCostMap")]
+  character_upgrade_cost_map: Vec<Vec<i32>>,
+  #[serde(rename = "evolveGoldCost")]
+  evolve_gold_cost: Vec<Vec<i32>>
+  // omitted many other fields
+}
+
+impl GamedataConst {
+  pub(super) fn into_game_constants(self) -> GameConstants {
+    GameConstants {
+      max_levels: self.max_level,
+      exp_costs: recollect(self.character_exp_map, convert_costs),
+      lmd_costs: recollect(self.character_upgrade_cost_map, convert_costs),
+      promotion_lmd_costs: recollect(self.evolve_gold_cost, convert_costs)
+    }
+  }
+}
+
+// the game uses -1 to mark levels or promotions that cannot be reached
+fn convert_costs(costs: Vec<i32>) -> Vec<u32> {
+  costs.into_iter().map_while(|cost| u32::try_from(cost).ok()).collect()
+}
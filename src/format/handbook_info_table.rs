@@ -11,19 +11,27 @@ impl DataFile for HandbookInfoTable {
 }
 
 #[repr(transparent)]
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub(super) struct HandbookInfoTable {
+  // entries are converted as they are deserialized, their raw stories are much larger than the converted ones
   #[serde(rename = "handbookDict")]
-  handbook_dict: HashMap<String, HandbookInfoTableEntry>
+  #[serde(deserialize_with = "deserialize_operator_files")]
+  handbook_dict: HashMap<String, OperatorFile>
 }
 
 impl HandbookInfoTable {
   pub(super) fn take_operator_file(&mut self, id: &str) -> Option<OperatorFile> {
-    self.handbook_dict.remove(id).map(HandbookInfoTableEntry::into_operator_file)
+    self.handbook_dict.remove(id)
   }
 }
 
+fn deserialize_operator_files<'de, D: Deserializer<'de>>(deserializer: D) -> Result<HashMap<String, OperatorFile>, D::Error> {
+  deserialize_map_filtered(deserializer, |_, entry: HandbookInfoTableEntry| Some(entry.into_operator_file()))
+}
+
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct HandbookInfoTableEntry {
   #[serde(rename = "charID")]
   char_id: String,
@@ -44,6 +52,7 @@ impl HandbookInfoTableEntry {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct HandbookStoryEntry {
   stories: [HandbookStory; 1],
   #[serde(rename = "storyTitle")]
@@ -66,6 +75,7 @@ impl HandbookStoryEntry {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct HandbookStory {
   #[serde(rename = "storyText")]
   story_text: String,
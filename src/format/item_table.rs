@@ -1,5 +1,5 @@
 use crate::format::*;
-use crate::game_data::{Item, ItemClass};
+use crate::game_data::{Item, ItemClass, ItemId, LoadWarning};
 
 use std::collections::HashMap;
 
@@ -8,21 +8,34 @@ impl DataFile for ItemTable {
   const IDENTIFIER: &'static str = "item_table";
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub(super) struct ItemTable {
-  items: HashMap<String, ItemTableItem>
+  items: HashMap<ItemId, ItemTableItem>,
+  #[serde(rename = "expItems")]
+  exp_items: HashMap<ItemId, ItemTableExpItem>
 }
 
 impl ItemTable {
-  pub(super) fn into_items(self) -> crate::Map<String, Item> {
-    recollect_map(self.items, ItemTableItem::into_item)
+  pub(super) fn into_items(self, load_warnings: &mut Vec<LoadWarning>) -> crate::Map<ItemId, Item> {
+    let exp_items = self.exp_items;
+    recollect_map(self.items, |item_table_item| {
+      if let ItemTableItemClassify::Other(classify) = &item_table_item.classify {
+        let message = format!("unknown classify type {classify}, treated as other");
+        load_warnings.push(LoadWarning::unrecognized(ItemTable::IDENTIFIER, item_table_item.id.as_str(), message));
+      };
+
+      let exp_value = exp_items.get(&item_table_item.id).map(|exp_item| exp_item.gain_exp);
+      item_table_item.into_item(exp_value)
+    })
   }
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct ItemTableItem {
   #[serde(rename = "itemId")]
-  id: String,
+  id: ItemId,
   name: String,
   description: Option<String>,
   rarity: u32,
@@ -30,13 +43,19 @@ struct ItemTableItem {
   #[serde(rename = "obtainApproach")]
   obtain: Option<String>,
   #[serde(rename = "classifyType")]
+  #[serde(deserialize_with = "deserialize_or_other")]
   classify: ItemTableItemClassify,
   #[serde(rename = "itemType")]
-  item_type: String
+  item_type: String,
+  #[serde(rename = "iconId")]
+  icon_id: String,
+  #[serde(rename = "sortId")]
+  #[serde(default)]
+  sort_id: i32
 }
 
 impl ItemTableItem {
-  fn into_item(self) -> Item {
+  fn into_item(self, exp_value: Option<u32>) -> Item {
     Item {
       id: self.id,
       name: self.name,
@@ -45,12 +64,23 @@ impl ItemTableItem {
       usage: self.usage,
       obtain: self.obtain,
       item_class: self.classify.into_item_class(),
-      item_type: self.item_type
+      item_type: self.item_type,
+      icon_id: self.icon_id,
+      exp_value,
+      sort_id: self.sort_id
     }
   }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct ItemTableExpItem {
+  // omitted fields: id
+  #[serde(rename = "gainExp")]
+  gain_exp: u32
+}
+
+#[derive(Debug, Clone, Deserialize)]
 enum ItemTableItemClassify {
   #[serde(rename = "CONSUME")]
   Consume,
@@ -59,7 +89,16 @@ enum ItemTableItemClassify {
   #[serde(rename = "NONE")]
   None,
   #[serde(rename = "NORMAL")]
-  Normal
+  Normal,
+  // any classify type added to the game after this crate was last updated
+  #[serde(skip)]
+  Other(String)
+}
+
+impl OtherVariant for ItemTableItemClassify {
+  fn other(value: String) -> Self {
+    ItemTableItemClassify::Other(value)
+  }
 }
 
 impl ItemTableItemClassify {
@@ -68,7 +107,7 @@ impl ItemTableItemClassify {
       ItemTableItemClassify::Consume => ItemClass::Consumable,
       ItemTableItemClassify::Normal => ItemClass::BasicItem,
       ItemTableItemClassify::Material => ItemClass::Material,
-      ItemTableItemClassify::None => ItemClass::Other
+      ItemTableItemClassify::None | ItemTableItemClassify::Other(_) => ItemClass::Other
     }
   }
 }
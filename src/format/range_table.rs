@@ -1,7 +1,7 @@
 use mint::Point2;
 
 use crate::format::*;
-use crate::game_data::AttackRange;
+use crate::game_data::{AttackRange, RangeId};
 
 use std::collections::HashMap;
 
@@ -10,9 +10,10 @@ impl DataFile for RangeTable {
   const IDENTIFIER: &'static str = "range_table";
 }
 
-pub(super) type RangeTable = HashMap<String, RangeTableEntry>;
+pub(super) type RangeTable = HashMap<RangeId, RangeTableEntry>;
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub(super) struct RangeTableEntry {
   // omitted `direction`, it seems to only be 1 for every entry
   grids: Vec<RangeTableGridPoint>
@@ -25,6 +26,7 @@ impl RangeTableEntry {
 }
 
 #[derive(Debug, Clone, Copy, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct RangeTableGridPoint {
   row: i32,
   col: i32
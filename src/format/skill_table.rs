@@ -1,5 +1,7 @@
+use serde::de::{Deserialize, Deserializer};
+
 use crate::format::*;
-use crate::game_data::{OperatorSkillLevel, SkillActivation, SkillRecovery};
+use crate::game_data::{OperatorSkillLevel, RangeId, SkillActivation, SkillRecovery};
 
 use std::collections::HashMap;
 
@@ -8,36 +10,88 @@ impl DataFile for SkillTable {
   const IDENTIFIER: &'static str = "skill_table";
 }
 
-pub(super) type SkillTable = HashMap<String, SkillTableEntry>;
+#[repr(transparent)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub(super) struct SkillTable {
+  // entries are filtered as they are deserialized, so that unusable skills are never all held in memory at once
+  #[serde(deserialize_with = "deserialize_skill_table_entries")]
+  skills: HashMap<String, SkillTableEntry>
+}
+
+fn deserialize_skill_table_entries<'de, D: Deserializer<'de>>(deserializer: D) -> Result<HashMap<String, SkillTableEntry>, D::Error> {
+  // operator skills always have 7 levels, plus 3 mastery levels if they can be mastered (see `into_split_levels`)
+  deserialize_map_filtered(deserializer, |_, entry: SkillTableEntry| (entry.levels.len() >= 7).then(|| entry))
+}
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub(super) struct SkillTableEntry {
+  #[serde(rename = "iconId")]
+  icon_id: Option<String>,
   levels: Vec<SkillTableLevel>
 }
 
 impl SkillTableEntry {
-  pub(super) fn split_levels(&self) -> Option<(&[SkillTableLevel; 7], Option<&[SkillTableLevel; 3]>)> {
+  pub(super) fn icon_id(&self) -> Option<&str> {
+    self.icon_id.as_deref()
+  }
+
+  pub(super) fn into_split_levels(self) -> Option<([SkillTableLevel; 7], Option<[SkillTableLevel; 3]>)> {
     if self.levels.len() < 7 { return None };
-    let (start, end) = self.levels.split_at(7);
-    let start: &[SkillTableLevel; 7] = start.try_into().ok()?;
-    let end: Option<&[SkillTableLevel; 3]> = end.try_into().ok();
+    let mut start = self.levels;
+    let end = start.split_off(7);
+    let start: [SkillTableLevel; 7] = start.try_into().ok()?;
+    let end: Option<[SkillTableLevel; 3]> = end.try_into().ok();
     Some((start, end))
   }
 
   pub(super) fn name_activation_recovery(&self) -> Option<(String, SkillActivation, SkillRecovery)> {
-    all_equal(self.levels.iter().map(|level| {
+    let (name, activation, recovery) = all_equal(self.levels.iter().map(|level| {
       let activation = level.skill_type.into_activation();
       let recovery = level.sp_data.sp_type.into_recovery();
-      (level.name.clone(), activation, recovery)
-    }))
+      (level.name.as_str(), activation, recovery)
+    }))?;
+
+    Some((name.to_owned(), activation, recovery))
+  }
+}
+
+/// The skill table, keeping track of how many operators still need each skill,
+/// so that skills are only cloned when they are shared between operators.
+#[derive(Debug, Clone)]
+pub(super) struct SkillTableMapped {
+  skills: HashMap<String, (usize, SkillTableEntry)>
+}
+
+impl SkillTableMapped {
+  pub(super) fn new(skill_table: SkillTable, uses: HashMap<String, usize>) -> Self {
+    let skills = skill_table.skills.into_iter()
+      .map(|(id, entry)| {
+        let uses = uses.get(&id).copied().unwrap_or(0);
+        (id, (uses, entry))
+      })
+      .collect();
+    SkillTableMapped { skills }
+  }
+
+  pub(super) fn take_skill(&mut self, id: &str) -> Option<SkillTableEntry> {
+    let (uses, entry) = self.skills.get_mut(id)?;
+    if *uses > 1 {
+      *uses -= 1;
+      Some(entry.clone())
+    } else {
+      self.skills.remove(id).map(|(_, entry)| entry)
+    }
   }
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub(super) struct SkillTableLevel {
   name: String,
   #[serde(rename = "rangeId")]
-  range_id: Option<String>,
+  range_id: Option<RangeId>,
   description: Option<String>,
   #[serde(rename = "skillType")]
   skill_type: SkillTableSkillType,
@@ -85,6 +139,7 @@ impl SkillTableLevel {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct SkillTableSpData {
   #[serde(rename = "spType")]
   sp_type: SkillTableSpType,
@@ -99,6 +154,7 @@ struct SkillTableSpData {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct SkillTableBlackboardEntry {
   key: String,
   value: f32
@@ -1,5 +1,5 @@
 use crate::format::*;
-use crate::game_data::OperatorSkin;
+use crate::game_data::{OperatorId, OperatorSkin, SkinId};
 
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
@@ -9,17 +9,18 @@ impl DataFile for SkinTable {
   const IDENTIFIER: &'static str = "skin_table";
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub(super) struct SkinTable {
   #[serde(rename = "charSkins")]
-  character_skins: HashMap<String, SkinTableCharacterSkin>,
+  character_skins: HashMap<SkinId, SkinTableCharacterSkin>,
   #[serde(rename = "buildinEvolveMap")]
   default_evolve_map: HashMap<String, SkinTableEvolutions>
 }
 
 impl SkinTable {
   pub(super) fn into_skin_table_mapped(mut self) -> SkinTableMapped {
-    let mut characters = HashMap::<String, SkinTableCharacterEntry>::new();
+    let mut characters = HashMap::<OperatorId, SkinTableCharacterEntry>::new();
     for (id, character_skin) in self.character_skins {
       if let Some(operator_skin) = character_skin.into_operator_skin() {
         let character_entry = match characters.entry(operator_skin.model_id.clone()) {
@@ -38,7 +39,7 @@ impl SkinTable {
   }
 }
 
-fn take_default_skins(default_evolve_map: &mut HashMap<String, SkinTableEvolutions>, id: &str) -> Option<[Option<String>; 3]> {
+fn take_default_skins(default_evolve_map: &mut HashMap<String, SkinTableEvolutions>, id: &str) -> Option<[Option<SkinId>; 3]> {
   default_evolve_map.remove(id).map(|mut default_evolutions| {
     [E0, E1, E2].map(|phase| default_evolutions.remove(&phase))
   })
@@ -46,7 +47,7 @@ fn take_default_skins(default_evolve_map: &mut HashMap<String, SkinTableEvolutio
 
 #[derive(Debug, Clone)]
 pub(super) struct SkinTableMapped {
-  characters: HashMap<String, SkinTableCharacterEntry>
+  characters: HashMap<OperatorId, SkinTableCharacterEntry>
 }
 
 impl SkinTableMapped {
@@ -57,12 +58,12 @@ impl SkinTableMapped {
 
 #[derive(Debug, Clone)]
 pub(super) struct SkinTableCharacterEntry {
-  pub(super) skins: crate::Map<String, OperatorSkin>,
-  pub(super) default_skins: [Option<String>; 3]
+  pub(super) skins: crate::Map<SkinId, OperatorSkin>,
+  pub(super) default_skins: [Option<SkinId>; 3]
 }
 
 impl SkinTableCharacterEntry {
-  fn new(default_skins: [Option<String>; 3]) -> Self {
+  fn new(default_skins: [Option<SkinId>; 3]) -> Self {
     SkinTableCharacterEntry {
       skins: crate::Map::new(),
       default_skins
@@ -71,11 +72,12 @@ impl SkinTableCharacterEntry {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct SkinTableCharacterSkin {
   #[serde(rename = "skinId")]
-  id: String,
+  id: SkinId,
   #[serde(rename = "charId")]
-  character_id: String,
+  character_id: OperatorId,
   #[serde(rename = "illustId")]
   illustration_id: Option<String>, // Always some for valid skins
   #[serde(rename = "dynIllustId")]
@@ -116,6 +118,7 @@ impl SkinTableCharacterSkin {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 struct SkinTableDisplaySkin {
   #[serde(rename = "skinName")]
   name: Option<String>, // Will be none if default outfit
@@ -133,7 +136,7 @@ struct SkinTableDisplaySkin {
   obtain: Option<String>
 }
 
-type SkinTableEvolutions = HashMap<SkinTableEvolvePhase, String>;
+type SkinTableEvolutions = HashMap<SkinTableEvolvePhase, SkinId>;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
 enum SkinTableEvolvePhase {
@@ -142,7 +145,10 @@ enum SkinTableEvolvePhase {
   #[serde(rename = "1")]
   Elite1,
   #[serde(rename = "2")]
-  Elite2
+  Elite2,
+  // promotions added to the game after this crate was last updated are ignored
+  #[serde(other)]
+  Other
 }
 
 const E0: SkinTableEvolvePhase = SkinTableEvolvePhase::Elite0;
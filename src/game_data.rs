@@ -9,88 +9,603 @@ use mint::Point2;
 pub use uord::UOrd;
 
 use std::cmp::Ordering;
+use std::fmt;
 use std::iter::{Chain, DoubleEndedIterator, Once};
 use std::num::NonZeroU8;
 use std::option::IntoIter as OptionIter;
-use std::ops::{Add, Deref};
+use std::ops::{Add, Deref, RangeInclusive};
 use std::path::Path;
+use std::str::FromStr;
 
 use crate::{Map, Set};
-use crate::options::Options;
+#[cfg(feature = "async")]
+use crate::options::{DataSource, Options};
+use crate::options::Region;
+
+mod base;
+mod builder;
+mod ids;
+mod index;
+mod localization;
+mod memory;
+mod query;
+mod report;
+mod schema;
+mod search;
+mod shared;
+mod validate;
+
+pub use self::base::{
+  dormitory_morale_recovery, BaseLayout, BaseSkillEffect, PlacedRoom, PlacementError,
+  RoomSimulation, StationedOperator, MAX_MORALE
+};
+pub use self::builder::{GameDataBuilder, TableSelection};
+pub use self::ids::{ItemId, ModuleId, OperatorId, RangeId, SkillId, SkinId};
+pub use self::index::GameDataIndex;
+pub use self::localization::{Localization, LocalizationBundle, MultiRegionGameData};
+pub use self::memory::{MemoryFootprint, ShrinkOptions};
+pub use self::query::{OperatorQuery, OperatorSort};
+pub use self::report::{ConversionReport, SkipReason, UpdateReport};
+pub use self::schema::{SchemaReport, TableSchemaReport, UnknownField};
+pub use self::search::normalize_name;
+pub use self::shared::SharedGameData;
+pub use self::validate::Violation;
 
 
 
 /// Encapsulates game data extracted from Arknights' game files.
 #[non_exhaustive]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GameData {
   /// The time this GameData was updated, if it was created from a remote source.
   pub last_updated: Option<DateTime<Utc>>,
-  /// Lists all of the pairs of alternate operators that exist.
-  pub alters: Vec<UOrd<String>>,
+  /// Where this GameData was obtained from, for attributing data derived from it.
+  pub provenance: Provenance,
+  /// The contents of `excel/data_version.txt` this GameData was created from, if it was available.
+  /// See [`GameData::get_outdated_version`].
+  #[serde(default)]
+  pub data_version: Option<String>,
+  /// Lists all of the groups of alternate operators that exist.
+  pub alters: Vec<AlterGroup>,
   /// A list of all obtainable operators in the game.
-  pub operators: Map<String, Operator>,
+  pub operators: Map<OperatorId, Operator>,
+  /// The IDs of every operator, grouped by profession and sorted by ID.
+  pub operators_by_profession: Map<Profession, Vec<OperatorId>>,
+  /// The IDs of every operator, grouped by sub-profession and sorted by ID.
+  pub operators_by_sub_profession: Map<SubProfession, Vec<OperatorId>>,
   /// A list of all items in the game.
-  pub items: Map<String, Item>,
+  pub items: Map<ItemId, Item>,
   /// A list of all RIIC base buildings.
   pub buildings: Map<BuildingType, Building>,
+  /// A list of all dormitory furniture, keyed by furniture ID.
+  #[serde(default)]
+  pub furniture: Map<String, Furniture>,
   /// A list of all operator attack ranges.
-  pub ranges: Map<String, AttackRange>,
+  pub ranges: Map<RangeId, AttackRange>,
   /// A list of all recruitment tags.
   pub recruitment_tags: Map<String, u32>,
+  /// Maps recruitment tag IDs to the IDs of every operator listing that tag, sorted by operator ID.
+  /// See [`Operator::iter_recruitment_tags`].
+  pub operators_by_recruitment_tag: Map<u32, Vec<OperatorId>>,
+  /// The IDs of every operator that can be obtained from recruitment.
+  pub recruitable_operators: Set<OperatorId>,
+  /// The operator rarities that each recruitment duration can yield, sorted from shortest to longest duration.
+  #[serde(default)]
+  pub recruitment_time_brackets: Vec<RecruitmentTimeBracket>,
   /// A list of all past, current and future banners according to the game files, sorted from oldest to newest.
   pub headhunting_banners: Vec<HeadhuntingBanner>,
+  /// A list of the beginner banners available to new players, which are not listed in [`GameData::headhunting_banners`].
+  #[serde(default)]
+  pub beginner_banners: Vec<BeginnerBanner>,
+  /// A list of all past, current and future special recruitment pools, sorted from oldest to newest.
+  /// These are not headhunting banners, and are not listed in [`GameData::headhunting_banners`].
+  #[serde(default)]
+  pub special_pools: Vec<SpecialRecruitmentPool>,
   /// A list of all past, current and future events according to the game files, sorted from oldest to newest.
-  pub events: Vec<Event>
+  pub events: Vec<Event>,
+  /// Leveling curves and other constants shared by every operator.
+  pub constants: GameConstants,
+  /// Lookup tables for finding operators and items without scanning every entry, built when this GameData is loaded.
+  /// Must be rebuilt with [`GameData::rebuild_index`] after modifying operators or items by hand.
+  #[serde(default)]
+  pub index: GameDataIndex,
+  /// Entries that were skipped while loading this GameData, instead of failing the whole load,
+  /// along with values in the game files that were added after this crate was last updated.
+  /// Malformed entries are only skipped when loading leniently, see [`GameDataBuilder::lenient`].
+  #[serde(default)]
+  pub load_warnings: Vec<LoadWarning>,
+  /// Every character that was not converted into an operator while loading this GameData, and why.
+  #[serde(default)]
+  pub conversion_report: ConversionReport
 }
 
 impl GameData {
+  /// The locations of every data file needed to construct a [`GameData`], relative to the `gamedata` folder.
+  pub const LOCATIONS: &'static [&'static str] = crate::format::DataFiles::LOCATIONS;
+
   /// Tries constructing a [`GameData`] instance from the given path.
   /// Note that the provided path should go to the `gamedata` folder, not the root folder of the repository.
+  #[cfg(feature = "async")]
   pub async fn from_local<P: AsRef<Path>>(path: P) -> Result<Self, crate::Error> {
-    let data_files = crate::format::DataFiles::from_local(path.as_ref()).await?;
-    Ok(data_files.into_game_data(None))
+    let data_files = crate::format::DataFiles::from_local(path.as_ref(), &TableSelection::ALL).await?;
+    let mut game_data = data_files.into_game_data(&TableSelection::ALL, None, Provenance::local(path.as_ref()), false)?;
+    game_data.data_version = crate::options::read_data_version(path.as_ref());
+    Ok(game_data)
+  }
+
+  /// Tries constructing a [`GameData`] instance from the given path, blocking the current thread.
+  /// Note that the provided path should go to the `gamedata` folder, not the root folder of the repository.
+  ///
+  /// Unlike [`GameData::from_local`], this is available without the `async` feature.
+  pub fn from_local_sync<P: AsRef<Path>>(path: P) -> Result<Self, crate::Error> {
+    let data_files = crate::format::DataFiles::from_local_sync(path.as_ref(), &TableSelection::ALL)?;
+    let mut game_data = data_files.into_game_data(&TableSelection::ALL, None, Provenance::local(path.as_ref()), false)?;
+    game_data.data_version = crate::options::read_data_version(path.as_ref());
+    Ok(game_data)
+  }
+
+  /// Tries constructing a [`GameData`] from data files held in memory, such as ones embedded with [`include_bytes!`]
+  /// or stored in a database. `get` is given the location of each data file relative to the `gamedata` folder
+  /// (see [`GameData::LOCATIONS`]), and should return its contents.
+  /// `get` is also given `"excel/data_version.txt"`, which fills in [`GameData::data_version`] if it is returned.
+  ///
+  /// ```no_run
+  /// # use ak_data::game_data::GameData;
+  /// # fn f(files: std::collections::HashMap<String, Vec<u8>>) -> Result<(), ak_data::Error> {
+  /// let game_data = GameData::from_bytes(|location| files.get(location))?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn from_bytes<F, B>(mut get: F) -> Result<Self, crate::Error>
+  where F: FnMut(&str) -> Option<B>, B: AsRef<[u8]> {
+    let data_files = crate::format::DataFiles::from_read_fn(&TableSelection::ALL, |location| {
+      get(location).ok_or_else(|| not_found(location))
+    })?;
+
+    let mut game_data = data_files.into_game_data(&TableSelection::ALL, None, Provenance::unknown(), false)?;
+    game_data.data_version = get(crate::options::DATA_VERSION_LOCATION)
+      .map(|contents| crate::options::parse_data_version(contents.as_ref()));
+    Ok(game_data)
+  }
+
+  /// Tries constructing a [`GameData`] from readers over data files, see [`GameData::from_bytes`].
+  pub fn from_readers<F, R>(mut get: F) -> Result<Self, crate::Error>
+  where F: FnMut(&str) -> Option<R>, R: std::io::Read {
+    let data_files = crate::format::DataFiles::from_read_fn(&TableSelection::ALL, |location| {
+      let mut reader = get(location).ok_or_else(|| not_found(location))?;
+      let mut contents = Vec::new();
+      reader.read_to_end(&mut contents)?;
+      Ok(contents)
+    })?;
+
+    let mut game_data = data_files.into_game_data(&TableSelection::ALL, None, Provenance::unknown(), false)?;
+    game_data.data_version = get(crate::options::DATA_VERSION_LOCATION).and_then(|mut reader| {
+      let mut contents = Vec::new();
+      reader.read_to_end(&mut contents).ok().map(|_| crate::options::parse_data_version(&contents))
+    });
+    Ok(game_data)
+  }
+
+  /// Tries constructing a [`GameData`] from a zip archive of the game data repository, without extracting it to disk.
+  /// Accepts anything readable and seekable, such as a [`File`][std::fs::File] or a [`Cursor`][std::io::Cursor] over some bytes.
+  ///
+  /// The archive may contain the repository at its root, or inside of a single top level folder
+  /// (as is the case for archives downloaded from GitHub).
+  /// [`GameData::data_version`] is filled in if the archive contains `excel/data_version.txt`.
+  #[cfg(feature = "zip")]
+  pub fn from_zip<R: std::io::Read + std::io::Seek>(reader: R, region: Region) -> Result<Self, crate::Error> {
+    use std::io::Read;
+
+    let mut archive = zip::ZipArchive::new(reader)?;
+    let prefix = format!("{region}/gamedata/");
+    let file_names = archive.file_names()
+      .filter_map(|file_name| {
+        // skip over the top level folder if there is one
+        let location = file_name.strip_prefix(&prefix)
+          .or_else(|| file_name.split_once('/')?.1.strip_prefix(&prefix))
+          .filter(|&location| {
+            crate::format::DataFiles::LOCATIONS.contains(&location) || location == crate::options::DATA_VERSION_LOCATION
+          })?;
+        Some((location.to_owned(), file_name.to_owned()))
+      })
+      .collect::<std::collections::HashMap<String, String>>();
+    let data_files = crate::format::DataFiles::from_read_fn(&TableSelection::ALL, |location| {
+      let file_name = file_names.get(location).ok_or(zip::result::ZipError::FileNotFound)?;
+      let mut contents = Vec::new();
+      archive.by_name(file_name)?.read_to_end(&mut contents)?;
+      Ok(contents)
+    })?;
+
+    let data_version = file_names.get(crate::options::DATA_VERSION_LOCATION).and_then(|file_name| {
+      let mut contents = Vec::new();
+      archive.by_name(file_name).ok()?.read_to_end(&mut contents).ok()?;
+      Some(crate::options::parse_data_version(&contents))
+    });
+
+    let provenance = Provenance { region: Some(region), ..Provenance::unknown() };
+    let mut game_data = data_files.into_game_data(&TableSelection::ALL, None, provenance, false)?;
+    game_data.data_version = data_version;
+    Ok(game_data)
+  }
+
+  /// Saves this [`GameData`] to the given file in a compact binary format, to be loaded again with [`GameData::load_cache`].
+  /// This is much faster to load than the game files themselves, or this struct serialized as JSON.
+  #[cfg(feature = "cache")]
+  pub fn save_cache<P: AsRef<Path>>(&self, path: P) -> Result<(), crate::Error> {
+    self.save_cache_with(path, CacheCompression::None)
+  }
+
+  /// Equivalent to [`GameData::save_cache`], compressing the cache with the given compression.
+  #[cfg(feature = "cache")]
+  pub fn save_cache_with<P: AsRef<Path>>(&self, path: P, compression: CacheCompression) -> Result<(), crate::Error> {
+    std::fs::write(path, self.to_cache_bytes(compression)?)?;
+    Ok(())
+  }
+
+  /// Serializes this [`GameData`] in the same format as [`GameData::save_cache_with`].
+  #[cfg(feature = "cache")]
+  pub fn to_cache_bytes(&self, compression: CacheCompression) -> Result<Vec<u8>, crate::Error> {
+    use std::io::Write;
+
+    let payload = bincode::serialize(self)?;
+    let mut checksum = flate2::Crc::new();
+    checksum.update(&payload);
+
+    let mut bytes = Vec::with_capacity(CACHE_HEADER_LEN + payload.len());
+    bytes.extend_from_slice(CACHE_MAGIC);
+    bytes.extend_from_slice(&CACHE_SCHEMA_VERSION.to_le_bytes());
+    bytes.push(compression as u8);
+    bytes.extend_from_slice(&checksum.sum().to_le_bytes());
+    match compression {
+      CacheCompression::None => bytes.extend_from_slice(&payload),
+      CacheCompression::Gzip => {
+        let mut encoder = flate2::write::GzEncoder::new(bytes, flate2::Compression::best());
+        encoder.write_all(&payload)?;
+        bytes = encoder.finish()?;
+      }
+    };
+
+    Ok(bytes)
+  }
+
+  /// Loads a [`GameData`] that was saved with [`GameData::save_cache`] or [`GameData::save_cache_with`].
+  ///
+  /// Caches are tied to the layout of [`GameData`], so caches saved by a different version of this crate
+  /// may be rejected with [`Error::IncompatibleCache`][crate::Error::IncompatibleCache], in which case they should be rebuilt.
+  #[cfg(feature = "cache")]
+  pub fn load_cache<P: AsRef<Path>>(path: P) -> Result<Self, crate::Error> {
+    GameData::from_cache_bytes(&std::fs::read(path)?)
+  }
+
+  /// Loads a [`GameData`] from a cache held in memory, such as one embedded with [`include_bytes!`].
+  /// See [`GameData::load_cache`].
+  #[cfg(feature = "cache")]
+  pub fn from_cache_bytes(bytes: &[u8]) -> Result<Self, crate::Error> {
+    use std::io::Read;
+
+    if bytes.len() < CACHE_HEADER_LEN || !bytes.starts_with(CACHE_MAGIC) {
+      return Err(crate::Error::IncompatibleCache(None));
+    };
+
+    let (header, contents) = bytes.split_at(CACHE_HEADER_LEN);
+    let version = u32::from_le_bytes(header[6..10].try_into().unwrap());
+    if version != CACHE_SCHEMA_VERSION {
+      return Err(crate::Error::IncompatibleCache(Some(version)));
+    };
+
+    let expected_checksum = u32::from_le_bytes(header[11..15].try_into().unwrap());
+    let payload = match header[10] {
+      0 => contents.to_vec(),
+      1 => {
+        let mut payload = Vec::new();
+        flate2::read::GzDecoder::new(contents).read_to_end(&mut payload)
+          .map_err(|_| crate::Error::CorruptCache)?;
+        payload
+      },
+      _ => return Err(crate::Error::CorruptCache)
+    };
+
+    let mut checksum = flate2::Crc::new();
+    checksum.update(&payload);
+    if checksum.sum() != expected_checksum {
+      return Err(crate::Error::CorruptCache);
+    };
+
+    let mut game_data: GameData = bincode::deserialize(&payload)?;
+    game_data.ensure_index();
+    Ok(game_data)
+  }
+
+  /// Serializes this [`GameData`] as MessagePack, keeping field names so that it can be read by other languages.
+  /// Unlike [`GameData::to_cache_bytes`], there is no header or checksum, which makes this better suited
+  /// for sending game data (or a subset of it, see [`TableSelection`]) between services.
+  #[cfg(feature = "msgpack")]
+  pub fn to_msgpack(&self) -> Result<Vec<u8>, crate::Error> {
+    Ok(rmp_serde::to_vec_named(self)?)
+  }
+
+  /// Deserializes a [`GameData`] that was serialized as MessagePack with [`GameData::to_msgpack`].
+  #[cfg(feature = "msgpack")]
+  pub fn from_msgpack(bytes: &[u8]) -> Result<Self, crate::Error> {
+    let mut game_data: GameData = rmp_serde::from_slice(bytes)?;
+    game_data.ensure_index();
+    Ok(game_data)
   }
 
   /// Tries constructing a [`GameData`] from a remote GitHub repository.
   /// The [`Options`] instance will dictate which repository to fetch from.
+  #[cfg(feature = "async")]
   pub async fn from_remote(options: &Options) -> Result<Self, crate::Error> {
     options.request_game_data().await
   }
 
+  /// Tries constructing a [`GameData`] instance from the given path, blocking the current thread.
+  /// This is the same as [`GameData::from_local_sync`], and is only provided alongside [`GameData::from_remote_blocking`].
+  #[cfg(feature = "blocking")]
+  pub fn from_local_blocking<P: AsRef<Path>>(path: P) -> Result<Self, crate::Error> {
+    GameData::from_local_sync(path)
+  }
+
+  /// Tries constructing a [`GameData`] from a remote GitHub repository, blocking the current thread.
+  /// This drives a single-threaded runtime internally, and must not be called from inside of an async runtime.
+  #[cfg(feature = "blocking")]
+  pub fn from_remote_blocking(options: &Options) -> Result<Self, crate::Error> {
+    block_on(GameData::from_remote(options))
+  }
+
+  /// Tries constructing a [`GameData`] from a custom [`DataSource`], such as a mirror or a set of test fixtures.
+  #[cfg(feature = "async")]
+  pub async fn from_source<S: DataSource>(source: &S) -> Result<Self, crate::Error> {
+    crate::options::request_game_data_from(source, &TableSelection::ALL, false).await
+  }
+
   /// Patches this [`GameData`] if the data it is based on is out of date.
   /// Replaces `self` and returns it if it was out of date.
+  #[cfg(feature = "async")]
   pub async fn patch_from_remote(&mut self, options: &Options) -> Result<Option<Self>, crate::Error> {
     options.patch_game_data(self).await
   }
 
+  /// Downloads only the selected tables again, replacing those parts of this [`GameData`] and leaving the rest untouched.
+  /// Parts that depend on each other are refreshed together, see [`TableSelection`].
+  ///
+  /// Because the rest of the game data is not refreshed, [`GameData::last_updated`],
+  /// [`GameData::provenance`] and [`GameData::data_version`] are left untouched as well.
+  #[cfg(feature = "async")]
+  pub async fn refresh_tables(&mut self, options: &Options, tables: TableSelection) -> Result<(), crate::Error> {
+    let tables = tables.with_dependencies();
+    let game_data = options.request_game_data_with(&tables, false).await?;
+    self.merge_selected(game_data, &tables);
+    Ok(())
+  }
+
+  /// Equivalent to [`GameData::refresh_tables`], loading the selected tables from a custom [`DataSource`] instead.
+  #[cfg(feature = "async")]
+  pub async fn refresh_tables_from_source<S: DataSource>(&mut self, source: &S, tables: TableSelection)
+  -> Result<(), crate::Error> {
+    let tables = tables.with_dependencies();
+    let game_data = crate::options::request_game_data_from(source, &tables, false).await?;
+    self.merge_selected(game_data, &tables);
+    Ok(())
+  }
+
   /// Gets the last updated time from the remote repository.
   /// If that time indicates that this [`GameData`] is out of date, the time is returned.
   /// Otherwise returns `None`.
+  #[cfg(feature = "async")]
   pub async fn get_outdated(&self, options: &Options) -> Result<Option<DateTime<Utc>>, crate::Error> {
     let last_updated = options.get_last_updated().await?;
     Ok(self.is_outdated(last_updated).then(|| last_updated))
   }
 
+  /// Gets the data version from the remote repository, which only requires downloading `excel/data_version.txt`.
+  /// If that version differs from the one included in this [`GameData`], the version is returned.
+  /// Otherwise returns `None`.
+  ///
+  /// This is much cheaper than [`GameData::get_outdated`], making it better suited to frequent polling.
+  #[cfg(feature = "async")]
+  pub async fn get_outdated_version(&self, options: &Options) -> Result<Option<String>, crate::Error> {
+    let data_version = options.get_data_version().await?;
+    Ok((self.data_version.as_deref() != Some(data_version.as_str())).then_some(data_version))
+  }
+
+  /// The SHA of the commit this game data was built from, if known. Shorthand for [`Provenance::commit`].
+  /// See also [`Provenance::cache_key`].
+  pub fn commit(&self) -> Option<&str> {
+    self.provenance.commit.as_deref()
+  }
+
   /// Returns true if the given date time is more recent than the update time included in this game data.
   pub fn is_outdated(&self, new_date_time: DateTime<Utc>) -> bool {
     self.last_updated.map_or(true, |last_updated| last_updated < new_date_time)
   }
 
   /// Takes an operator ID, returns the operator ID if an alter exists corresponding to it.
+  /// If the operator has more than one alter, only the first one is returned.
   pub fn get_alter_for(&self, operator: &str) -> Option<&str> {
+    self.get_alter_group(operator)
+      .and_then(|alter_group| alter_group.iter_others(operator).next())
+  }
+
+  /// Takes an operator ID, returns the [`AlterGroup`] that operator belongs to, if any.
+  pub fn get_alter_group(&self, operator: &str) -> Option<&AlterGroup> {
+    self.alters.iter().find(|alter_group| alter_group.contains(operator))
+  }
+
+  /// Takes an operator ID, returns the [`Operator`] if an alter exists corresponding to it.
+  /// If the operator has more than one alter, only the first one is returned.
+  pub fn get_alter_operator(&self, operator: &str) -> Option<&Operator> {
+    self.get_alter_group(operator).and_then(|alter_group| {
+      alter_group.iter_others(operator).find_map(|id| self.operators.get(id))
+    })
+  }
+
+  /// Returns an iterator over every pair of alternate operators, resolved to their [`Operator`]s.
+  /// Pairs where either operator does not exist in [`GameData::operators`] are skipped.
+  pub fn alter_pairs_resolved(&self) -> impl Iterator<Item = (&Operator, &Operator)> {
     self.alters.iter()
-      .find_map(|alter_group| alter_group.other(operator))
-      .map(String::as_str)
+      .flat_map(AlterGroup::iter_member_pairs)
+      .filter_map(|(a, b)| Option::zip(self.operators.get(a), self.operators.get(b)))
+  }
+
+  /// Returns an iterator over all operators belonging to the given faction ID (a nation, group or team).
+  ///
+  /// When `include_sub_groups` is true, members of groups and teams that belong to the faction are included too.
+  /// (Example: asking for `"victoria"` includes members of `"glasgow"`)
+  /// Otherwise, only operators whose most specific faction is the given one are returned.
+  pub fn iter_operators_by_faction<'a>(&'a self, faction_id: &'a str, include_sub_groups: bool)
+  -> impl Iterator<Item = &'a Operator> + 'a {
+    self.index.iter_operators_by_faction(self, faction_id, include_sub_groups)
+  }
+
+  /// Finds the faction ID that the given group or team ID belongs to, based on the affiliations of every operator.
+  pub fn get_faction_parent(&self, faction_id: &str) -> Option<&str> {
+    self.index.get_faction_parent(faction_id)
+  }
+
+  /// Returns an iterator over the faction IDs an operator belongs to, from most specific to least specific,
+  /// including any parent factions that the operator does not list themselves.
+  pub fn iter_faction_hierarchy<'a>(&'a self, operator: &'a Operator) -> impl Iterator<Item = &'a str> + 'a {
+    self.index.iter_faction_hierarchy(operator)
+  }
+
+  /// Returns a builder for filtering and sorting operators. See [`OperatorQuery`].
+  pub fn query_operators(&self) -> OperatorQuery {
+    OperatorQuery::new(self)
+  }
+
+  /// Returns an iterator over every operator with the given profession, sorted by ID.
+  pub fn iter_operators_by_profession(&self, profession: Profession) -> impl Iterator<Item = &Operator> + DoubleEndedIterator {
+    self.resolve_operators(self.operators_by_profession.get(&profession))
+  }
+
+  /// Returns an iterator over every operator with the given sub-profession, sorted by ID.
+  pub fn iter_operators_by_sub_profession(&self, sub_profession: SubProfession) -> impl Iterator<Item = &Operator> + DoubleEndedIterator {
+    self.resolve_operators(self.operators_by_sub_profession.get(&sub_profession))
+  }
+
+  fn resolve_operators<'a>(&'a self, ids: Option<&'a Vec<OperatorId>>) -> impl Iterator<Item = &'a Operator> + DoubleEndedIterator {
+    ids.into_iter().flatten().filter_map(|id| self.operators.get(id))
+  }
+
+  /// Returns an iterator over every operator, sorted from highest to lowest rarity, then by name.
+  pub fn iter_operators_sorted_by_rarity(&self) -> impl Iterator<Item = &Operator> + DoubleEndedIterator + ExactSizeIterator {
+    self.query_operators().sort_by(OperatorSort::RarityDescending).iter()
+  }
+
+  /// Groups every operator by their profession.
+  /// Operators within each group are sorted from highest to lowest rarity, then by name.
+  pub fn group_operators_by_profession(&self) -> Map<Profession, Vec<&Operator>> {
+    let mut groups = Map::<Profession, Vec<&Operator>>::new();
+    for operator in self.iter_operators_sorted_by_rarity() {
+      groups.entry(operator.profession).or_default().push(operator);
+    };
+
+    groups
   }
 
-  /// Searches for an operator, given their in-game name.
+  /// Returns whether or not the given operator ID can be obtained from recruitment.
+  pub fn is_operator_recruitable(&self, operator: &str) -> bool {
+    self.recruitable_operators.contains(operator)
+  }
+
+  /// Returns whether or not the given operator ID is a limited operator on any headhunting banner.
+  pub fn is_operator_limited(&self, operator: &str) -> bool {
+    self.headhunting_banners.iter().any(|banner| {
+      banner.rules.limited_operator_ids.iter().any(|id| id == operator)
+    })
+  }
+
+  /// Returns an iterator over every operator that lists the given recruitment tag, sorted by ID.
+  /// The tag may be given either by its name (region dependent) or by its numeric ID.
+  ///
+  /// Operators that cannot be recruited are included too, see [`GameData::is_operator_recruitable`].
+  pub fn operators_with_tag(&self, tag: &str) -> impl Iterator<Item = &Operator> + DoubleEndedIterator {
+    let tag_id = self.recruitment_tags.get(tag).copied().or_else(|| tag.parse::<u32>().ok());
+    self.resolve_operators(tag_id.and_then(|tag_id| self.operators_by_recruitment_tag.get(&tag_id)))
+  }
+
+  /// Returns the range of operator rarities that a recruitment lasting the given number of hours can yield,
+  /// or `None` if the duration is shorter than every bracket in [`GameData::recruitment_time_brackets`].
+  pub fn recruitment_rarities(&self, hours: u32) -> Option<RangeInclusive<u8>> {
+    self.recruitment_time_brackets.iter()
+      .rev().find(|bracket| bracket.min_hours <= hours)
+      .map(RecruitmentTimeBracket::rarities)
+  }
+
+  /// Returns the shortest recruitment duration in hours that can no longer yield operators below the given rarity,
+  /// such as the 9 hours needed to rule out 1 and 2-star operators.
+  pub fn recruitment_hours_for(&self, min_rarity: u8) -> Option<u32> {
+    self.recruitment_time_brackets.iter()
+      .find(|bracket| bracket.min_rarity >= min_rarity)
+      .map(|bracket| bracket.min_hours)
+  }
+
+  /// Searches for an operator, given their in-game name, ignoring case.
   /// Please remember that names are region dependent!
+  ///
+  /// If several operators share the name, the one with the lowest ID is returned.
   pub fn find_operator(&self, operator_name: impl AsRef<str>) -> Option<&Operator> {
-    let operator_name = operator_name.as_ref();
-    self.operators.values().find(|&operator| {
-      operator.name.eq_ignore_ascii_case(operator_name)
-    })
+    let operator_name = operator_name.as_ref().to_lowercase();
+    if self.index.names.is_empty() {
+      // the index has not been built, such as for game data deserialized by hand
+      return self.operators.values().find(|&operator| operator.name.to_lowercase() == operator_name);
+    };
+
+    let id = self.index.names.get(&operator_name)?;
+    self.operators.get(id)
+  }
+
+  /// Searches for an operator, given their in-game name, appellation or display number, ignoring case.
+  /// See [`GameDataIndex::find_operator_id`].
+  pub fn lookup_operator(&self, query: impl AsRef<str>) -> Option<&Operator> {
+    if self.index.names.is_empty() {
+      return self.build_index().find_operator_id(query).and_then(|id| self.operators.get(id));
+    };
+
+    self.index.find_operator(self, query)
+  }
+
+  /// Builds lookup tables for quickly finding operators by name, appellation or display number.
+  /// These are already built when game data is loaded, see [`GameData::index`].
+  pub fn build_index(&self) -> GameDataIndex {
+    GameDataIndex::new(self)
+  }
+
+  /// Rebuilds [`GameData::index`], which is necessary after modifying operators or items by hand.
+  pub fn rebuild_index(&mut self) {
+    self.index = GameDataIndex::new(self);
+  }
+
+  // game data serialized without its index (or by an older version of this crate) deserializes with an empty one
+  #[cfg(any(feature = "cache", feature = "msgpack"))]
+  fn ensure_index(&mut self) {
+    let outdated = self.index.names.is_empty() || self.index.operators_by_faction.is_empty();
+    if outdated && !self.operators.is_empty() {
+      self.rebuild_index();
+    };
+  }
+
+  /// Searches for operators whose name or appellation resembles the given name, tolerating typos,
+  /// accents, punctuation and a leading "the". Useful for handling free-form user input.
+  ///
+  /// Returns matching operators and their scores (from `0.0` to `1.0`), sorted from best to worst match.
+  pub fn search_operators(&self, operator_name: impl AsRef<str>) -> Vec<(&Operator, f32)> {
+    let query = normalize_name(operator_name.as_ref());
+    let mut results = self.operators.values()
+      .filter_map(|operator| {
+        let score = std::iter::once(&operator.name)
+          .chain(operator.appellation.as_ref())
+          .map(|name| self::search::score_normalized(&query, &normalize_name(name)))
+          .fold(0.0, f32::max);
+        (score >= self::search::SEARCH_THRESHOLD).then(|| (operator, score))
+      })
+      .collect::<Vec<(&Operator, f32)>>();
+    results.sort_by(|(a, a_score), (b, b_score)| {
+      b_score.total_cmp(a_score).then_with(|| a.name.cmp(&b.name))
+    });
+    results
   }
 
   /// Searches for an item, given its in-game name.
@@ -102,6 +617,78 @@ impl GameData {
     })
   }
 
+  /// Returns an iterator over every item, sorted from lowest to highest rarity, then by ID.
+  pub fn iter_items_sorted_by_rarity(&self) -> impl Iterator<Item = &Item> + DoubleEndedIterator + ExactSizeIterator {
+    let mut items = self.items.values().collect::<Vec<&Item>>();
+    items.sort_by_key(|item| item.rarity);
+    items.into_iter()
+  }
+
+  /// Returns an iterator over every item of the given class, sorted by ID.
+  pub fn items_by_class(&self, item_class: ItemClass) -> impl Iterator<Item = &Item> + DoubleEndedIterator {
+    self.index.iter_items_by_class(self, item_class)
+  }
+
+  /// Returns an iterator over every item of the given raw item type, sorted by ID. (Example: `"CARD_EXP"` for EXP cards)
+  pub fn items_by_type<'a>(&'a self, item_type: &'a str) -> impl Iterator<Item = &'a Item> + DoubleEndedIterator {
+    self.index.iter_items_by_type(self, item_type)
+  }
+
+  /// Returns an iterator over every upgrade material of the given kind and tier, sorted by ID. See [`Item::tier`].
+  pub fn materials_by_tier(&self, tier: MaterialTier) -> impl Iterator<Item = &Item> + DoubleEndedIterator {
+    self.index.iter_materials_by_tier(self, tier)
+  }
+
+  /// Returns every operator promotion, skill level, skill mastery, module and building upgrade that consumes the given item.
+  pub fn item_usages(&self, item_id: &str) -> &[ItemUsage] {
+    self.index.item_usages(item_id)
+  }
+
+  /// Returns an iterator over every item consumed by every operator promotion, skill level, skill mastery,
+  /// module and building upgrade, paired with the ID of the item being consumed.
+  pub fn iter_item_usages(&self) -> impl Iterator<Item = (&str, ItemUsage)> {
+    fn costs<'a>(cost: &'a ItemsCost, f: impl Fn(u32) -> ItemUsage + 'a) -> impl Iterator<Item = (&'a str, ItemUsage)> + 'a {
+      cost.iter().map(move |(item_id, &count)| (item_id.as_str(), f(count)))
+    }
+
+    let operators = self.operators.values().flat_map(|operator| {
+      let promotions = [Promotion::Elite1, Promotion::Elite2].into_iter()
+        .filter_map(move |promotion| operator.promotions.get(promotion).map(|p| (promotion, p)))
+        .flat_map(move |(promotion, operator_promotion)| costs(&operator_promotion.upgrade_cost, move |count| {
+          ItemUsage::Promotion { operator_id: operator.id.clone(), promotion, count }
+        }));
+      let skill_levels = operator.skill_level_upgrades.iter().zip(2..).flat_map(move |(skill_level_upgrade, level)| {
+        costs(&skill_level_upgrade.upgrade_cost, move |count| ItemUsage::SkillLevel {
+          operator_id: operator.id.clone(), level, count
+        })
+      });
+      let masteries = operator.skills.iter()
+        .filter_map(|skill| skill.mastery.as_ref().map(|mastery| (skill, mastery)))
+        .flat_map(move |(skill, mastery)| mastery.iter().zip(1..).flat_map(move |(skill_mastery, level)| {
+          costs(&skill_mastery.upgrade_cost, move |count| ItemUsage::SkillMastery {
+            operator_id: operator.id.clone(), skill_id: skill.id.clone(), level, count
+          })
+        }));
+      let modules = operator.modules.iter().flat_map(move |module| {
+        costs(&module.upgrade_cost, move |count| ItemUsage::Module {
+          operator_id: operator.id.clone(), module_id: module.id.clone(), count
+        })
+      });
+
+      promotions.chain(skill_levels).chain(masteries).chain(modules)
+    });
+
+    let buildings = self.buildings.values().flat_map(|building| {
+      building.upgrades.iter().zip(1..).flat_map(move |(building_upgrade, level)| {
+        costs(&building_upgrade.construction_cost, move |count| ItemUsage::BuildingUpgrade {
+          building_type: building.building_type, level, count
+        })
+      })
+    });
+
+    operators.chain(buildings)
+  }
+
   /// Returns an iterator over all headhunting banners based on a filter, from oldest to newest.
   pub fn iter_banners(&self, now: DateTime<Utc>, tense: Tense)
   -> impl Iterator<Item = &HeadhuntingBanner> + DoubleEndedIterator {
@@ -109,20 +696,275 @@ impl GameData {
     self.headhunting_banners.iter().filter(move |banner| predicate(banner, now))
   }
 
+  /// Returns an iterator over all headhunting banners that run alongside the given event ID, from oldest to newest.
+  /// See [`HeadhuntingBanner::event_id`].
+  pub fn iter_event_banners<'a>(&'a self, event_id: &'a str)
+  -> impl Iterator<Item = &'a HeadhuntingBanner> + DoubleEndedIterator {
+    self.headhunting_banners.iter().filter(move |banner| banner.event_id.as_deref() == Some(event_id))
+  }
+
   /// Returns an iterator over all events based on a filter, from oldest to newest.
   pub fn iter_events(&self, now: DateTime<Utc>, tense: Tense)
   -> impl Iterator<Item = &Event> + DoubleEndedIterator {
     let predicate = tense.into_event_predicate();
     self.events.iter().filter(move |event| predicate(event, now))
   }
+
+  /// Searches for a headhunting banner given its name, ignoring case.
+  /// If no banner has exactly that name, a banner whose name contains it is returned instead.
+  /// When several banners match (such as reruns), the newest one is preferred.
+  pub fn find_banner(&self, banner_name: impl AsRef<str>) -> Option<&HeadhuntingBanner> {
+    find_by_name(&self.headhunting_banners, banner_name.as_ref(), |banner| &banner.name)
+  }
+
+  /// Searches for an event given its name, ignoring case.
+  /// If no event has exactly that name, an event whose name contains it is returned instead.
+  /// When several events match (such as reruns), the newest one is preferred.
+  pub fn find_event(&self, event_name: impl AsRef<str>) -> Option<&Event> {
+    find_by_name(&self.events, event_name.as_ref(), |event| &event.name)
+  }
+
+  /// Compares the attributes of two operators (given their IDs) at the same promotion, level and trust percentage.
+  /// Returns `None` if either operator does not exist or cannot reach the given promotion.
+  pub fn compare_operators(&self, a: &str, b: &str, promotion_and_level: PromotionAndLevel, trust: u32)
+  -> Option<OperatorComparison> {
+    let a = self.operators.get(a)?.get_attributes(promotion_and_level, trust)?;
+    let b = self.operators.get(b)?.get_attributes(promotion_and_level, trust)?;
+    Some(OperatorComparison::new(a, b))
+  }
+
+  /// Returns an iterator over all events that are open at any point between `start` and `end`, from oldest to newest.
+  pub fn iter_events_between(&self, start: DateTime<Utc>, end: DateTime<Utc>)
+  -> impl Iterator<Item = &Event> + DoubleEndedIterator {
+    self.events.iter().filter(move |event| event.overlaps(start, end))
+  }
+
+  /// Returns an iterator over all headhunting banners that are open at any point between `start` and `end`, from oldest to newest.
+  pub fn iter_banners_between(&self, start: DateTime<Utc>, end: DateTime<Utc>)
+  -> impl Iterator<Item = &HeadhuntingBanner> + DoubleEndedIterator {
+    self.headhunting_banners.iter().filter(move |banner| banner.overlaps(start, end))
+  }
+
+  /// Returns an iterator over all currently open events, from oldest to newest.
+  pub fn current_events(&self, now: DateTime<Utc>) -> impl Iterator<Item = &Event> + DoubleEndedIterator {
+    self.iter_events(now, Tense::Current)
+  }
+
+  /// Returns an iterator over all currently open headhunting banners, from oldest to newest.
+  pub fn current_banners(&self, now: DateTime<Utc>) -> impl Iterator<Item = &HeadhuntingBanner> + DoubleEndedIterator {
+    self.iter_banners(now, Tense::Current)
+  }
+
+  /// Gets the soonest event that has yet to open, if any are known.
+  pub fn next_event(&self, now: DateTime<Utc>) -> Option<&Event> {
+    self.iter_events(now, Tense::Future).next()
+  }
+
+  /// Gets the soonest headhunting banner that has yet to open, if any are known.
+  pub fn next_banner(&self, now: DateTime<Utc>) -> Option<&HeadhuntingBanner> {
+    self.iter_banners(now, Tense::Future).next()
+  }
+
+  /// Returns an iterator over all events and headhunting banners based on a filter, merged from oldest to newest.
+  /// Events and banners opening at the same time are ordered with events first.
+  pub fn iter_timeline(&self, now: DateTime<Utc>, tense: Tense)
+  -> impl Iterator<Item = TimelineEntry> + DoubleEndedIterator {
+    let mut timeline = self.iter_events(now, tense).map(TimelineEntry::Event)
+      .chain(self.iter_banners(now, tense).map(TimelineEntry::Banner))
+      .collect::<Vec<TimelineEntry>>();
+    timeline.sort_by_key(TimelineEntry::open_time);
+    timeline.into_iter()
+  }
+}
+
+#[cfg(feature = "blocking")]
+fn block_on<T>(future: impl std::future::Future<Output = Result<T, crate::Error>>) -> Result<T, crate::Error> {
+  tokio::runtime::Builder::new_current_thread()
+    .enable_all()
+    .build()?
+    .block_on(future)
+}
+
+/// How a cache saved with [`GameData::save_cache_with`] is compressed.
+#[cfg(feature = "cache")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheCompression {
+  /// The cache is not compressed, making it the fastest to load.
+  None = 0,
+  /// The cache is compressed with gzip, making it several times smaller.
+  Gzip = 1
+}
+
+#[cfg(feature = "cache")]
+impl Default for CacheCompression {
+  #[inline]
+  fn default() -> Self {
+    CacheCompression::None
+  }
+}
+
+// caches start with the magic bytes, the schema version, the compression,
+// and a CRC-32 checksum of the uncompressed contents, followed by the (possibly compressed) contents
+#[cfg(feature = "cache")]
+const CACHE_MAGIC: &[u8; 6] = b"AKDATA";
+#[cfg(feature = "cache")]
+const CACHE_HEADER_LEN: usize = 15;
+/// Incremented whenever the layout of [`GameData`] or of the cache itself changes, invalidating every existing cache.
+#[cfg(feature = "cache")]
+const CACHE_SCHEMA_VERSION: u32 = 20;
+
+fn not_found(location: &str) -> crate::Error {
+  std::io::Error::new(std::io::ErrorKind::NotFound, format!("{location} was not provided")).into()
 }
 
+// entries are sorted from oldest to newest, so searching in reverse prefers the newest match
+fn find_by_name<'a, T, F>(entries: &'a [T], name: &str, f: F) -> Option<&'a T>
+where F: Fn(&T) -> &String {
+  let name = name.to_lowercase();
+  entries.iter().rev().find(|&entry| f(entry).to_lowercase() == name)
+    .or_else(|| entries.iter().rev().find(|&entry| f(entry).to_lowercase().contains(&name)))
+}
+
+/// Describes where a [`GameData`] was obtained from.
+///
+/// Tools that redistribute data derived from a [`GameData`] can use this to attribute their source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Provenance {
+  /// The host the game data was fetched from, if it was not GitHub. (Example: `"gitlab.com"`)
+  /// For game data fetched from a [`Source::RawUrl`][crate::options::Source::RawUrl], this is the base URL.
+  pub host: Option<String>,
+  /// The owner (`0`) and repository (`1`) of the repository the game data was fetched from, if any.
+  pub repository: Option<(String, String)>,
+  /// The branch of that repository the game data was fetched from, if any.
+  pub branch: Option<String>,
+  /// The SHA of the commit the game data was fetched from, if known.
+  pub commit: Option<String>,
+  /// The region of the game data, if known.
+  pub region: Option<Region>,
+  /// The time the game data was fetched or loaded.
+  pub fetched_at: DateTime<Utc>
+}
+
+impl Provenance {
+  /// Creates a [`Provenance`] for game data loaded from the given local `gamedata` folder.
+  /// The region is guessed from the name of the folder's parent, if possible.
+  pub fn local(path: &Path) -> Self {
+    let region = path.parent()
+      .and_then(Path::file_name)
+      .and_then(|name| name.to_str())
+      .and_then(|name| name.parse::<Region>().ok());
+    Provenance { region, ..Provenance::unknown() }
+  }
+
+  /// Creates a [`Provenance`] for game data from an unknown source, such as files held in memory.
+  pub fn unknown() -> Self {
+    Provenance {
+      host: None,
+      repository: None,
+      branch: None,
+      commit: None,
+      region: None,
+      fetched_at: Utc::now()
+    }
+  }
+
+  /// Produces a short, human-readable line attributing the source of the game data.
+  pub fn attribution(&self) -> String {
+    let mut attribution = match (&self.host, &self.repository) {
+      (host, Some((owner, repo))) => {
+        let host = host.as_deref().unwrap_or("github.com");
+        format!("Arknights game data from https://{host}/{owner}/{repo}")
+      },
+      (Some(host), None) => format!("Arknights game data from {host}"),
+      (None, None) => "Arknights game data from local files".to_owned()
+    };
+
+    match (&self.branch, &self.commit) {
+      (Some(branch), Some(commit)) => attribution.push_str(&format!(" ({branch} @ {commit})")),
+      (Some(branch), None) => attribution.push_str(&format!(" ({branch})")),
+      (None, Some(commit)) => attribution.push_str(&format!(" ({commit})")),
+      (None, None) => ()
+    };
+
+    if let Some(region) = self.region {
+      attribution.push_str(&format!(", region {region}"));
+    };
+
+    attribution.push_str(&format!(", fetched {}", self.fetched_at.to_rfc3339()));
+    attribution
+  }
+
+  /// Produces a key that identifies the exact game data described by this provenance,
+  /// made up of the host, repository, commit and region. Unlike [`GameData::last_updated`],
+  /// this is stable across refetches and differs between regions, making it suitable for keying caches.
+  ///
+  /// Returns `None` if the commit is not known, such as for game data loaded from local files.
+  pub fn cache_key(&self) -> Option<String> {
+    let commit = self.commit.as_deref()?;
+    let host = self.host.as_deref().unwrap_or("github.com");
+    let repository = self.repository.as_ref().map_or(String::new(), |(owner, repo)| format!("/{owner}/{repo}"));
+    let region = self.region.map_or("", Region::to_str);
+    Some(format!("{host}{repository}@{commit}:{region}"))
+  }
+}
+
+/// An entry of a data file that could not be fully loaded into a [`GameData`], see [`GameData::load_warnings`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LoadWarning {
+  /// The identifier of the data file the entry belongs to. (Example: `"character_table"`)
+  pub table: String,
+  /// The ID of the entry within that data file. (Example: `"char_002_amiya"`)
+  pub id: String,
+  /// Whether the entry was skipped, or kept with a value this crate does not recognize.
+  #[serde(default)]
+  pub kind: LoadWarningKind,
+  /// Why the entry was skipped, or which value was not recognized.
+  pub message: String
+}
+
+impl LoadWarning {
+  /// Creates a warning for an entry that was skipped.
+  pub fn new(table: impl Into<String>, id: impl Into<String>, message: impl Into<String>) -> Self {
+    LoadWarning { table: table.into(), id: id.into(), kind: LoadWarningKind::Skipped, message: message.into() }
+  }
+
+  /// Creates a warning for an entry that was kept, but contains a value this crate does not recognize.
+  pub fn unrecognized(table: impl Into<String>, id: impl Into<String>, message: impl Into<String>) -> Self {
+    LoadWarning { table: table.into(), id: id.into(), kind: LoadWarningKind::Unrecognized, message: message.into() }
+  }
+}
+
+impl fmt::Display for LoadWarning {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.kind {
+      LoadWarningKind::Skipped => write!(f, "skipped {} in {}: {}", self.id, self.table, self.message),
+      LoadWarningKind::Unrecognized => write!(f, "unrecognized value in {} in {}: {}", self.id, self.table, self.message)
+    }
+  }
+}
+
+/// What happened to an entry listed in a [`LoadWarning`].
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum LoadWarningKind {
+  /// The entry was left out of the game data.
+  #[default]
+  Skipped,
+  /// The entry was kept, with the value it did not recognize treated as a fallback. (Such as an `Other` variant)
+  Unrecognized
+}
+
+
 /// An operator.
 #[non_exhaustive]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Operator {
   /// This operator's internal ID.
-  pub id: String,
+  pub id: OperatorId,
   /// This operator's name, region dependent.
   pub name: String,
   /// The nation this operator belongs to, region independent. (Example: `"victoria"` for Bagpipe)
@@ -133,11 +975,25 @@ pub struct Operator {
   pub team_id: Option<String>,
   /// A three or four letter code that is displayed in the in-game archive screen. (Example: `"LT77"` for Mostima)
   pub display_number: String,
+  /// The key the game uses to order operators in lists, lower values are shown first.
+  /// Not present in older game files.
+  pub sort_index: Option<i32>,
   /// Appears to be for an 'alternate name' like the Ursus operators' cyrillic names.
   /// (On non-EN regions, the appellation will be the operator's name in latin script)
   pub appellation: Option<String>,
-  /// Whether this operator is ranged or melee.
-  /// (Note that operators that can do both may show as melee)
+  /// This operator's trait as shown in-game, with the trait effect values of its first phase filled in.
+  /// (Example: `"Blocks 3 enemies"` for Cuora)
+  /// See [`Operator::traits`] for the trait as it changes through promotions.
+  pub description: Option<String>,
+  /// The phases of this operator's trait, with trait effect values filled in.
+  /// Empty if the trait has no values that change.
+  pub traits: Vec<OperatorTraitPhase>,
+  /// The first line of this operator's description on their recruitment card, region dependent.
+  pub item_usage: Option<String>,
+  /// The second line of this operator's description on their recruitment card, region dependent.
+  pub item_description: Option<String>,
+  /// Whether this operator is ranged, melee, or can be deployed as either.
+  /// (Note that some operators that can do both still show as melee)
   pub position: Position,
   /// The recruitment tags for this operator, region dependent.
   pub recruitment_tags: Vec<String>,
@@ -148,36 +1004,50 @@ pub struct Operator {
   /// The operator's secondary sub-profession.
   pub sub_profession: SubProfession,
   /// A list of promotions that this operator can achieve.
-  pub promotions: OperatorPromotions,
+  ///
+  /// Boxed, along with [`Operator::file`], to keep operators small enough to move around cheaply.
+  pub promotions: Box<OperatorPromotions>,
   /// The item required to upgrade this operator's potential.
-  pub potential_item_id: Option<String>,
+  pub potential_item_id: Option<ItemId>,
   /// This operator's potential upgrades. Will almost always be length 5.
   /// Exceptions are Savage and any operators without potential.
   pub potential: Vec<OperatorPotential>,
   /// A list of skills and their upgrade phases that this operator can achieve.
   pub skills: Vec<OperatorSkill>,
+  /// Upgrades for skill levels 2-7, shared between all of this operator's skills.
+  pub skill_level_upgrades: Vec<OperatorSkillLevelUpgrade>,
   /// A list of talents and their unlock phases that this operator can achieve.
   pub talents: Vec<OperatorTalent>,
   /// The list of non-default modules for this operator.
   pub modules: Vec<OperatorModule>,
   /// This list of this operator's outfits, including default outfits.
-  pub skins: Map<String, OperatorSkin>,
+  pub skins: Map<SkinId, OperatorSkin>,
   /// This skills that this operator can use in the RIIC base.
   pub base_skills: Vec<OperatorBaseSkill>,
   /// Attributes gained from trust level.
   pub trust_bonus: OperatorTrustAttributes,
   /// Information from the operator file or archive menus.
-  pub file: OperatorFile
+  pub file: Box<OperatorFile>
 }
 
 impl Operator {
   /// Retrieves a reference to the [`Item`] associated with this operator's potential item.
-  pub fn get_potential_item<'a>(&self, items: &'a Map<String, Item>) -> Option<&'a Item> {
+  pub fn get_potential_item<'a>(&self, items: &'a Map<ItemId, Item>) -> Option<&'a Item> {
     self.potential_item_id.as_deref().and_then(|item_id| items.get(item_id))
   }
 
+  /// Given a promotion, level and potential level, gets the description of this operator's trait at that point.
+  pub fn get_trait_description(&self, promotion_and_level: PromotionAndLevel, potential: u8) -> Option<&str> {
+    self.traits.iter().rev()
+      .find(|phase| phase.is_unlocked(promotion_and_level, potential))
+      .map(|phase| phase.description.as_str())
+      .or(self.description.as_deref())
+  }
+
   /// Calculates the stats of this operator at the given promotion, level, and trust percentage.
   /// (Does not account for stat boosts from talents.)
+  ///
+  /// Levels past the promotion's maximum level are clamped, use [`PromotionAndLevel::is_valid_for`] to check them first.
   pub fn get_attributes(&self, promotion_and_level: PromotionAndLevel, trust: u32) -> Option<OperatorPromotionAttributes> {
     self.promotions.get_attributes(promotion_and_level).map(|attributes| {
       attributes + self.trust_bonus.get_trust_level_attributes(trust)
@@ -189,15 +1059,208 @@ impl Operator {
     self.promotions.iter().filter_map(|promotion| promotion.get_skin(&self.skins))
   }
 
+  /// Returns this operator's most specific faction ID, preferring their team, then group, then nation.
+  pub fn faction_id(&self) -> Option<&str> {
+    self.team_id.as_deref()
+      .or(self.group_id.as_deref())
+      .or(self.nation_id.as_deref())
+  }
+
+  /// Returns an iterator over this operator's team, group and nation IDs, in that order, skipping any that are missing.
+  pub fn iter_faction_ids(&self) -> impl Iterator<Item = &str> + DoubleEndedIterator {
+    [&self.team_id, &self.group_id, &self.nation_id].into_iter()
+      .filter_map(|faction_id| faction_id.as_deref())
+  }
+
+  /// Calculates the EXP and LMD needed to level this operator from one promotion and level to another,
+  /// including the LMD fees for any promotions in between. (Promotion materials are not included)
+  ///
+  /// Returns `None` if either promotion and level cannot be reached by this operator, or if `to` is lower than `from`.
+  pub fn leveling_cost(&self, from: PromotionAndLevel, to: PromotionAndLevel, constants: &GameConstants) -> Option<LevelingCost> {
+    if from > to || !from.is_valid_for(self) || !to.is_valid_for(self) { return None };
+
+    let mut cost = LevelingCost::default();
+    let mut current = from;
+    while current.promotion < to.promotion {
+      let max_level = self.promotions.get(current.promotion)?.max_level;
+      cost = cost + constants.leveling_cost(current.promotion, current.level, max_level)?;
+      let next = current.promotion.next()?;
+      cost.lmd += constants.promotion_lmd_cost(self.rarity.get(), next)?;
+      current = next.with_level(1);
+    };
+
+    Some(cost + constants.leveling_cost(current.promotion, current.level, to.level)?)
+  }
+
+  /// Gets the highest promotion this operator can achieve.
+  pub fn max_promotion(&self) -> Promotion {
+    match *self.promotions {
+      OperatorPromotions { elite2: Some(_), .. } => Promotion::Elite2,
+      OperatorPromotions { elite1: Some(_), .. } => Promotion::Elite1,
+      OperatorPromotions { .. } => Promotion::None
+    }
+  }
+
+  /// Gets the maximum level of this operator at the given promotion,
+  /// or `None` if this operator cannot achieve that promotion.
+  pub fn max_level(&self, promotion: Promotion) -> Option<u32> {
+    self.promotions.get(promotion).map(|operator_promotion| operator_promotion.max_level)
+  }
+
+  /// Sums every material needed to raise this operator from scratch to the given target:
+  /// promotions, skill levels, skill masteries and modules.
+  ///
+  /// Like [`ItemsIter`], item IDs that are not present in `items` are skipped.
+  pub fn total_upgrade_cost(&self, target: &RaiseTarget, items: &Map<ItemId, Item>) -> ItemsCost {
+    let mut total = ItemsCost::new();
+    let mut add = |cost: &ItemsCost| {
+      for (item_id, &count) in cost.iter().filter(|&(item_id, _)| items.contains_key(item_id)) {
+        *total.entry(item_id.clone()).or_insert(0) += count;
+      };
+    };
+
+    for promotion in [Promotion::Elite1, Promotion::Elite2] {
+      if promotion <= target.promotion {
+        if let Some(operator_promotion) = self.promotions.get(promotion) {
+          add(&operator_promotion.upgrade_cost);
+        };
+      };
+    };
+
+    // the first entry is the upgrade from skill level 1 to 2
+    let skill_levels = target.skill_level.saturating_sub(1) as usize;
+    for skill_level_upgrade in self.skill_level_upgrades.iter().take(skill_levels) {
+      add(&skill_level_upgrade.upgrade_cost);
+    };
+
+    for (skill, &mastery) in self.skills.iter().zip(target.masteries.iter()) {
+      for skill_mastery in skill.mastery.iter().flatten().take(mastery as usize) {
+        add(&skill_mastery.upgrade_cost);
+      };
+    };
+
+    for module in self.modules.iter().filter(|module| target.modules.contains(&module.id)) {
+      add(&module.upgrade_cost);
+    };
+
+    total
+  }
+
+  /// Returns whether or not this operator has an alternate version of themselves.
+  pub fn is_alter(&self, game_data: &GameData) -> bool {
+    game_data.get_alter_group(&self.id).is_some()
+  }
+
   pub fn iter_recruitment_tags<'a>(&'a self, recruitment_tags: &'a Map<String, u32>)
   -> impl Iterator<Item = u32> + DoubleEndedIterator + 'a {
     self.recruitment_tags.iter().filter_map(|tag| recruitment_tags.get(tag).copied())
   }
+
+  /// Returns an iterator over every tag this operator can be found with in recruitment.
+  ///
+  /// Along with [`Operator::recruitment_tags`], this includes the tags the game derives from the operator itself:
+  /// their qualification (from rarity), their position and their profession.
+  pub fn iter_effective_recruitment_tags(&self) -> impl Iterator<Item = RecruitmentTag<'_>> + '_ {
+    let qualification = match self.rarity.get() {
+      2 => Some(RecruitmentTag::Starter),
+      5 => Some(RecruitmentTag::SeniorOperator),
+      6 => Some(RecruitmentTag::TopOperator),
+      _ => None
+    };
+
+    let position = match self.position {
+      Position::Melee | Position::Ranged => Some(RecruitmentTag::Position(self.position)),
+      Position::Any => None
+    };
+
+    qualification.into_iter()
+      .chain(position)
+      .chain(std::iter::once(RecruitmentTag::Profession(self.profession)))
+      .chain(self.recruitment_tags.iter().map(|tag| RecruitmentTag::Affix(tag)))
+  }
+}
+
+/// A tag that an operator can be found with in recruitment, see [`Operator::iter_effective_recruitment_tags`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RecruitmentTag<'a> {
+  /// Given to every 2-star operator.
+  Starter,
+  /// Given to every 5-star operator.
+  SeniorOperator,
+  /// Given to every 6-star operator.
+  TopOperator,
+  /// Given to every operator that is deployed on melee or ranged tiles.
+  Position(Position),
+  /// Given to every operator of a profession.
+  Profession(Profession),
+  /// One of [`Operator::recruitment_tags`], named in the language of the region the game data is from.
+  Affix(&'a str)
+}
+
+/// The operator rarities that can be recruited when a recruitment lasts at least a certain number of hours.
+/// See [`GameData::recruitment_time_brackets`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RecruitmentTimeBracket {
+  /// The shortest recruitment duration this bracket applies to, in hours.
+  pub min_hours: u32,
+  /// The lowest rarity that can be recruited, from 1 to 6.
+  pub min_rarity: u8,
+  /// The highest rarity that can be recruited, from 1 to 6.
+  pub max_rarity: u8
+}
+
+impl RecruitmentTimeBracket {
+  /// The range of rarities that can be recruited.
+  pub fn rarities(&self) -> RangeInclusive<u8> {
+    self.min_rarity..=self.max_rarity
+  }
+}
+
+/// A group of operators that are alternate versions of each other.
+/// (Example: Amiya, Guard Amiya and Medic Amiya)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AlterGroup {
+  /// The internal ID of this group, usually the ID of the original operator.
+  pub id: OperatorId,
+  /// The IDs of all of the operators in this group, including the original operator.
+  pub members: Vec<OperatorId>
+}
+
+impl AlterGroup {
+  /// Returns whether or not the given operator ID is a member of this group.
+  pub fn contains(&self, operator: &str) -> bool {
+    self.members.iter().any(|member| member == operator)
+  }
+
+  /// Returns an iterator over the IDs of every member of this group except for the given operator ID.
+  pub fn iter_others<'a>(&'a self, operator: &'a str) -> impl Iterator<Item = &'a str> + DoubleEndedIterator {
+    self.members.iter().map(OperatorId::as_str).filter(move |&member| member != operator)
+  }
+
+  /// Returns an iterator over every unordered pair of operator IDs in this group.
+  pub fn iter_pairs(&self) -> impl Iterator<Item = UOrd<&str>> {
+    self.iter_member_pairs().map(|(a, b)| UOrd::new(a, b))
+  }
+
+  fn iter_member_pairs(&self) -> impl Iterator<Item = (&str, &str)> {
+    self.members.iter().enumerate().flat_map(move |(i, a)| {
+      self.members[i + 1..].iter().map(move |b| (a.as_str(), b.as_str()))
+    })
+  }
+
+  /// Resolves every member of this group that exists in the given operator list.
+  pub fn iter_operators<'a>(&'a self, operators: &'a Map<OperatorId, Operator>)
+  -> impl Iterator<Item = &'a Operator> + DoubleEndedIterator {
+    self.members.iter().filter_map(|member| operators.get(member))
+  }
 }
 
 /// Contains information about an operator's three possible promotion phases.
 /// The default (none) promotion, elite level 1, and elite level 2.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorPromotions {
   /// The default (none) promotion level.
   pub none: OperatorPromotion,
@@ -260,9 +1323,10 @@ impl<'a> IntoIterator for &'a OperatorPromotions {
 
 /// An unlockable promotion level for an operator.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorPromotion {
   /// The ID of the prefab associated with this operator's attack range.
-  pub attack_range_id: Option<String>,
+  pub attack_range_id: Option<RangeId>,
   /// The minimum attributes of this promotion, starting from level 1.
   pub min_attributes: OperatorPromotionAttributes,
   /// The maximum attributes of this promotion, attainable at level `max_level`.
@@ -272,23 +1336,23 @@ pub struct OperatorPromotion {
   /// The items required to upgrade to obtain this promotion.
   pub upgrade_cost: ItemsCost,
   /// The skin unlocked at this promotion level.
-  pub skin_id: Option<String>
+  pub skin_id: Option<SkinId>
 }
 
 impl OperatorPromotion {
   /// Returns an iterator over the [`Item`]s required to obtain this promotion.
   #[inline]
-  pub fn iter_upgrade_cost<'a>(&'a self, items: &'a Map<String, Item>) -> ItemsIter<'a> {
+  pub fn iter_upgrade_cost<'a>(&'a self, items: &'a Map<ItemId, Item>) -> ItemsIter<'a> {
     ItemsIter::new(&self.upgrade_cost, items)
   }
 
   /// Gets the [`AttackRange`] of this operator's promotion, if any.
-  pub fn get_attack_range<'a>(&self, ranges: &'a Map<String, AttackRange>) -> Option<&'a AttackRange> {
+  pub fn get_attack_range<'a>(&self, ranges: &'a Map<RangeId, AttackRange>) -> Option<&'a AttackRange> {
     self.attack_range_id.as_deref().and_then(|attack_range_id| ranges.get(attack_range_id))
   }
 
   /// Gets the [`OperatorSkin`] that is unlocked with this promotion level, if any.
-  pub fn get_skin<'a>(&self, skins: &'a Map<String, OperatorSkin>) -> Option<&'a OperatorSkin> {
+  pub fn get_skin<'a>(&self, skins: &'a Map<SkinId, OperatorSkin>) -> Option<&'a OperatorSkin> {
     self.skin_id.as_deref().and_then(|skin_id| skins.get(skin_id))
   }
 
@@ -322,6 +1386,7 @@ impl OperatorPromotion {
 
 /// Operator attributes associated with an operator promotion.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorPromotionAttributes {
   pub level: u32,
   pub max_hp: u32,
@@ -345,6 +1410,82 @@ pub struct OperatorPromotionAttributes {
   pub is_frozen_immune: bool
 }
 
+impl OperatorPromotionAttributes {
+  /// The lowest attack speed an operator can have after all bonuses.
+  pub const MIN_ATTACK_SPEED: f32 = 10.0;
+  /// The highest attack speed an operator can have after all bonuses.
+  pub const MAX_ATTACK_SPEED: f32 = 600.0;
+  /// The minimum fraction of an attack's damage that is always dealt, regardless of DEF or RES.
+  pub const MIN_DAMAGE_FRACTION: f32 = 0.05;
+
+  /// Calculates the time between attacks in seconds, given a flat attack speed bonus (or penalty).
+  /// Uses the game's formula of `base_attack_time * 100 / (attack_speed + bonus)`,
+  /// with the final attack speed limited to between 10 and 600.
+  pub fn attack_interval(&self, bonus_attack_speed: f32) -> f32 {
+    let attack_speed = (self.attack_speed + bonus_attack_speed)
+      .clamp(Self::MIN_ATTACK_SPEED, Self::MAX_ATTACK_SPEED);
+    self.base_attack_time * 100.0 / attack_speed
+  }
+
+  /// Calculates the number of attacks per second, given a flat attack speed bonus (or penalty).
+  pub fn attacks_per_second(&self, bonus_attack_speed: f32) -> f32 {
+    self.attack_interval(bonus_attack_speed).recip()
+  }
+
+  /// Calculates the damage of one physical hit against a target with the given DEF.
+  pub fn physical_damage_per_hit(&self, target_def: f32) -> f32 {
+    let atk = self.atk as f32;
+    (atk - target_def).max(atk * Self::MIN_DAMAGE_FRACTION)
+  }
+
+  /// Calculates the damage of one arts hit against a target with the given RES (from 0 to 100).
+  pub fn arts_damage_per_hit(&self, target_res: f32) -> f32 {
+    let atk = self.atk as f32;
+    (atk * (1.0 - target_res / 100.0)).max(atk * Self::MIN_DAMAGE_FRACTION)
+  }
+
+  /// Calculates damage per second, given the damage of a single hit and a flat attack speed bonus (or penalty).
+  pub fn dps(&self, damage_per_hit: f32, bonus_attack_speed: f32) -> f32 {
+    damage_per_hit / self.attack_interval(bonus_attack_speed)
+  }
+}
+
+/// The difference between two operators' attributes, see [`GameData::compare_operators`].
+/// Each delta is the second operator's attribute minus the first operator's attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OperatorComparison {
+  /// The attributes of the first operator.
+  pub a: OperatorPromotionAttributes,
+  /// The attributes of the second operator.
+  pub b: OperatorPromotionAttributes,
+  pub max_hp: i64,
+  pub atk: i64,
+  pub def: i64,
+  pub magic_resistance: f32,
+  pub deployment_cost: i64,
+  pub block_count: i16,
+  /// The difference in base attack time (attack interval), in seconds.
+  pub base_attack_time: f32,
+  pub redeploy_time: i64
+}
+
+impl OperatorComparison {
+  pub fn new(a: OperatorPromotionAttributes, b: OperatorPromotionAttributes) -> Self {
+    OperatorComparison {
+      max_hp: b.max_hp as i64 - a.max_hp as i64,
+      atk: b.atk as i64 - a.atk as i64,
+      def: b.def as i64 - a.def as i64,
+      magic_resistance: b.magic_resistance - a.magic_resistance,
+      deployment_cost: b.deployment_cost as i64 - a.deployment_cost as i64,
+      block_count: b.block_count as i16 - a.block_count as i16,
+      base_attack_time: b.base_attack_time - a.base_attack_time,
+      redeploy_time: b.redeploy_time as i64 - a.redeploy_time as i64,
+      a, b
+    }
+  }
+}
+
 impl Add<OperatorTrustAttributes> for OperatorPromotionAttributes {
   type Output = OperatorPromotionAttributes;
 
@@ -360,6 +1501,7 @@ impl Add<OperatorTrustAttributes> for OperatorPromotionAttributes {
 
 /// Operator attributes associated with an operator's trust level.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorTrustAttributes {
   pub max_hp: u32,
   pub atk: u32,
@@ -397,6 +1539,7 @@ fn lerp_u32(min: u32, max: u32, t: f32) -> u32 {
 
 /// A single 'potential' upgrade level for an operator.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorPotential {
   /// Only two values currently appear:
   /// - `0`, which corresponds to stat boosts.
@@ -407,10 +1550,13 @@ pub struct OperatorPotential {
 
 /// An operator's skill and all of its upgradeable levels.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorSkill {
   /// The internal ID of this operator skill.
-  pub id: String,
+  pub id: SkillId,
   pub name: String,
+  /// The ID of this skill's icon, see [`AssetMirror::skill_icon_url`][crate::assets::AssetMirror::skill_icon_url].
+  pub icon_id: String,
   pub prefab_key: Option<String>,
   pub condition: PromotionAndLevel,
   pub activation: SkillActivation,
@@ -440,9 +1586,10 @@ impl OperatorSkill {
 
 /// An upgradeable level of an operator's skill.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorSkillLevel {
   pub description: Option<String>,
-  pub attack_range_id: Option<String>,
+  pub attack_range_id: Option<RangeId>,
   pub prefab_key: Option<String>,
   pub duration: f32,
   pub max_charge_time: u32,
@@ -453,16 +1600,70 @@ pub struct OperatorSkillLevel {
 
 impl OperatorSkillLevel {
   /// Gets the [`AttackRange`] of this operator's skill level, if any.
-  pub fn get_attack_range<'a>(&self, ranges: &'a Map<String, AttackRange>) -> Option<&'a AttackRange> {
+  pub fn get_attack_range<'a>(&self, ranges: &'a Map<RangeId, AttackRange>) -> Option<&'a AttackRange> {
     self.attack_range_id.as_deref().and_then(|attack_range_id| ranges.get(attack_range_id))
   }
 }
 
+/// An upgrade to the level of all of an operator's skills, from levels 2 through 7.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OperatorSkillLevelUpgrade {
+  pub condition: PromotionAndLevel,
+  pub upgrade_cost: ItemsCost
+}
+
+impl OperatorSkillLevelUpgrade {
+  /// Returns an iterator over the [`Item`]s required for this upgrade.
+  #[inline]
+  pub fn iter_upgrade_cost<'a>(&'a self, items: &'a Map<ItemId, Item>) -> ItemsIter<'a> {
+    ItemsIter::new(&self.upgrade_cost, items)
+  }
+}
+
+/// The state an operator is being raised to, see [`Operator::total_upgrade_cost`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RaiseTarget {
+  /// The promotion to raise the operator to.
+  pub promotion: Promotion,
+  /// The skill level (1-7) to raise the operator's skills to.
+  pub skill_level: u8,
+  /// The mastery level (0-3) to raise each of the operator's skills to, in order.
+  pub masteries: [u8; 3],
+  /// The IDs of the modules to unlock.
+  pub modules: Vec<ModuleId>
+}
+
+impl RaiseTarget {
+  /// A target of elite 2, skill level 7, and every skill at mastery 3, without any modules.
+  pub fn max() -> Self {
+    RaiseTarget {
+      promotion: Promotion::Elite2,
+      skill_level: 7,
+      masteries: [3, 3, 3],
+      modules: Vec::new()
+    }
+  }
+}
+
+impl Default for RaiseTarget {
+  fn default() -> Self {
+    RaiseTarget {
+      promotion: Promotion::None,
+      skill_level: 1,
+      masteries: [0, 0, 0],
+      modules: Vec::new()
+    }
+  }
+}
+
 /// An upgradeable mastery level of an operator's skill.
 ///
 /// Implements `Deref<Target = OperatorSkillLevel>` so that you can access
 /// the fields of [`OperatorSkillLevel`] directly.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorSkillMastery {
   pub condition: PromotionAndLevel,
   pub upgrade_time: u32,
@@ -487,7 +1688,7 @@ impl OperatorSkillMastery {
 
   /// Returns an iterator over the [`Item`]s required to obtain this mastery upgrade.
   #[inline]
-  pub fn iter_upgrade_cost<'a>(&'a self, items: &'a Map<String, Item>) -> ItemsIter<'a> {
+  pub fn iter_upgrade_cost<'a>(&'a self, items: &'a Map<ItemId, Item>) -> ItemsIter<'a> {
     ItemsIter::new(&self.upgrade_cost, items)
   }
 }
@@ -495,6 +1696,7 @@ impl OperatorSkillMastery {
 /// The activation mode of an operator's skill.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum SkillActivation {
   Passive,
   Manual,
@@ -504,6 +1706,7 @@ pub enum SkillActivation {
 /// The recovery mode of an operator's skill.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum SkillRecovery {
   Passive,
   AutoRecovery,
@@ -511,9 +1714,28 @@ pub enum SkillRecovery {
   DefensiveRecovery
 }
 
+/// An unlockable phase of an operator's trait.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OperatorTraitPhase {
+  pub description: String,
+  pub condition: PromotionAndLevel,
+  pub required_potential: u8,
+  pub attack_range_id: Option<RangeId>,
+  pub effects: Map<String, f32>
+}
+
+impl OperatorTraitPhase {
+  /// Returns whether or not this trait phase's promotion, level and potential requirements have been met.
+  pub fn is_unlocked(&self, promotion_and_level: PromotionAndLevel, potential: u8) -> bool {
+    self.condition <= promotion_and_level && self.required_potential <= potential
+  }
+}
+
 /// An operator's talent and all of its unlockable phases.
 #[repr(transparent)]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorTalent {
   pub phases: Vec<OperatorTalentPhase>
 }
@@ -527,6 +1749,7 @@ impl OperatorTalent {
 
 /// An unlockable phase of an operator's talent.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorTalentPhase {
   pub name: String,
   pub description: String,
@@ -540,7 +1763,7 @@ pub struct OperatorTalentPhase {
   /// - `#` is currently only present on Amiya's "???" talent and on Phantom's "Phantom Mastery" talent.
   ///   There's no discernible pattern here, maybe a "special" talent marker?
   pub prefab_key: String,
-  pub attack_range_id: Option<String>,
+  pub attack_range_id: Option<RangeId>,
   pub effects: Map<String, f32>
 }
 
@@ -551,27 +1774,45 @@ impl OperatorTalentPhase {
   }
 
   /// Gets the [`AttackRange`] of this operator's talent phase.
-  pub fn get_attack_range<'a>(&self, ranges: &'a Map<String, AttackRange>) -> Option<&'a AttackRange> {
+  pub fn get_attack_range<'a>(&self, ranges: &'a Map<RangeId, AttackRange>) -> Option<&'a AttackRange> {
     self.attack_range_id.as_deref().and_then(|attack_range_id| ranges.get(attack_range_id))
   }
 }
 
-/// An unlockable module for an operator. Currently, no operators have more than one.
+/// An unlockable module for an operator.
+/// Operators with more than one module can tell them apart by [`OperatorModule::label`].
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorModule {
   /// The internal ID of this operator module.
-  pub id: String,
+  pub id: ModuleId,
   pub name: String,
   /// Story text accessible after unlocking this module.
   pub description: String,
+  /// The type of this module, shared by every module of the same sub-profession. (Example: `"PIO"`)
+  pub type_name: String,
+  /// Which of the operator's modules of this type this is. (Example: `"X"`, `"Y"` or `"Δ"`)
+  pub type_variant: Option<String>,
+  /// The ID of this module's type icon. (Example: `"pio-x"`)
+  pub icon_id: String,
   pub condition: PromotionAndLevel,
   pub required_trust: u32,
   pub upgrade_cost: ItemsCost,
   /// A list of missions that must be completed before this module can be unlocked.
-  pub missions: Map<String, OperatorModuleMission>
+  pub missions: Map<String, OperatorModuleMission>,
+  /// The changes this module makes to the operator's trait at each of its stages, starting at stage 1.
+  pub stages: Vec<OperatorModuleStage>
 }
 
 impl OperatorModule {
+  /// The label the game shows for this module, made up of its type name and variant. (Example: `"PIO-X"`)
+  pub fn label(&self) -> String {
+    match &self.type_variant {
+      Some(type_variant) => format!("{}-{}", self.type_name, type_variant),
+      None => self.type_name.clone()
+    }
+  }
+
   /// Returns whether or not this module's promotion, level, and trust requirements have been met.
   pub fn is_unlockable(&self, promotion_and_level: PromotionAndLevel, trust: u32) -> bool {
     self.condition <= promotion_and_level && self.required_trust <= trust
@@ -579,13 +1820,27 @@ impl OperatorModule {
 
   /// Returns an iterator over the [`Item`]s required to obtain this module.
   #[inline]
-  pub fn iter_upgrade_cost<'a>(&'a self, items: &'a Map<String, Item>) -> ItemsIter<'a> {
+  pub fn iter_upgrade_cost<'a>(&'a self, items: &'a Map<ItemId, Item>) -> ItemsIter<'a> {
     ItemsIter::new(&self.upgrade_cost, items)
   }
 }
 
+/// The changes a module makes to an operator's trait at one of its stages.
+/// Descriptions have their templates filled in and their formatting tags removed, like skill descriptions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OperatorModuleStage {
+  /// The stage of the module, from 1 to 3.
+  pub level: u32,
+  /// Text added after the operator's trait description.
+  pub trait_addition: Option<String>,
+  /// Text that replaces the operator's trait description.
+  pub trait_override: Option<String>
+}
+
 /// A mission that must be completed in order to unlock an operator module.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorModuleMission {
   /// A description of the mission requirements.
   pub description: String,
@@ -593,7 +1848,8 @@ pub struct OperatorModuleMission {
 }
 
 /// An operator's base skill and all of its unlockable phases.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorBaseSkill {
   pub phases: Vec<OperatorBaseSkillPhase>
 }
@@ -606,13 +1862,24 @@ impl OperatorBaseSkill {
 }
 
 /// An unlockable phase of an operator's base skill.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorBaseSkillPhase {
   pub name: String,
+  /// A description of this base skill phase's effects, region dependent.
+  #[serde(default)]
+  pub description: Option<String>,
   pub condition: PromotionAndLevel,
   pub sort: u32,
   pub category: OperatorBaseSkillCategory,
-  pub building_type: BuildingType
+  pub building_type: BuildingType,
+  /// The effect of this base skill phase on the room it is used in, read from its description.
+  /// See [`GameData::simulate_room`].
+  ///
+  /// This is `None` when the description is not one of the simple forms recognized by [`BaseSkillEffect::from_description`],
+  /// and [`GameData::simulate_room`] then lists the phase in [`RoomSimulation::unknown_skills`].
+  #[serde(default)]
+  pub effect: Option<BaseSkillEffect>
 }
 
 impl OperatorBaseSkillPhase {
@@ -625,6 +1892,7 @@ impl OperatorBaseSkillPhase {
 /// The category of an operator's base skill.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum OperatorBaseSkillCategory {
   Function,
   Recovery,
@@ -633,12 +1901,13 @@ pub enum OperatorBaseSkillCategory {
 
 /// An operator equippable outfit.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorSkin {
   /// The internal ID of this operator skin.
-  pub id: String,
+  pub id: SkinId,
   pub name: Option<String>,
   /// The ID of the operator to whom this skin belongs.
-  pub model_id: String,
+  pub model_id: OperatorId,
   /// The name of the operator to whom this skin belongs.
   pub model_name: String,
   /// Whether or not this skin costs originite prime.
@@ -658,18 +1927,48 @@ pub struct OperatorSkin {
 /// Indicates whether an operator is primarily melee or primarily ranged.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Position {
   Melee,
-  Ranged
+  Ranged,
+  /// The operator can be deployed on both melee and ranged tiles. (Listed as `ALL` in the game files)
+  Any
 }
 
 /// Represents the promotion level and numeric level of an operator.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PromotionAndLevel {
   pub promotion: Promotion,
   pub level: u32
 }
 
+impl PromotionAndLevel {
+  /// Elite 2 at the highest possible level, parsed from `"max"`.
+  /// Use [`PromotionAndLevel::clamp_for`] to turn this into a specific operator's maximum level.
+  pub const MAX: PromotionAndLevel = PromotionAndLevel { promotion: Promotion::Elite2, level: u32::MAX };
+
+  /// Lowers this promotion and level until it can be reached by the given operator.
+  /// (Example: [`PromotionAndLevel::MAX`] becomes elite 1 level 55 for a 3-star operator)
+  pub fn clamp_for(self, operator: &Operator) -> PromotionAndLevel {
+    let promotion = self.promotion.min(operator.max_promotion());
+    let level = if promotion < self.promotion { u32::MAX } else { self.level };
+    let max_level = operator.max_level(promotion).unwrap_or(1);
+    promotion.with_level(level.clamp(1, max_level))
+  }
+
+  /// Returns whether or not this promotion and level can be reached by the given operator.
+  pub fn is_valid_for(self, operator: &Operator) -> bool {
+    operator.max_level(self.promotion).map_or(false, |max_level| (1..=max_level).contains(&self.level))
+  }
+
+  /// Returns whether or not this promotion and level can be reached by an operator of the given rarity (1 to 6),
+  /// according to the rarity caps in the game constants.
+  pub fn is_valid_for_rarity(self, rarity: u8, constants: &GameConstants) -> bool {
+    constants.max_level(rarity, self.promotion).map_or(false, |max_level| (1..=max_level).contains(&self.level))
+  }
+}
+
 impl PartialOrd for PromotionAndLevel {
   #[inline]
   fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
@@ -685,9 +1984,124 @@ impl Ord for PromotionAndLevel {
   }
 }
 
+/// Leveling curves and other constants shared by every operator, from the game's `gamedata_const` table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct GameConstants {
+  /// The maximum level at each promotion, indexed by rarity (0 for 1-star) then by promotion.
+  pub max_levels: Vec<Vec<u32>>,
+  /// The EXP needed to go from each level to the next, indexed by promotion then by level (0 for level 1).
+  pub exp_costs: Vec<Vec<u32>>,
+  /// The LMD needed to go from each level to the next, indexed by promotion then by level (0 for level 1).
+  pub lmd_costs: Vec<Vec<u32>>,
+  /// The LMD fee for each promotion, indexed by rarity (0 for 1-star) then by promotion (0 for elite 1).
+  pub promotion_lmd_costs: Vec<Vec<u32>>
+}
+
+impl GameConstants {
+  /// Calculates the EXP and LMD needed to level an operator from `from_level` to `to_level` within a single promotion.
+  /// Returns `None` if the curves do not cover the given levels.
+  pub fn leveling_cost(&self, promotion: Promotion, from_level: u32, to_level: u32) -> Option<LevelingCost> {
+    let range = from_level.checked_sub(1)? as usize..to_level.checked_sub(1)? as usize;
+    let exp = self.exp_costs.get(promotion as usize)?.get(range.clone())?.iter().sum();
+    let lmd = self.lmd_costs.get(promotion as usize)?.get(range)?.iter().sum();
+    Some(LevelingCost { exp, lmd })
+  }
+
+  /// Gets the LMD fee for promoting an operator of the given rarity (1 to 6) to the given promotion.
+  /// Returns `Some(0)` for [`Promotion::None`].
+  pub fn promotion_lmd_cost(&self, rarity: u8, promotion: Promotion) -> Option<u32> {
+    match promotion {
+      Promotion::None => Some(0),
+      promotion => self.promotion_lmd_costs
+        .get(rarity.checked_sub(1)? as usize)?
+        .get(promotion as usize - 1)
+        .copied()
+    }
+  }
+
+  /// Gets the maximum level for an operator of the given rarity (1 to 6) at the given promotion.
+  pub fn max_level(&self, rarity: u8, promotion: Promotion) -> Option<u32> {
+    self.max_levels.get(rarity.checked_sub(1)? as usize)?.get(promotion as usize).copied()
+  }
+}
+
+/// The EXP and LMD needed to level an operator, see [`Operator::leveling_cost`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LevelingCost {
+  pub exp: u32,
+  pub lmd: u32
+}
+
+impl Add for LevelingCost {
+  type Output = LevelingCost;
+
+  fn add(self, other: LevelingCost) -> LevelingCost {
+    LevelingCost {
+      exp: self.exp + other.exp,
+      lmd: self.lmd + other.lmd
+    }
+  }
+}
+
+impl FromStr for PromotionAndLevel {
+  type Err = ParsePromotionAndLevelError;
+
+  /// Parses a promotion and level, ignoring case. Accepted forms include `"E2 40"`, `"e1 55"`, `"e2l90"`,
+  /// `"elite 1 level 30"` and `"40"` (no promotion). The level may also be `"max"`, and `"max"` on its own
+  /// means [`PromotionAndLevel::MAX`].
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let s = s.trim().to_ascii_lowercase();
+    if s == "max" { return Ok(PromotionAndLevel::MAX) };
+
+    let (promotion, rest) = match s.strip_prefix("elite").or_else(|| s.strip_prefix('e')) {
+      Some(rest) => {
+        let rest = rest.trim_start();
+        let promotion = match rest.chars().next() {
+          Some('0') => Promotion::None,
+          Some('1') => Promotion::Elite1,
+          Some('2') => Promotion::Elite2,
+          _ => return Err(ParsePromotionAndLevelError)
+        };
+
+        (promotion, &rest[1..])
+      },
+      None => (Promotion::None, s.as_str())
+    };
+
+    let rest = rest.trim_start();
+    let rest = ["level", "lvl", "lv", "l"].into_iter()
+      .find_map(|prefix| rest.strip_prefix(prefix))
+      .unwrap_or(rest)
+      .trim();
+    let level = match rest {
+      "max" => u32::MAX,
+      rest => rest.parse::<u32>().map_err(|_| ParsePromotionAndLevelError)?
+    };
+
+    Ok(promotion.with_level(level))
+  }
+}
+
+impl fmt::Display for PromotionAndLevel {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      PromotionAndLevel::MAX => f.write_str("max"),
+      PromotionAndLevel { promotion, level: u32::MAX } => write!(f, "E{} max", promotion as u8),
+      PromotionAndLevel { promotion, level } => write!(f, "E{} {}", promotion as u8, level)
+    }
+  }
+}
+
+#[derive(Debug, Error, Clone, Copy)]
+#[error("expected a promotion and level such as \"E2 40\" or \"max\"")]
+pub struct ParsePromotionAndLevelError;
+
 /// The promotion level of an operator.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Promotion {
   /// The default (none) promotion level.
   None = 0,
@@ -702,11 +2116,21 @@ impl Promotion {
   pub fn with_level(self, level: u32) -> PromotionAndLevel {
     PromotionAndLevel { promotion: self, level }
   }
+
+  /// Gets the promotion after this one, if any.
+  pub fn next(self) -> Option<Promotion> {
+    match self {
+      Promotion::None => Some(Promotion::Elite1),
+      Promotion::Elite1 => Some(Promotion::Elite2),
+      Promotion::Elite2 => None
+    }
+  }
 }
 
 /// An operator's primary profession.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Profession {
   Caster,
   Medic,
@@ -718,12 +2142,85 @@ pub enum Profession {
   Guard
 }
 
+impl Profession {
+  /// Every profession, in declaration order.
+  pub const ALL: &'static [Profession] = &[
+    Profession::Caster,
+    Profession::Medic,
+    Profession::Vanguard,
+    Profession::Sniper,
+    Profession::Specialist,
+    Profession::Support,
+    Profession::Tank,
+    Profession::Guard
+  ];
+
+  /// Returns an iterator over every profession, in declaration order.
+  pub fn iter() -> impl Iterator<Item = Profession> + DoubleEndedIterator + ExactSizeIterator {
+    Self::ALL.iter().copied()
+  }
+
+  /// The name of this profession. (Example: `"Vanguard"`)
+  pub fn name(self) -> &'static str {
+    match self {
+      Self::Caster => "Caster",
+      Self::Medic => "Medic",
+      Self::Vanguard => "Vanguard",
+      Self::Sniper => "Sniper",
+      Self::Specialist => "Specialist",
+      Self::Support => "Support",
+      Self::Tank => "Tank",
+      Self::Guard => "Guard"
+    }
+  }
+
+  /// The identifier the game files use for this profession. (Example: `"PIONEER"` for Vanguard)
+  pub fn game_id(self) -> &'static str {
+    match self {
+      Self::Caster => "CASTER",
+      Self::Medic => "MEDIC",
+      Self::Vanguard => "PIONEER",
+      Self::Sniper => "SNIPER",
+      Self::Specialist => "SPECIAL",
+      Self::Support => "SUPPORT",
+      Self::Tank => "TANK",
+      Self::Guard => "WARRIOR"
+    }
+  }
+}
+
+impl FromStr for Profession {
+  type Err = ParseProfessionError;
+
+  /// Parses either the profession's name or its identifier in the game files, ignoring case.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Self::iter()
+      .find(|profession| s.eq_ignore_ascii_case(profession.name()) || s.eq_ignore_ascii_case(profession.game_id()))
+      .ok_or(ParseProfessionError)
+  }
+}
+
+impl fmt::Display for Profession {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str(self.name())
+  }
+}
+
+#[derive(Debug, Error, Clone, Copy)]
+#[error("unknown profession")]
+pub struct ParseProfessionError;
+
 /// An operator's secondary sub-profession.
 ///
 /// This enum is marked as non-exhaustive because Hypergryph may add new sub-professions in the future.
-#[repr(u8)]
+/// Sub-professions that this crate does not know about yet are represented by [`SubProfession::Other`].
+///
+/// Sub-professions are serialized as their identifier in the game files (see [`SubProfession::game_id`]),
+/// so that they can be used as map keys.
 #[non_exhaustive]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(into = "String", from = "String")]
 pub enum SubProfession {
   // Casters
   BlastCaster,
@@ -784,13 +2281,17 @@ pub enum SubProfession {
   Lord,
   Musha,
   Reaper,
-  Swordmaster
+  Swordmaster,
+  /// A sub-profession that this crate does not know about yet, holding its identifier in the game files.
+  /// (Example: `"funnel"` before Mech-Accord Casters were added)
+  Other(String)
 }
 
 impl SubProfession {
   /// Gets the [`Profession`] that this [`SubProfession`] belongs to.
-  pub fn to_profession(self) -> Profession {
-    match self {
+  /// Returns `None` for [`SubProfession::Other`], since the profession of an unknown sub-profession cannot be known.
+  pub fn to_profession(&self) -> Option<Profession> {
+    Some(match self {
       // Casters
       Self::BlastCaster => Profession::Caster,
       Self::ChainCaster => Profession::Caster,
@@ -850,14 +2351,259 @@ impl SubProfession {
       Self::Lord => Profession::Guard,
       Self::Musha => Profession::Guard,
       Self::Reaper => Profession::Guard,
-      Self::Swordmaster => Profession::Guard
+      Self::Swordmaster => Profession::Guard,
+      Self::Other(..) => return None
+    })
+  }
+}
+
+impl SubProfession {
+  /// Every known sub-profession, in declaration order. Does not include [`SubProfession::Other`].
+  pub const ALL: &'static [SubProfession] = &[
+    SubProfession::BlastCaster,
+    SubProfession::ChainCaster,
+    SubProfession::CoreCaster,
+    SubProfession::MechAccordCaster,
+    SubProfession::MysticCaster,
+    SubProfession::PhalanxCaster,
+    SubProfession::SplashCaster,
+    SubProfession::Therapist,
+    SubProfession::Medic,
+    SubProfession::MultiTargetMedic,
+    SubProfession::WanderingMedic,
+    SubProfession::StandardBearer,
+    SubProfession::Charger,
+    SubProfession::Pioneer,
+    SubProfession::Tactician,
+    SubProfession::Artilleryman,
+    SubProfession::Flinger,
+    SubProfession::Heavyshooter,
+    SubProfession::Marksman,
+    SubProfession::Deadeye,
+    SubProfession::Spreadshooter,
+    SubProfession::Besieger,
+    SubProfession::Dollkeeper,
+    SubProfession::Executor,
+    SubProfession::Geek,
+    SubProfession::Hookmaster,
+    SubProfession::Merchant,
+    SubProfession::PushStroker,
+    SubProfession::Ambusher,
+    SubProfession::Trapmaster,
+    SubProfession::Bard,
+    SubProfession::Abjurer,
+    SubProfession::Artificer,
+    SubProfession::DecelBinder,
+    SubProfession::Summoner,
+    SubProfession::Hexer,
+    SubProfession::ArtsProtector,
+    SubProfession::Duelist,
+    SubProfession::Fortress,
+    SubProfession::Guardian,
+    SubProfession::Protector,
+    SubProfession::Juggernaut,
+    SubProfession::ArtsFighter,
+    SubProfession::Centurion,
+    SubProfession::Dreadnought,
+    SubProfession::Fighter,
+    SubProfession::Instructor,
+    SubProfession::Liberator,
+    SubProfession::Lord,
+    SubProfession::Musha,
+    SubProfession::Reaper,
+    SubProfession::Swordmaster
+  ];
+
+  /// Returns an iterator over every known sub-profession, in declaration order.
+  pub fn iter() -> impl Iterator<Item = SubProfession> + DoubleEndedIterator + ExactSizeIterator {
+    Self::ALL.iter().cloned()
+  }
+
+  /// The name of this sub-profession. (Example: `"Mech-Accord Caster"`)
+  /// For [`SubProfession::Other`], this is its identifier in the game files.
+  pub fn name(&self) -> &str {
+    match self {
+      Self::BlastCaster => "Blast Caster",
+      Self::ChainCaster => "Chain Caster",
+      Self::CoreCaster => "Core Caster",
+      Self::MechAccordCaster => "Mech-Accord Caster",
+      Self::MysticCaster => "Mystic Caster",
+      Self::PhalanxCaster => "Phalanx Caster",
+      Self::SplashCaster => "Splash Caster",
+      Self::Therapist => "Therapist",
+      Self::Medic => "Medic",
+      Self::MultiTargetMedic => "Multi-target Medic",
+      Self::WanderingMedic => "Wandering Medic",
+      Self::StandardBearer => "Standard Bearer",
+      Self::Charger => "Charger",
+      Self::Pioneer => "Pioneer",
+      Self::Tactician => "Tactician",
+      Self::Artilleryman => "Artilleryman",
+      Self::Flinger => "Flinger",
+      Self::Heavyshooter => "Heavyshooter",
+      Self::Marksman => "Marksman",
+      Self::Deadeye => "Deadeye",
+      Self::Spreadshooter => "Spreadshooter",
+      Self::Besieger => "Besieger",
+      Self::Dollkeeper => "Dollkeeper",
+      Self::Executor => "Executor",
+      Self::Geek => "Geek",
+      Self::Hookmaster => "Hookmaster",
+      Self::Merchant => "Merchant",
+      Self::PushStroker => "Push Stroker",
+      Self::Ambusher => "Ambusher",
+      Self::Trapmaster => "Trapmaster",
+      Self::Bard => "Bard",
+      Self::Abjurer => "Abjurer",
+      Self::Artificer => "Artificer",
+      Self::DecelBinder => "Decel Binder",
+      Self::Summoner => "Summoner",
+      Self::Hexer => "Hexer",
+      Self::ArtsProtector => "Arts Protector",
+      Self::Duelist => "Duelist",
+      Self::Fortress => "Fortress",
+      Self::Guardian => "Guardian",
+      Self::Protector => "Protector",
+      Self::Juggernaut => "Juggernaut",
+      Self::ArtsFighter => "Arts Fighter",
+      Self::Centurion => "Centurion",
+      Self::Dreadnought => "Dreadnought",
+      Self::Fighter => "Fighter",
+      Self::Instructor => "Instructor",
+      Self::Liberator => "Liberator",
+      Self::Lord => "Lord",
+      Self::Musha => "Musha",
+      Self::Reaper => "Reaper",
+      Self::Swordmaster => "Swordmaster",
+      Self::Other(game_id) => game_id
+    }
+  }
+
+  /// The identifier the game files use for this sub-profession. (Example: `"funnel"` for Mech-Accord Caster)
+  pub fn game_id(&self) -> &str {
+    match self {
+      Self::BlastCaster => "blastcaster",
+      Self::ChainCaster => "chain",
+      Self::CoreCaster => "corecaster",
+      Self::MechAccordCaster => "funnel",
+      Self::MysticCaster => "mystic",
+      Self::PhalanxCaster => "phalanx",
+      Self::SplashCaster => "splashcaster",
+      Self::Therapist => "healer",
+      Self::Medic => "physician",
+      Self::MultiTargetMedic => "ringhealer",
+      Self::WanderingMedic => "wandermedic",
+      Self::StandardBearer => "bearer",
+      Self::Charger => "charger",
+      Self::Pioneer => "pioneer",
+      Self::Tactician => "tactician",
+      Self::Artilleryman => "aoesniper",
+      Self::Flinger => "bombarder",
+      Self::Heavyshooter => "closerange",
+      Self::Marksman => "fastshot",
+      Self::Deadeye => "longrange",
+      Self::Spreadshooter => "reaperrange",
+      Self::Besieger => "siegesniper",
+      Self::Dollkeeper => "dollkeeper",
+      Self::Executor => "executor",
+      Self::Geek => "geek",
+      Self::Hookmaster => "hookmaster",
+      Self::Merchant => "merchant",
+      Self::PushStroker => "pusher",
+      Self::Ambusher => "stalker",
+      Self::Trapmaster => "traper",
+      Self::Bard => "bard",
+      Self::Abjurer => "blessing",
+      Self::Artificer => "craftsman",
+      Self::DecelBinder => "slower",
+      Self::Summoner => "summoner",
+      Self::Hexer => "underminer",
+      Self::ArtsProtector => "artsprotector",
+      Self::Duelist => "duelist",
+      Self::Fortress => "fortress",
+      Self::Guardian => "guardian",
+      Self::Protector => "protector",
+      Self::Juggernaut => "unyield",
+      Self::ArtsFighter => "artsfghter",
+      Self::Centurion => "centurion",
+      Self::Dreadnought => "fearless",
+      Self::Fighter => "fighter",
+      Self::Instructor => "instructor",
+      Self::Liberator => "librator",
+      Self::Lord => "lord",
+      Self::Musha => "musha",
+      Self::Reaper => "reaper",
+      Self::Swordmaster => "sword",
+      Self::Other(game_id) => game_id
     }
   }
 }
 
+impl FromStr for SubProfession {
+  type Err = ParseSubProfessionError;
+
+  /// Parses either the sub-profession's name or its identifier in the game files,
+  /// ignoring case, spaces, hyphens and underscores. (Example: `"mech-accord caster"`, `"MechAccordCaster"` or `"funnel"`)
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn simplify(s: &str) -> String {
+      s.chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+    }
+
+    let s = simplify(s);
+    Self::iter()
+      .find(|sub_profession| s == simplify(sub_profession.name()) || s == sub_profession.game_id())
+      .ok_or(ParseSubProfessionError)
+  }
+}
+
+impl fmt::Display for SubProfession {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str(self.name())
+  }
+}
+
+impl From<String> for SubProfession {
+  /// Converts an identifier in the game files into a sub-profession,
+  /// falling back to [`SubProfession::Other`] for identifiers this crate does not know about.
+  fn from(game_id: String) -> Self {
+    Self::iter()
+      .find(|sub_profession| sub_profession.game_id() == game_id)
+      .unwrap_or(SubProfession::Other(game_id))
+  }
+}
+
+impl From<SubProfession> for String {
+  fn from(sub_profession: SubProfession) -> Self {
+    match sub_profession {
+      SubProfession::Other(game_id) => game_id,
+      sub_profession => sub_profession.game_id().to_owned()
+    }
+  }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for SubProfession {
+  fn schema_name() -> String {
+    "SubProfession".to_owned()
+  }
+
+  fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    String::json_schema(gen)
+  }
+}
+
+#[derive(Debug, Error, Clone, Copy)]
+#[error("unknown sub-profession")]
+pub struct ParseSubProfessionError;
+
 /// Past, current or future. Used for filtering events and headhunting banners.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Tense {
   Past,
   Current,
@@ -882,13 +2628,58 @@ impl Tense {
   }
 }
 
-/// A playable in-game event.
+/// Either an event or a headhunting banner, see [`GameData::iter_timeline`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineEntry<'a> {
+  Event(&'a Event),
+  Banner(&'a HeadhuntingBanner)
+}
+
+impl<'a> TimelineEntry<'a> {
+  /// The internal ID of this event or banner.
+  pub fn id(&self) -> &'a str {
+    match *self {
+      TimelineEntry::Event(event) => &event.id,
+      TimelineEntry::Banner(banner) => &banner.id
+    }
+  }
+
+  /// The name of this event or banner.
+  pub fn name(&self) -> &'a str {
+    match *self {
+      TimelineEntry::Event(event) => &event.name,
+      TimelineEntry::Banner(banner) => &banner.name
+    }
+  }
+
+  /// The time this event or banner opens.
+  pub fn open_time(&self) -> DateTime<Utc> {
+    match *self {
+      TimelineEntry::Event(event) => event.open_time,
+      TimelineEntry::Banner(banner) => banner.open_time
+    }
+  }
+
+  /// The time this event or banner closes entirely. For events, this includes the time the shop is still open.
+  pub fn close_time(&self) -> DateTime<Utc> {
+    match *self {
+      TimelineEntry::Event(event) => event.close_time_rewards,
+      TimelineEntry::Banner(banner) => banner.close_time
+    }
+  }
+}
+
+/// An in-game event.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Event {
   /// The internal ID of this event.
   pub id: String,
   pub name: String,
   pub event_type: EventType,
+  /// The activity type of this event as listed in the game files, such as `TYPE_ACT9D0` or `LOGIN_ONLY`.
+  #[serde(default)]
+  pub activity_type: String,
   /// The time this event starts.
   pub open_time: DateTime<Utc>,
   /// The time the levels on this event close.
@@ -919,11 +2710,52 @@ impl Event {
   pub fn is_future(&self, now: DateTime<Utc>) -> bool {
     self.open_time > now
   }
+
+  /// Whether this event is open at any point between `start` and `end`, including the time the shop is still open.
+  pub fn overlaps(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+    self.open_time < end && start < self.close_time_rewards
+  }
+
+  /// Classifies this event by how it is played, see [`EventCategory`].
+  pub fn category(&self) -> EventCategory {
+    match self.event_type {
+      EventType::Intermezzi | EventType::SideStory | EventType::Vignette | EventType::AprilFools => EventCategory::HasStages,
+      EventType::Login => EventCategory::LoginOnly,
+      EventType::Checkin => EventCategory::Checkin,
+      EventType::Collab => EventCategory::Collab,
+      EventType::Other => EventCategory::Other
+    }
+  }
+
+  /// Whether this event has stages to play, as opposed to only handing out rewards.
+  pub fn has_stages(&self) -> bool {
+    self.category() == EventCategory::HasStages
+  }
+}
+
+/// How an event is played, for telling playable content apart from events that only hand out rewards.
+/// See [`Event::category`].
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum EventCategory {
+  /// Events with stages to play, such as story events.
+  HasStages,
+  /// Events where rewards are claimed by logging in.
+  LoginOnly,
+  /// Events where rewards are claimed by checking in each day.
+  Checkin,
+  /// The reward pages of crossover events with other franchises.
+  /// (The stages of a crossover are listed as their own story event)
+  Collab,
+  /// Any other kind of event, such as mini-games or special shops.
+  Other
 }
 
 /// A playable in-game event's categorization.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum EventType {
   /// For example: A Walk in the Dust, Darknights Memoir.
   Intermezzi,
@@ -931,11 +2763,22 @@ pub enum EventType {
   SideStory,
   /// Also known as "Story Collections" or "Omnibus Events".
   /// For example: Children of Ursus, Vigilo.
-  Vignette
+  Vignette,
+  /// Events where rewards are claimed by logging in.
+  Login,
+  /// Events where rewards are claimed by checking in each day, such as the anniversary and new year check-ins.
+  Checkin,
+  /// Crossover events with other franchises.
+  Collab,
+  /// The annual April Fools' events.
+  AprilFools,
+  /// Any other kind of event, such as mini-games or special shops.
+  Other
 }
 
 /// A headhunting banner.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct HeadhuntingBanner {
   /// The internal ID of this headhunting banner.
   pub id: String,
@@ -949,8 +2792,15 @@ pub struct HeadhuntingBanner {
   pub close_time: DateTime<Utc>,
   /// The ID of the 'Headhunting Data Contract' item (free 10-pull item).
   /// associated with this banner, if it has one.
-  pub item_id: Option<String>,
-  pub banner_type: HeadhuntingBannerType
+  pub item_id: Option<ItemId>,
+  pub banner_type: HeadhuntingBannerType,
+  /// The ID of the event that this banner runs alongside, if any.
+  ///
+  /// The game files do not link banners to events directly, so this is a best-effort guess
+  /// based on the banner and event opening at (nearly) the same time.
+  pub event_id: Option<String>,
+  /// The pull rules that apply to this banner.
+  pub rules: BannerRules
 }
 
 impl HeadhuntingBanner {
@@ -969,15 +2819,109 @@ impl HeadhuntingBanner {
     self.open_time > now
   }
 
+  /// Whether this banner is open at any point between `start` and `end`.
+  pub fn overlaps(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+    self.open_time < end && start < self.close_time
+  }
+
   /// Gets the [`Item`] of the 'Headhunting Data Contract' item associated with this banner, if any.
-  pub fn get_item<'a>(&self, items: &'a Map<String, Item>) -> Option<&'a Item> {
+  pub fn get_item<'a>(&self, items: &'a Map<ItemId, Item>) -> Option<&'a Item> {
     self.item_id.as_deref().and_then(|item_id| items.get(item_id))
   }
+
+  /// Gets the [`Event`] that this banner runs alongside, if any. See [`HeadhuntingBanner::event_id`].
+  pub fn get_event<'a>(&self, events: &'a [Event]) -> Option<&'a Event> {
+    self.event_id.as_deref().and_then(|event_id| events.iter().find(|event| event.id == event_id))
+  }
+}
+
+/// A beginner headhunting banner, only available to new players.
+///
+/// Unlike [`HeadhuntingBanner`]s, beginner banners never open or close, and instead allow a limited number of pulls.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BeginnerBanner {
+  /// The internal ID of this beginner banner.
+  pub id: String,
+  pub name: String,
+  /// A description of this banner's rules, if any.
+  pub description: Option<String>,
+  pub index: u32,
+  /// The cost of a single pull on this banner, in Orundum.
+  pub pull_cost: u32,
+  /// The number of pulls that can be made on this banner in total.
+  pub max_pulls: u32
+}
+
+/// A special recruitment pool, which temporarily changes the recruitment durations on offer and what they cost.
+///
+/// Unlike [`HeadhuntingBanner`]s, special pools have no featured operators or pull rules.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SpecialRecruitmentPool {
+  /// The internal ID of this special pool.
+  pub id: String,
+  pub index: u32,
+  pub open_time: DateTime<Utc>,
+  pub close_time: DateTime<Utc>,
+  /// The recruitment durations offered while this pool is open.
+  pub durations: Vec<SpecialRecruitmentDuration>
+}
+
+/// A recruitment duration offered by a [`SpecialRecruitmentPool`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SpecialRecruitmentDuration {
+  /// How long a recruitment of this duration lasts, in hours.
+  pub hours: u32,
+  /// The price of a recruitment of this duration, as listed in the game files.
+  pub price: u32,
+  /// The items consumed by a recruitment of this duration, if any.
+  pub item_cost: ItemsCost
+}
+
+/// Rules that apply to pulls made on a headhunting banner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BannerRules {
+  /// If present, a 5-star or higher operator is guaranteed within this many pulls.
+  /// (Usually the first 10 pulls made on the banner)
+  pub guaranteed_5_star_within: Option<u32>,
+  /// If present, the featured 6-star operator is guaranteed within this many pulls.
+  /// (So far, this has only appeared on crossover banners)
+  pub guaranteed_6_star_within: Option<u32>,
+  /// If present, the number of pulls after which a featured limited operator can be claimed directly.
+  pub spark_pulls: Option<u32>,
+  /// The number of free pulls this banner gives out, if any.
+  pub free_pulls: Option<u32>,
+  /// The IDs of the limited operators featured on this banner.
+  pub limited_operator_ids: Vec<OperatorId>
+}
+
+impl BannerRules {
+  /// The number of pulls without a 6-star operator after which the 6-star rate starts to increase.
+  ///
+  /// This is not stored in the game files, and is the same for every banner.
+  pub const PITY_THRESHOLD: u32 = 50;
+  /// The amount the 6-star rate increases by for every pull past [`BannerRules::PITY_THRESHOLD`].
+  ///
+  /// This is not stored in the game files, and is the same for every banner.
+  pub const PITY_RATE_INCREASE: f32 = 0.02;
+  /// The base rate of pulling a 6-star operator.
+  pub const BASE_6_STAR_RATE: f32 = 0.02;
+
+  /// Calculates the chance that the next pull will be a 6-star operator,
+  /// given the number of pulls that have been made since the last one.
+  pub fn six_star_rate(pulls_without_6_star: u32) -> f32 {
+    let pity_pulls = (pulls_without_6_star + 1).saturating_sub(Self::PITY_THRESHOLD);
+    (Self::BASE_6_STAR_RATE + pity_pulls as f32 * Self::PITY_RATE_INCREASE).min(1.0)
+  }
 }
 
 /// A headhunting banner's categorization.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum HeadhuntingBannerType {
   /// A typical event banner.
   Normal,
@@ -991,6 +2935,7 @@ pub enum HeadhuntingBannerType {
 
 /// Represents an RIIC base room that can exist.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Building {
   pub building_type: BuildingType,
   pub name: String,
@@ -1004,6 +2949,7 @@ pub struct Building {
 
 /// Represents a potential upgrade that can be applied to an RIIC base room.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct BuildingUpgrade {
   pub unlock_condition: String,
   /// Materials required to construct/upgrade this building.
@@ -1020,14 +2966,31 @@ pub struct BuildingUpgrade {
 impl BuildingUpgrade {
   /// Returns an iterator over the [`Item`]s required to obtain this upgrade.
   #[inline]
-  pub fn iter_construction_cost<'a>(&'a self, items: &'a Map<String, Item>) -> ItemsIter<'a> {
+  pub fn iter_construction_cost<'a>(&'a self, items: &'a Map<ItemId, Item>) -> ItemsIter<'a> {
     ItemsIter::new(&self.construction_cost, items)
   }
 }
 
+/// A piece of furniture that can be placed in an RIIC base dormitory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Furniture {
+  /// The internal ID of this furniture.
+  pub id: String,
+  pub name: String,
+  pub description: Option<String>,
+  pub usage: Option<String>,
+  pub rarity: u32,
+  /// The ID of the furniture set (theme) this furniture belongs to, if any.
+  pub theme_id: Option<String>,
+  /// The ambience this furniture adds to a dormitory, see [`GameData::dormitory_ambience`].
+  pub ambience: u32
+}
+
 /// An RIIC base building's categorization.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum BuildingType {
   ControlCenter,
   PowerPlant,
@@ -1044,25 +3007,155 @@ pub enum BuildingType {
 
 /// A map of item IDs and counts.
 /// Usually represents the total resource cost of an upgrade or unlockable.
-pub type ItemsCost = Map<String, u32>;
+pub type ItemsCost = Map<ItemId, u32>;
+
+/// Something that consumes an item, see [`GameData::item_usages`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ItemUsage {
+  /// Promoting an operator to the given promotion.
+  Promotion {
+    operator_id: OperatorId,
+    promotion: Promotion,
+    count: u32
+  },
+  /// Upgrading all of an operator's skills to the given skill level (2-7).
+  SkillLevel {
+    operator_id: OperatorId,
+    level: u8,
+    count: u32
+  },
+  /// Upgrading an operator's skill to the given mastery level (1-3).
+  SkillMastery {
+    operator_id: OperatorId,
+    skill_id: SkillId,
+    level: u8,
+    count: u32
+  },
+  /// Unlocking one of an operator's modules.
+  Module {
+    operator_id: OperatorId,
+    module_id: ModuleId,
+    count: u32
+  },
+  /// Constructing or upgrading an RIIC base room to the given level.
+  BuildingUpgrade {
+    building_type: BuildingType,
+    level: u32,
+    count: u32
+  }
+}
+
+impl ItemUsage {
+  /// The number of the item consumed.
+  pub fn count(&self) -> u32 {
+    match *self {
+      ItemUsage::Promotion { count, .. } => count,
+      ItemUsage::SkillLevel { count, .. } => count,
+      ItemUsage::SkillMastery { count, .. } => count,
+      ItemUsage::Module { count, .. } => count,
+      ItemUsage::BuildingUpgrade { count, .. } => count
+    }
+  }
+
+  /// The ID of the operator this usage belongs to, if any.
+  pub fn operator_id(&self) -> Option<&str> {
+    match self {
+      ItemUsage::Promotion { operator_id, .. } => Some(operator_id.as_str()),
+      ItemUsage::SkillLevel { operator_id, .. } => Some(operator_id.as_str()),
+      ItemUsage::SkillMastery { operator_id, .. } => Some(operator_id.as_str()),
+      ItemUsage::Module { operator_id, .. } => Some(operator_id.as_str()),
+      ItemUsage::BuildingUpgrade { .. } => None
+    }
+  }
+}
 
 /// An item.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Item {
   /// The internal ID of this item.
-  pub id: String,
+  pub id: ItemId,
   pub name: String,
   pub description: Option<String>,
   pub rarity: u32,
   pub usage: Option<String>,
   pub obtain: Option<String>,
   pub item_class: ItemClass,
-  pub item_type: String
+  pub item_type: String,
+  /// The ID of this item's icon, see [`AssetMirror::item_icon_url`][crate::assets::AssetMirror::item_icon_url].
+  pub icon_id: String,
+  /// The amount of operator EXP this item gives when used, if it is an EXP card.
+  #[serde(default)]
+  pub exp_value: Option<u32>,
+  /// The key the game uses to order items in the depot, lower values are shown first.
+  pub sort_id: i32
+}
+
+impl Item {
+  /// Classifies this item as an upgrade material, chip, skill summary or module data, along with its tier.
+  /// Returns `None` for items that are not used for upgrading operators, such as EXP cards.
+  pub fn tier(&self) -> Option<MaterialTier> {
+    if self.item_type != "MATERIAL" { return None };
+    let id = self.id.as_str();
+    let is_numeric = id.bytes().all(|byte| byte.is_ascii_digit());
+    let kind = if id.starts_with("mod_unlock_token") || id.starts_with("mod_update_token") {
+      MaterialKind::ModuleData
+    } else if !is_numeric {
+      return None;
+    } else if id.len() == 5 && (id.starts_with("30") || id.starts_with("31")) {
+      MaterialKind::Material
+    } else if id.len() == 5 && id.starts_with("32") {
+      MaterialKind::ChipCatalyst
+    } else if id.len() == 4 && id.starts_with("32") {
+      MaterialKind::Chip
+    } else if id.len() == 4 && id.starts_with("33") {
+      MaterialKind::SkillSummary
+    } else {
+      return None;
+    };
+
+    Some(MaterialTier { kind, tier: self.rarity as u8 + 1 })
+  }
+}
+
+/// The kind and tier of an upgrade material, see [`Item::tier`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MaterialTier {
+  pub kind: MaterialKind,
+  /// The tier of this material from 1 to 5, which is one more than its rarity.
+  /// (Example: Orirock Cube is a tier 2 material, and a Vanguard Chip Pack is a tier 4 chip)
+  pub tier: u8
+}
+
+impl MaterialTier {
+  pub const fn new(kind: MaterialKind, tier: u8) -> Self {
+    MaterialTier { kind, tier }
+  }
+}
+
+/// A kind of upgrade material, see [`MaterialTier`].
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum MaterialKind {
+  /// Materials used for promotions, skill levels, masteries and building upgrades, such as Orirock.
+  Material,
+  /// Chips, chip packs and dualchips used for promotions.
+  Chip,
+  /// Chip Catalysts, used to craft dualchips.
+  ChipCatalyst,
+  /// Skill summaries used for skill levels.
+  SkillSummary,
+  /// Module data blocks used to unlock and upgrade modules.
+  ModuleData
 }
 
 /// An item's categorization.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ItemClass {
   Consumable,
   BasicItem,
@@ -1072,6 +3165,7 @@ pub enum ItemClass {
 
 /// Contains operator file entries.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorFile {
   /// The ID of the operator to whom this file belongs.
   pub operator_id: String,
@@ -1083,6 +3177,16 @@ pub struct OperatorFile {
 }
 
 impl OperatorFile {
+  /// An operator file with no contents, used when operator files are not loaded.
+  /// See [`TableSelection::operator_files`].
+  pub fn empty(operator_id: &str) -> Self {
+    OperatorFile {
+      operator_id: operator_id.to_owned(),
+      illustrator_name: String::new(),
+      entries: Vec::new()
+    }
+  }
+
   /// Returns an iterator over all contained [`OperatorFileEntry`]s.
   #[inline]
   pub fn iter(&self) -> <&Self as IntoIterator>::IntoIter {
@@ -1118,6 +3222,7 @@ impl<'a> IntoIterator for &'a OperatorFile {
 
 /// A single entry in the operator's file.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatorFileEntry {
   pub title: String,
   pub text: String,
@@ -1164,6 +3269,7 @@ fn split_text_line(line: &str) -> Option<(&str, &str)> {
 
 /// The unlock condition associated with an operator file entry.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum OperatorFileUnlock {
   /// This file entry is always unlocked.
   AlwaysUnlocked,
@@ -1191,7 +3297,10 @@ impl OperatorFileUnlock {
 
 /// The set of grid tiles that an operator can attack.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AttackRange {
+  // mint serializes points as `[x, y]`
+  #[cfg_attr(feature = "schemars", schemars(with = "Set<[i32; 2]>"))]
   pub points: Set<Point2<i32>>
 }
 
@@ -1206,6 +3315,58 @@ impl AttackRange {
   pub fn iter(&self) -> <&Self as IntoIterator>::IntoIter {
     self.into_iter()
   }
+
+  /// Returns the minimum and maximum corners of the smallest rectangle containing every grid tile.
+  /// This always includes the tile that the operator is standing on (`(0, 0)`).
+  pub fn bounding_box(&self) -> (Point2<i32>, Point2<i32>) {
+    let origin = Point2 { x: 0, y: 0 };
+    self.points.iter().fold((origin, origin), |(min, max), point| {
+      let min = Point2 { x: min.x.min(point.x), y: min.y.min(point.y) };
+      let max = Point2 { x: max.x.max(point.x), y: max.y.max(point.y) };
+      (min, max)
+    })
+  }
+
+  /// The number of columns covered by this attack range's bounding box.
+  pub fn width(&self) -> u32 {
+    let (min, max) = self.bounding_box();
+    (max.x - min.x) as u32 + 1
+  }
+
+  /// The number of rows covered by this attack range's bounding box.
+  pub fn height(&self) -> u32 {
+    let (min, max) = self.bounding_box();
+    (max.y - min.y) as u32 + 1
+  }
+
+  /// Returns an iterator over every row of this attack range's bounding box, from the lowest row to the highest.
+  /// Each row contains one entry per column, indicating whether or not that grid tile is in range.
+  pub fn iter_rows(&self) -> impl Iterator<Item = Vec<bool>> + DoubleEndedIterator + '_ {
+    let (min, max) = self.bounding_box();
+    (min.y..=max.y).map(move |y| {
+      (min.x..=max.x).map(|x| self.contains([x, y])).collect()
+    })
+  }
+
+  /// Converts this attack range into a 2D grid of booleans, indexed by `[row][column]`.
+  /// See [`AttackRange::iter_rows`].
+  pub fn to_grid(&self) -> Vec<Vec<bool>> {
+    self.iter_rows().collect()
+  }
+
+  /// Renders this attack range as text, one line per row.
+  /// Tiles in range are drawn as `#`, tiles out of range as `.`, and the operator's own tile as `O`.
+  pub fn render_ascii(&self) -> String {
+    let (min, _) = self.bounding_box();
+    let lines = self.iter_rows().enumerate().map(|(row, tiles)| {
+      tiles.into_iter().enumerate().map(|(col, tile)| {
+        let is_origin = min.x + col as i32 == 0 && min.y + row as i32 == 0;
+        if is_origin { 'O' } else if tile { '#' } else { '.' }
+      }).collect::<String>()
+    });
+
+    lines.collect::<Vec<String>>().join("\n")
+  }
 }
 
 impl IntoIterator for AttackRange {
@@ -1232,19 +3393,26 @@ impl<'a> IntoIterator for &'a AttackRange {
 #[derive(Debug, Clone)]
 pub struct ItemsIter<'a> {
   iter: <&'a ItemsCost as IntoIterator>::IntoIter,
-  items: &'a Map<String, Item>
+  items: &'a Map<ItemId, Item>
 }
 
 impl<'a> ItemsIter<'a> {
   #[inline]
-  pub fn new(list: &'a ItemsCost, items: &'a Map<String, Item>) -> Self {
+  pub fn new(list: &'a ItemsCost, items: &'a Map<ItemId, Item>) -> Self {
     ItemsIter { iter: list.iter(), items }
   }
 
+  /// Converts this iterator into one that reports item IDs missing from the items map
+  /// as errors, instead of silently skipping them.
+  #[inline]
+  pub fn strict(self) -> ItemsIterStrict<'a> {
+    ItemsIterStrict { iter: self.iter, items: self.items }
+  }
+
   #[inline]
   fn get(
-    items: &'a Map<String, Item>,
-    (id, &count): (&'a String, &'a u32)
+    items: &'a Map<ItemId, Item>,
+    (id, &count): (&'a ItemId, &'a u32)
   ) -> Option<(&'a Item, u32)> {
     items.get(id).map(|item| (item, count))
   }
@@ -1276,3 +3444,35 @@ impl<'a> Iterator for ItemsIter<'a> {
     })
   }
 }
+
+/// Iterates over [`Item`]s given a list of item IDs, yielding an error for any item ID that could not be found.
+/// Created with [`ItemsIter::strict`].
+#[derive(Debug, Clone)]
+pub struct ItemsIterStrict<'a> {
+  iter: <&'a ItemsCost as IntoIterator>::IntoIter,
+  items: &'a Map<ItemId, Item>
+}
+
+impl<'a> Iterator for ItemsIterStrict<'a> {
+  type Item = Result<(&'a Item, u32), MissingItemId>;
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next().map(|(id, &count)| match self.items.get(id) {
+      Some(item) => Ok((item, count)),
+      None => Err(MissingItemId(id.clone()))
+    })
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<'a> ExactSizeIterator for ItemsIterStrict<'a> {}
+
+/// An item ID that was referenced by a cost but does not exist in the items map.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Error)]
+#[error("missing item id \"{0}\"")]
+pub struct MissingItemId(pub ItemId);
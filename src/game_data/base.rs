@@ -0,0 +1,365 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{Map, Set};
+use super::{Building, BuildingType, BuildingUpgrade, GameData, OperatorBaseSkillPhase, OperatorId, PromotionAndLevel};
+
+/// Efficiency that every operator stationed in a production room adds, regardless of their base skills.
+const EFFICIENCY_PER_OPERATOR: f32 = 0.01;
+/// Morale that every stationed operator loses per hour, before their base skills are applied.
+const BASE_MORALE_DRAIN: f32 = 1.0;
+/// The most morale an operator can have.
+pub const MAX_MORALE: f32 = 24.0;
+/// The most ambience a dormitory counts for each of its levels.
+const AMBIENCE_PER_DORMITORY_LEVEL: u32 = 1000;
+/// Morale that operators resting in a dormitory recover per hour, before ambience is applied.
+const BASE_MORALE_RECOVERY: f32 = 1.5;
+/// Morale recovered per hour for every point of ambience.
+const MORALE_RECOVERY_PER_AMBIENCE: f32 = 0.0004;
+
+/// A room placed in a [`BaseLayout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PlacedRoom {
+  pub building_type: BuildingType,
+  /// The level of this room, starting at 1. (Level 1 is [`Building::upgrades`] index 0)
+  pub level: u32,
+  /// The column and row of this room's top left tile. Rows are counted downwards, one row per floor.
+  pub position: (u32, u32),
+  /// The number of columns and rows this room covers, taken from [`Building::size`].
+  pub size: (u32, u32)
+}
+
+impl PlacedRoom {
+  fn overlaps(&self, other: &PlacedRoom) -> bool {
+    let (x, y, width, height) = self.bounds();
+    let (other_x, other_y, other_width, other_height) = other.bounds();
+    x < other_x + other_width && other_x < x + width && y < other_y + other_height && other_y < y + height
+  }
+
+  /// Whether or not operators can walk between this room and another room.
+  /// Rooms are connected when they are side by side on the same floor, or when they are elevators stacked on top of each other.
+  fn is_connected_to(&self, other: &PlacedRoom) -> bool {
+    let (x, y, width, height) = self.bounds();
+    let (other_x, other_y, other_width, other_height) = other.bounds();
+    let shares_floor = y < other_y + other_height && other_y < y + height;
+    let shares_column = x < other_x + other_width && other_x < x + width;
+    let side_by_side = shares_floor && (x + width == other_x || other_x + other_width == x);
+    let stacked = shares_column && (y + height == other_y || other_y + other_height == y);
+    let both_elevators = self.building_type == BuildingType::Elevator && other.building_type == BuildingType::Elevator;
+    side_by_side || (stacked && both_elevators)
+  }
+
+  fn bounds(&self) -> (u32, u32, u32, u32) {
+    (self.position.0, self.position.1, self.size.0, self.size.1)
+  }
+}
+
+/// A reason a room could not be placed in a [`BaseLayout`], or a problem found by [`BaseLayout::validate`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Error)]
+pub enum PlacementError {
+  /// The building does not exist in the given buildings.
+  #[error("unknown building {0:?}")]
+  UnknownBuilding(BuildingType),
+  /// The building does not have the requested level.
+  #[error("building {building_type:?} has no level {level}")]
+  InvalidLevel { building_type: BuildingType, level: u32 },
+  /// The layout already has [`Building::max_count`] rooms of this building.
+  #[error("building {building_type:?} cannot be placed more than {max_count} times")]
+  TooMany { building_type: BuildingType, max_count: u32 },
+  /// The room would overlap the room at the given index.
+  #[error("room overlaps room {0}")]
+  Overlapping(usize),
+  /// The layout does not have a control center.
+  #[error("layout has no control center")]
+  MissingControlCenter,
+  /// The room at the given index cannot be reached from the control center
+  /// through neighboring rooms, corridors and elevators.
+  #[error("room {0} is not connected to the control center")]
+  Disconnected(usize)
+}
+
+/// A plan for an RIIC base, made up of rooms placed on a grid.
+///
+/// Rooms are placed with [`BaseLayout::place`], which checks each room against the buildings it was given,
+/// while [`BaseLayout::validate`] checks that every room can be reached from the control center.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BaseLayout {
+  pub rooms: Vec<PlacedRoom>
+}
+
+impl BaseLayout {
+  pub fn new() -> Self {
+    BaseLayout::default()
+  }
+
+  /// Places a room at the given level and position, returning its index.
+  ///
+  /// Fails if the building or level does not exist, if the building already appears [`Building::max_count`] times,
+  /// or if the room would overlap another room.
+  pub fn place(
+    &mut self,
+    buildings: &Map<BuildingType, Building>,
+    building_type: BuildingType,
+    level: u32,
+    position: (u32, u32)
+  ) -> Result<usize, PlacementError> {
+    let building = buildings.get(&building_type)
+      .ok_or(PlacementError::UnknownBuilding(building_type))?;
+    get_upgrade(building, level)
+      .ok_or(PlacementError::InvalidLevel { building_type, level })?;
+    if let Some(max_count) = building.max_count {
+      if self.count(building_type) >= max_count as usize {
+        return Err(PlacementError::TooMany { building_type, max_count });
+      };
+    };
+
+    let room = PlacedRoom { building_type, level, position, size: building.size };
+    if let Some(index) = self.rooms.iter().position(|other| room.overlaps(other)) {
+      return Err(PlacementError::Overlapping(index));
+    };
+
+    self.rooms.push(room);
+    Ok(self.rooms.len() - 1)
+  }
+
+  /// Removes the room at the given index, shifting the indices of the rooms after it.
+  pub fn remove(&mut self, index: usize) -> Option<PlacedRoom> {
+    (index < self.rooms.len()).then(|| self.rooms.remove(index))
+  }
+
+  /// Changes the level of the room at the given index. Panics if the index is out of bounds.
+  pub fn set_level(&mut self, buildings: &Map<BuildingType, Building>, index: usize, level: u32) -> Result<(), PlacementError> {
+    let room = &mut self.rooms[index];
+    let building_type = room.building_type;
+    buildings.get(&building_type)
+      .ok_or(PlacementError::UnknownBuilding(building_type))
+      .and_then(|building| get_upgrade(building, level).ok_or(PlacementError::InvalidLevel { building_type, level }))?;
+    room.level = level;
+    Ok(())
+  }
+
+  /// The number of rooms of the given building in this layout.
+  pub fn count(&self, building_type: BuildingType) -> usize {
+    self.rooms.iter().filter(|room| room.building_type == building_type).count()
+  }
+
+  /// Checks that this layout has a control center, and that every room can be reached from it.
+  /// An empty list means the layout is valid.
+  pub fn validate(&self) -> Vec<PlacementError> {
+    let control_center = match self.rooms.iter().position(|room| room.building_type == BuildingType::ControlCenter) {
+      Some(control_center) => control_center,
+      None => return vec![PlacementError::MissingControlCenter]
+    };
+
+    let mut connected = vec![false; self.rooms.len()];
+    let mut queue = vec![control_center];
+    connected[control_center] = true;
+    while let Some(index) = queue.pop() {
+      for (other_index, other) in self.rooms.iter().enumerate() {
+        if !connected[other_index] && self.rooms[index].is_connected_to(other) {
+          connected[other_index] = true;
+          queue.push(other_index);
+        };
+      };
+    };
+
+    connected.into_iter().enumerate()
+      .filter(|&(_, connected)| !connected)
+      .map(|(index, _)| PlacementError::Disconnected(index))
+      .collect()
+  }
+
+  /// Returns an iterator over every room along with the upgrade matching its level.
+  /// Rooms whose building or level does not exist in the given buildings are left out.
+  pub fn iter_upgrades<'a>(&'a self, buildings: &'a Map<BuildingType, Building>) -> impl Iterator<Item = (&'a PlacedRoom, &'a BuildingUpgrade)> {
+    self.rooms.iter().filter_map(|room| {
+      let building = buildings.get(&room.building_type)?;
+      Some((room, get_upgrade(building, room.level)?))
+    })
+  }
+
+  /// The power produced by power plants, minus the power consumed by every other room.
+  /// A negative power balance means the base does not have enough power plants.
+  pub fn power_balance(&self, buildings: &Map<BuildingType, Building>) -> i32 {
+    self.iter_upgrades(buildings).map(|(_, upgrade)| upgrade.power).sum()
+  }
+
+  /// The total number of drones spent constructing and upgrading every room to its current level.
+  pub fn construction_drones(&self, buildings: &Map<BuildingType, Building>) -> u32 {
+    self.rooms.iter()
+      .filter_map(|room| buildings.get(&room.building_type).map(|building| (room, building)))
+      .flat_map(|(room, building)| building.upgrades.iter().take(room.level as usize))
+      .map(|upgrade| upgrade.construction_drones)
+      .sum()
+  }
+
+  /// The total manpower cost of every room at its current level.
+  pub fn manpower_cost(&self, buildings: &Map<BuildingType, Building>) -> u32 {
+    self.iter_upgrades(buildings).map(|(_, upgrade)| upgrade.manpower_cost).sum()
+  }
+
+  /// The total number of operators that can be stationed across every room.
+  pub fn operator_capacity(&self, buildings: &Map<BuildingType, Building>) -> u32 {
+    self.iter_upgrades(buildings).map(|(_, upgrade)| upgrade.operator_capacity).sum()
+  }
+}
+
+fn get_upgrade(building: &Building, level: u32) -> Option<&BuildingUpgrade> {
+  building.upgrades.get(level.checked_sub(1)? as usize)
+}
+
+/// The effect of a base skill phase on the room it is used in, see [`GameData::simulate_room`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BaseSkillEffect {
+  /// Added to the efficiency of the room, where `0.15` is +15%.
+  pub efficiency: f32,
+  /// Added to the hourly morale drain of the operator with this skill, where `-0.25` drains 0.25 less morale per hour.
+  pub morale_drain: f32
+}
+
+static RX_BASE_SKILL: Lazy<Regex> = Lazy::new(|| {
+  Regex::new(r"(?i)^when (?:this operator is )?(?:assigned|stationed) (?:to|in) (?:an? |the )?[a-z ]+?, (.+?)\.?$").unwrap()
+});
+static RX_EFFICIENCY: Lazy<Regex> = Lazy::new(|| {
+  Regex::new(r"(?i)^(?:productivity|order acquisition efficiency) ([+-]\d+(?:\.\d+)?)%$").unwrap()
+});
+static RX_MORALE_DRAIN: Lazy<Regex> = Lazy::new(|| {
+  Regex::new(r"(?i)^morale (?:consumption|drain) per hour ([+-]\d+(?:\.\d+)?)$").unwrap()
+});
+
+impl BaseSkillEffect {
+  /// Reads the effect of a base skill phase from its English description, with any rich text tags already removed.
+  ///
+  /// Only descriptions made up entirely of a flat productivity or order acquisition efficiency bonus
+  /// and a change to the operator's own morale drain are recognized.
+  /// (Example: `"When this Operator is assigned to a Factory, Productivity +15%"`)
+  /// Anything else, such as morale recovery, effects that depend on other operators or the room,
+  /// or descriptions in other languages, returns `None` rather than a guess.
+  pub fn from_description(description: &str) -> Option<BaseSkillEffect> {
+    let clauses = RX_BASE_SKILL.captures(description.trim())?.get(1)?.as_str();
+    let (mut efficiency, mut morale_drain) = (None, None);
+    for clause in clauses.split([',', ';']).map(str::trim) {
+      if let Some(captures) = RX_EFFICIENCY.captures(clause) {
+        if efficiency.replace(captures[1].parse::<f32>().ok()? / 100.0).is_some() { return None };
+      } else if let Some(captures) = RX_MORALE_DRAIN.captures(clause) {
+        if morale_drain.replace(captures[1].parse::<f32>().ok()?).is_some() { return None };
+      } else {
+        return None;
+      };
+    };
+
+    Some(BaseSkillEffect {
+      efficiency: efficiency.unwrap_or(0.0),
+      morale_drain: morale_drain.unwrap_or(0.0)
+    })
+  }
+}
+
+/// An operator stationed in a room, see [`GameData::simulate_room`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct StationedOperator {
+  pub operator_id: OperatorId,
+  pub promotion_and_level: PromotionAndLevel
+}
+
+/// The result of [`GameData::simulate_room`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RoomSimulation {
+  /// The efficiency of the room, where `1.0` is the room's base production rate.
+  pub efficiency: f32,
+  /// The hourly morale drain of each stationed operator, in the order they were given.
+  pub morale_drain: Vec<f32>,
+  /// The names of the base skill phases that were applied.
+  pub applied_skills: Vec<String>,
+  /// The names of the base skill phases that apply to the room, but whose effects were not known.
+  pub unknown_skills: Vec<String>
+}
+
+impl RoomSimulation {
+  /// The amount produced per hour, given the amount the room produces per hour at base efficiency.
+  pub fn production_per_hour(&self, base_per_hour: f32) -> f32 {
+    base_per_hour * self.efficiency
+  }
+
+  /// The number of hours each stationed operator can work, starting from full morale.
+  /// Operators who do not lose morale can work indefinitely.
+  pub fn hours_until_exhausted(&self) -> Vec<f32> {
+    self.morale_drain.iter()
+      .map(|&morale_drain| if morale_drain > 0.0 { MAX_MORALE / morale_drain } else { f32::INFINITY })
+      .collect()
+  }
+}
+
+impl GameData {
+  /// Computes the efficiency and morale drain of a factory, trading post or other production room
+  /// with the given operators stationed in it.
+  ///
+  /// Every stationed operator adds 1% efficiency and drains 1 morale per hour, after which the
+  /// [`effect`][OperatorBaseSkillPhase::effect] of each of their unlocked base skill phases for this room is added.
+  /// Phases without a known effect are listed in [`RoomSimulation::unknown_skills`].
+  /// Operators that do not exist in [`GameData::operators`] are treated as having no base skills.
+  pub fn simulate_room(&self, building_type: BuildingType, operators: &[StationedOperator]) -> RoomSimulation {
+    self.simulate_room_with(building_type, operators, |_| None)
+  }
+
+  /// Like [`GameData::simulate_room`], except that `override_effect` is asked for the effect of each phase first,
+  /// falling back to the effect read from the game data if it returns `None`.
+  /// Useful for skills whose effects depend on more than their description says, such as the other stationed operators.
+  pub fn simulate_room_with<F>(&self, building_type: BuildingType, operators: &[StationedOperator], mut override_effect: F) -> RoomSimulation
+  where F: FnMut(&OperatorBaseSkillPhase) -> Option<BaseSkillEffect> {
+    let mut simulation = RoomSimulation { efficiency: 1.0, ..RoomSimulation::default() };
+    for stationed_operator in operators {
+      simulation.efficiency += EFFICIENCY_PER_OPERATOR;
+      let mut morale_drain = BASE_MORALE_DRAIN;
+
+      let base_skills = self.operators.get(&stationed_operator.operator_id)
+        .map_or(&[][..], |operator| &operator.base_skills);
+      let phases = base_skills.iter()
+        .filter_map(|base_skill| base_skill.get_unlocked(stationed_operator.promotion_and_level))
+        .filter(|phase| phase.building_type == building_type);
+      for phase in phases {
+        match override_effect(phase).or(phase.effect) {
+          Some(effect) => {
+            simulation.efficiency += effect.efficiency;
+            morale_drain += effect.morale_drain;
+            simulation.applied_skills.push(phase.name.clone());
+          },
+          None => simulation.unknown_skills.push(phase.name.clone())
+        };
+      };
+
+      simulation.morale_drain.push(morale_drain.max(0.0));
+    };
+
+    simulation
+  }
+
+  /// Computes the ambience of a dormitory of the given level furnished with the given furniture.
+  ///
+  /// Each distinct piece of furniture only counts once, and the total is capped at 1000 ambience per dormitory level.
+  /// Furniture that does not exist in [`GameData::furniture`] is ignored. Bonuses for completing a furniture set are not included.
+  pub fn dormitory_ambience<'a, I>(&self, furniture_ids: I, level: u32) -> u32
+  where I: IntoIterator<Item = &'a str> {
+    let furniture_ids = furniture_ids.into_iter().collect::<Set<&str>>();
+    let ambience = furniture_ids.into_iter()
+      .filter_map(|furniture_id| self.furniture.get(furniture_id))
+      .map(|furniture| furniture.ambience)
+      .sum::<u32>();
+    ambience.min(level * AMBIENCE_PER_DORMITORY_LEVEL)
+  }
+}
+
+/// The morale recovered per hour by each operator resting in a dormitory of the given level with the given ambience,
+/// not including the effects of base skills. See [`GameData::dormitory_ambience`].
+///
+/// Like [`GameData::dormitory_ambience`], only up to 1000 ambience per dormitory level counts towards recovery.
+pub fn dormitory_morale_recovery(ambience: u32, level: u32) -> f32 {
+  let ambience = ambience.min(level * AMBIENCE_PER_DORMITORY_LEVEL);
+  BASE_MORALE_RECOVERY + ambience as f32 * MORALE_RECOVERY_PER_AMBIENCE
+}
@@ -0,0 +1,325 @@
+use std::ops::BitOr;
+use std::path::Path;
+
+#[cfg(feature = "async")]
+use crate::options::{DataSource, Options};
+use super::{GameData, Provenance};
+
+/// Describes which parts of a [`GameData`] should be loaded.
+/// Parts that are not selected are left empty, and the tables they are built from are not downloaded or parsed.
+///
+/// Some parts depend on others:
+/// [`GameData::recruitable_operators`] is only filled in if both `recruitment` and `operators` are selected,
+/// and [`HeadhuntingBanner::event_id`][super::HeadhuntingBanner::event_id] is only filled in
+/// if both `banners` and `events` are selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TableSelection {
+  /// Whether or not to load [`GameData::operators`].
+  pub operators: bool,
+  /// Whether or not to load the contents of each operator's [`file`][super::Operator::file], which are megabytes of text
+  /// that many applications never read. If not selected, every operator's file is left empty.
+  /// Only has an effect if `operators` is also selected.
+  pub operator_files: bool,
+  /// Whether or not to load [`GameData::alters`].
+  pub alters: bool,
+  /// Whether or not to load [`GameData::items`].
+  pub items: bool,
+  /// Whether or not to load [`GameData::buildings`] and [`GameData::furniture`].
+  pub buildings: bool,
+  /// Whether or not to load [`GameData::ranges`].
+  pub ranges: bool,
+  /// Whether or not to load [`GameData::recruitment_tags`] and [`GameData::recruitable_operators`].
+  pub recruitment: bool,
+  /// Whether or not to load [`GameData::headhunting_banners`].
+  pub banners: bool,
+  /// Whether or not to load [`GameData::events`].
+  pub events: bool,
+  /// Whether or not to load [`GameData::constants`].
+  pub constants: bool
+}
+
+impl TableSelection {
+  /// Selects every part of [`GameData`].
+  pub const ALL: Self = TableSelection {
+    operators: true,
+    operator_files: true,
+    alters: true,
+    items: true,
+    buildings: true,
+    ranges: true,
+    recruitment: true,
+    banners: true,
+    events: true,
+    constants: true
+  };
+
+  /// Selects nothing, only useful as a starting point for the builder methods.
+  pub const NONE: Self = TableSelection {
+    operators: false,
+    operator_files: false,
+    alters: false,
+    items: false,
+    buildings: false,
+    ranges: false,
+    recruitment: false,
+    banners: false,
+    events: false,
+    constants: false
+  };
+
+  pub fn operators(self, operators: bool) -> Self {
+    TableSelection { operators, ..self }
+  }
+
+  pub fn operator_files(self, operator_files: bool) -> Self {
+    TableSelection { operator_files, ..self }
+  }
+
+  pub fn alters(self, alters: bool) -> Self {
+    TableSelection { alters, ..self }
+  }
+
+  pub fn items(self, items: bool) -> Self {
+    TableSelection { items, ..self }
+  }
+
+  pub fn buildings(self, buildings: bool) -> Self {
+    TableSelection { buildings, ..self }
+  }
+
+  pub fn ranges(self, ranges: bool) -> Self {
+    TableSelection { ranges, ..self }
+  }
+
+  pub fn recruitment(self, recruitment: bool) -> Self {
+    TableSelection { recruitment, ..self }
+  }
+
+  pub fn banners(self, banners: bool) -> Self {
+    TableSelection { banners, ..self }
+  }
+
+  pub fn events(self, events: bool) -> Self {
+    TableSelection { events, ..self }
+  }
+
+  pub fn constants(self, constants: bool) -> Self {
+    TableSelection { constants, ..self }
+  }
+
+  /// Selects the parts of [`GameData`] that are built from any of the data files with the given identifiers,
+  /// along with the parts that have to be rebuilt alongside them. See [`TableSelection::with_dependencies`].
+  pub(crate) fn affected_by<'a>(identifiers: impl IntoIterator<Item = &'a str>) -> Self {
+    identifiers.into_iter().fold(TableSelection::NONE, |selection, identifier| {
+      let affected = TableSelection {
+        operators: TableSelection::NONE.operators(true).includes_table(identifier),
+        operator_files: TableSelection::NONE.operators(true).operator_files(true).includes_table(identifier),
+        alters: TableSelection::NONE.alters(true).includes_table(identifier),
+        items: TableSelection::NONE.items(true).includes_table(identifier),
+        buildings: TableSelection::NONE.buildings(true).includes_table(identifier),
+        ranges: TableSelection::NONE.ranges(true).includes_table(identifier),
+        recruitment: TableSelection::NONE.recruitment(true).includes_table(identifier),
+        banners: TableSelection::NONE.banners(true).includes_table(identifier),
+        events: TableSelection::NONE.events(true).includes_table(identifier),
+        constants: TableSelection::NONE.constants(true).includes_table(identifier)
+      };
+
+      selection | affected
+    })
+    .with_dependencies()
+  }
+
+  /// Adds the parts of [`GameData`] that cannot be built correctly without each other:
+  /// recruitment needs operators (and vice versa), banners need events (and vice versa),
+  /// and operator files need operators, since files are only loaded into operators that are loaded alongside them.
+  pub(crate) fn with_dependencies(self) -> Self {
+    let operators = self.operators || self.operator_files || self.recruitment;
+    TableSelection {
+      operators,
+      recruitment: operators,
+      banners: self.banners || self.events,
+      events: self.banners || self.events,
+      ..self
+    }
+  }
+
+  /// Returns whether or not the data file with the given identifier is needed by this selection.
+  pub(crate) fn includes_table(&self, identifier: &str) -> bool {
+    match identifier {
+      "activity_table" => self.events,
+      "building_data" => self.buildings || self.operators,
+      "char_meta_table" => self.alters,
+      "character_table" => self.operators,
+      "gacha_table" => self.recruitment || self.banners,
+      "gamedata_const" => self.constants,
+      "item_table" => self.items,
+      "range_table" => self.ranges,
+      "handbook_info_table" => self.operators && self.operator_files,
+      "battle_equip_table" | "skill_table" | "skin_table" | "uniequip_table" => self.operators,
+      _ => true
+    }
+  }
+}
+
+impl BitOr for TableSelection {
+  type Output = Self;
+
+  fn bitor(self, other: Self) -> Self {
+    TableSelection {
+      operators: self.operators || other.operators,
+      operator_files: self.operator_files || other.operator_files,
+      alters: self.alters || other.alters,
+      items: self.items || other.items,
+      buildings: self.buildings || other.buildings,
+      ranges: self.ranges || other.ranges,
+      recruitment: self.recruitment || other.recruitment,
+      banners: self.banners || other.banners,
+      events: self.events || other.events,
+      constants: self.constants || other.constants
+    }
+  }
+}
+
+impl Default for TableSelection {
+  fn default() -> Self {
+    TableSelection::ALL
+  }
+}
+
+/// A builder for loading a [`GameData`] from only some of the game's tables.
+///
+/// ```no_run
+/// # use ak_data::game_data::{GameDataBuilder, TableSelection};
+/// # use ak_data::options::Options;
+/// # async fn f() -> Result<(), ak_data::Error> {
+/// let game_data = GameDataBuilder::new()
+///   .tables(TableSelection::NONE.banners(true).events(true))
+///   .load_remote(&Options::default()).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameDataBuilder {
+  tables: TableSelection,
+  lenient: bool
+}
+
+impl GameDataBuilder {
+  /// Creates a builder which loads every table.
+  pub fn new() -> Self {
+    GameDataBuilder { tables: TableSelection::ALL, lenient: false }
+  }
+
+  /// Sets which parts of the [`GameData`] should be loaded.
+  pub fn tables(self, tables: TableSelection) -> Self {
+    GameDataBuilder { tables, ..self }
+  }
+
+  /// Sets whether or not malformed entries should be skipped instead of failing the whole load.
+  /// Skipped entries are listed in [`GameData::load_warnings`], along with the reason they were skipped.
+  ///
+  /// Defaults to `false`, in which case the first malformed entry is returned as an error.
+  pub fn lenient(self, lenient: bool) -> Self {
+    GameDataBuilder { lenient, ..self }
+  }
+
+  /// Equivalent to [`GameData::from_local`], only loading the selected tables.
+  #[cfg(feature = "async")]
+  pub async fn load_local<P: AsRef<Path>>(&self, path: P) -> Result<GameData, crate::Error> {
+    let data_files = crate::format::DataFiles::from_local(path.as_ref(), &self.tables).await?;
+    let mut game_data = data_files.into_game_data(&self.tables, None, Provenance::local(path.as_ref()), self.lenient)?;
+    game_data.data_version = crate::options::read_data_version(path.as_ref());
+    Ok(game_data)
+  }
+
+  /// Equivalent to [`GameData::from_local_sync`], only loading the selected tables.
+  pub fn load_local_sync<P: AsRef<Path>>(&self, path: P) -> Result<GameData, crate::Error> {
+    let data_files = crate::format::DataFiles::from_local_sync(path.as_ref(), &self.tables)?;
+    let mut game_data = data_files.into_game_data(&self.tables, None, Provenance::local(path.as_ref()), self.lenient)?;
+    game_data.data_version = crate::options::read_data_version(path.as_ref());
+    Ok(game_data)
+  }
+
+  /// Equivalent to [`GameData::from_remote`], only loading the selected tables.
+  #[cfg(feature = "async")]
+  pub async fn load_remote(&self, options: &Options) -> Result<GameData, crate::Error> {
+    options.request_game_data_with(&self.tables, self.lenient).await
+  }
+
+  /// Equivalent to [`GameData::from_source`], only loading the selected tables.
+  #[cfg(feature = "async")]
+  pub async fn load_source<S: DataSource>(&self, source: &S) -> Result<GameData, crate::Error> {
+    crate::options::request_game_data_from(source, &self.tables, self.lenient).await
+  }
+}
+
+impl GameData {
+  /// Replaces the selected parts of this game data with the ones from `other`,
+  /// leaving everything else (including the update time and provenance) untouched.
+  pub(crate) fn merge_selected(&mut self, other: GameData, tables: &TableSelection) {
+    if tables.operators {
+      let mut operators = other.operators;
+      if !tables.operator_files {
+        // the new operators have empty files, keep the ones that were already loaded
+        for (id, operator) in operators.iter_mut() {
+          if let Some(old_operator) = self.operators.get_mut(id) {
+            std::mem::swap(&mut operator.file, &mut old_operator.file);
+          };
+        };
+      };
+
+      self.operators = operators;
+      self.operators_by_profession = other.operators_by_profession;
+      self.operators_by_sub_profession = other.operators_by_sub_profession;
+    };
+
+    if tables.alters {
+      self.alters = other.alters;
+    };
+
+    if tables.items {
+      self.items = other.items;
+    };
+
+    if tables.buildings {
+      self.buildings = other.buildings;
+      self.furniture = other.furniture;
+    };
+
+    if tables.ranges {
+      self.ranges = other.ranges;
+    };
+
+    if tables.recruitment {
+      self.recruitment_tags = other.recruitment_tags;
+      self.operators_by_recruitment_tag = other.operators_by_recruitment_tag;
+      self.recruitable_operators = other.recruitable_operators;
+      self.recruitment_time_brackets = other.recruitment_time_brackets;
+    };
+
+    if tables.banners {
+      self.headhunting_banners = other.headhunting_banners;
+      self.beginner_banners = other.beginner_banners;
+      self.special_pools = other.special_pools;
+    };
+
+    if tables.events {
+      self.events = other.events;
+    };
+
+    if tables.constants {
+      self.constants = other.constants;
+    };
+
+    // the new game data only has warnings from the tables that were loaded again
+    self.load_warnings.retain(|load_warning| !tables.includes_table(&load_warning.table));
+    self.load_warnings.extend(other.load_warnings);
+
+    if tables.operators {
+      self.conversion_report = other.conversion_report;
+    };
+
+    self.rebuild_index();
+  }
+}
@@ -0,0 +1,120 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::ops::Deref;
+
+macro_rules! id_newtypes {
+  ($($(#[$meta:meta])* $Ident:ident;)*) => {
+    $(
+      $(#[$meta])*
+      #[repr(transparent)]
+      #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+      #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+      #[serde(transparent)]
+      pub struct $Ident(pub String);
+
+      impl $Ident {
+        #[inline]
+        pub fn new(id: impl Into<String>) -> Self {
+          $Ident(id.into())
+        }
+
+        #[inline]
+        pub fn as_str(&self) -> &str {
+          &self.0
+        }
+
+        #[inline]
+        pub fn into_inner(self) -> String {
+          self.0
+        }
+      }
+
+      impl Deref for $Ident {
+        type Target = str;
+
+        #[inline]
+        fn deref(&self) -> &str {
+          &self.0
+        }
+      }
+
+      impl AsRef<str> for $Ident {
+        #[inline]
+        fn as_ref(&self) -> &str {
+          &self.0
+        }
+      }
+
+      impl Borrow<str> for $Ident {
+        #[inline]
+        fn borrow(&self) -> &str {
+          &self.0
+        }
+      }
+
+      impl From<String> for $Ident {
+        #[inline]
+        fn from(id: String) -> Self {
+          $Ident(id)
+        }
+      }
+
+      impl From<&str> for $Ident {
+        #[inline]
+        fn from(id: &str) -> Self {
+          $Ident(id.to_owned())
+        }
+      }
+
+      impl From<$Ident> for String {
+        #[inline]
+        fn from(id: $Ident) -> Self {
+          id.0
+        }
+      }
+
+      impl PartialEq<str> for $Ident {
+        #[inline]
+        fn eq(&self, other: &str) -> bool {
+          self.0 == other
+        }
+      }
+
+      impl PartialEq<&str> for $Ident {
+        #[inline]
+        fn eq(&self, other: &&str) -> bool {
+          self.0 == *other
+        }
+      }
+
+      impl PartialEq<String> for $Ident {
+        #[inline]
+        fn eq(&self, other: &String) -> bool {
+          self.0 == *other
+        }
+      }
+
+      impl fmt::Display for $Ident {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+          f.write_str(&self.0)
+        }
+      }
+    )*
+  };
+}
+
+id_newtypes! {
+  /// The internal ID of an [`Operator`][super::Operator]. (Example: `"char_002_amiya"`)
+  OperatorId;
+  /// The internal ID of an [`Item`][super::Item]. (Example: `"30012"` for Orirock Cube)
+  ItemId;
+  /// The internal ID of an [`OperatorSkill`][super::OperatorSkill]. (Example: `"skchr_amiya_2"`)
+  SkillId;
+  /// The internal ID of an [`OperatorSkin`][super::OperatorSkin]. (Example: `"char_002_amiya#1"`)
+  SkinId;
+  /// The internal ID of an [`AttackRange`][super::AttackRange]. (Example: `"3-1"`)
+  RangeId;
+  /// The internal ID of an [`OperatorModule`][super::OperatorModule]. (Example: `"uniequip_002_amiya"`)
+  ModuleId;
+}
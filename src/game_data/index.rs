@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use super::{GameData, Item, ItemClass, ItemId, ItemUsage, MaterialTier, Operator, OperatorId};
+
+/// Lookup tables for finding operators and items in a [`GameData`] without scanning every entry.
+/// Every [`GameData`] has one built when it is loaded, see [`GameData::index`].
+///
+/// An index only reflects the [`GameData`] it was built from,
+/// it should be rebuilt whenever the game data is modified by hand.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct GameDataIndex {
+  /// Maps lowercase operator names to operator IDs.
+  /// Names shared by several operators map to the one with the lowest ID.
+  pub names: HashMap<String, OperatorId>,
+  /// Maps lowercase operator appellations to operator IDs.
+  pub appellations: HashMap<String, OperatorId>,
+  /// Maps lowercase operator display numbers to operator IDs. (Example: `"lt77"` for Mostima)
+  pub display_numbers: HashMap<String, OperatorId>,
+  /// Maps item classes to the IDs of every item of that class, sorted by ID.
+  pub items_by_class: HashMap<ItemClass, Vec<ItemId>>,
+  /// Maps raw item types to the IDs of every item of that type, sorted by ID. (Example: `"CARD_EXP"`)
+  pub items_by_type: HashMap<String, Vec<ItemId>>,
+  /// Maps material tiers to the IDs of every upgrade material of that tier, sorted by ID. See [`Item::tier`].
+  #[serde(default)]
+  pub materials_by_tier: HashMap<MaterialTier, Vec<ItemId>>,
+  /// Maps item IDs to everything that consumes that item. See [`GameData::item_usages`].
+  pub item_usages: HashMap<ItemId, Vec<ItemUsage>>,
+  /// Maps group and team IDs to the faction they belong to, based on the affiliations of every operator.
+  /// See [`GameData::get_faction_parent`].
+  #[serde(default)]
+  pub faction_parents: HashMap<String, String>,
+  /// Maps faction IDs to the IDs of every operator belonging to that faction or any of its groups and teams,
+  /// sorted by ID. See [`GameData::iter_operators_by_faction`].
+  #[serde(default)]
+  pub operators_by_faction: HashMap<String, Vec<OperatorId>>
+}
+
+impl GameDataIndex {
+  pub fn new(game_data: &GameData) -> Self {
+    let mut index = GameDataIndex::default();
+    // operators are visited in order of ID, so a name shared by several operators
+    // (such as an operator and their alter) resolves to the one with the lowest ID
+    for operator in game_data.operators.values() {
+      index.names.entry(operator.name.to_lowercase()).or_insert_with(|| operator.id.clone());
+      if let Some(appellation) = &operator.appellation {
+        index.appellations.entry(appellation.to_lowercase()).or_insert_with(|| operator.id.clone());
+      };
+
+      index.display_numbers.entry(operator.display_number.to_lowercase()).or_insert_with(|| operator.id.clone());
+
+      // team -> group -> nation, each faction's parent is the next one listed by the first operator listing it
+      let faction_ids = operator.iter_faction_ids().collect::<Vec<&str>>();
+      for pair in faction_ids.windows(2) {
+        index.faction_parents.entry(pair[0].to_owned()).or_insert_with(|| pair[1].to_owned());
+      };
+    };
+
+    index.faction_parents.retain(|faction_id, parent_id| faction_id != parent_id);
+
+    for operator in game_data.operators.values() {
+      let mut faction_ids = index.iter_faction_hierarchy(operator).map(str::to_owned).collect::<Vec<String>>();
+      faction_ids.sort_unstable();
+      faction_ids.dedup();
+      for faction_id in faction_ids {
+        index.operators_by_faction.entry(faction_id).or_default().push(operator.id.clone());
+      };
+    };
+
+    for item in game_data.items.values() {
+      index.items_by_class.entry(item.item_class).or_default().push(item.id.clone());
+      index.items_by_type.entry(item.item_type.clone()).or_default().push(item.id.clone());
+      if let Some(tier) = item.tier() {
+        index.materials_by_tier.entry(tier).or_default().push(item.id.clone());
+      };
+    };
+
+    for (item_id, item_usage) in game_data.iter_item_usages() {
+      index.item_usages.entry(ItemId::new(item_id)).or_default().push(item_usage);
+    };
+
+    index
+  }
+
+  /// Finds the ID of an operator given their in-game name, appellation or display number, ignoring case.
+  /// Names take priority over appellations, which take priority over display numbers.
+  pub fn find_operator_id(&self, query: impl AsRef<str>) -> Option<&str> {
+    let query = query.as_ref().to_lowercase();
+    self.names.get(&query)
+      .or_else(|| self.appellations.get(&query))
+      .or_else(|| self.display_numbers.get(&query))
+      .map(OperatorId::as_str)
+  }
+
+  /// Finds an operator given their in-game name, appellation or display number, ignoring case.
+  /// See [`GameDataIndex::find_operator_id`].
+  pub fn find_operator<'a>(&self, game_data: &'a GameData, query: impl AsRef<str>) -> Option<&'a Operator> {
+    self.find_operator_id(query).and_then(|id| game_data.operators.get(id))
+  }
+
+  /// Returns an iterator over every item of the given class.
+  pub fn iter_items_by_class<'a>(&'a self, game_data: &'a GameData, item_class: ItemClass)
+  -> impl Iterator<Item = &'a Item> + DoubleEndedIterator {
+    resolve_items(game_data, self.items_by_class.get(&item_class))
+  }
+
+  /// Returns an iterator over every item of the given raw item type. (Example: `"CARD_EXP"` for EXP cards)
+  pub fn iter_items_by_type<'a>(&'a self, game_data: &'a GameData, item_type: &str)
+  -> impl Iterator<Item = &'a Item> + DoubleEndedIterator {
+    resolve_items(game_data, self.items_by_type.get(item_type))
+  }
+
+  /// Returns an iterator over every upgrade material of the given kind and tier.
+  pub fn iter_materials_by_tier<'a>(&'a self, game_data: &'a GameData, tier: MaterialTier)
+  -> impl Iterator<Item = &'a Item> + DoubleEndedIterator {
+    resolve_items(game_data, self.materials_by_tier.get(&tier))
+  }
+
+  /// Returns an iterator over all operators belonging to the given faction ID.
+  /// See [`GameData::iter_operators_by_faction`].
+  pub fn iter_operators_by_faction<'a>(&'a self, game_data: &'a GameData, faction_id: &'a str, include_sub_groups: bool)
+  -> impl Iterator<Item = &'a Operator> + DoubleEndedIterator {
+    self.operators_by_faction.get(faction_id).into_iter().flatten()
+      .filter_map(|id| game_data.operators.get(id))
+      .filter(move |operator| include_sub_groups || operator.faction_id() == Some(faction_id))
+  }
+
+  /// Finds the faction ID that the given group or team ID belongs to. See [`GameData::get_faction_parent`].
+  pub fn get_faction_parent(&self, faction_id: &str) -> Option<&str> {
+    self.faction_parents.get(faction_id).map(String::as_str)
+  }
+
+  /// Returns an iterator over the faction IDs an operator belongs to. See [`GameData::iter_faction_hierarchy`].
+  pub fn iter_faction_hierarchy<'a>(&'a self, operator: &'a Operator) -> impl Iterator<Item = &'a str> + 'a {
+    // nation -> group -> team is at most three levels deep, the limit guards against cycles
+    std::iter::successors(operator.faction_id(), move |&faction_id| self.get_faction_parent(faction_id))
+      .take(3)
+      .chain(operator.iter_faction_ids())
+  }
+
+  /// Returns everything that consumes the given item.
+  pub fn item_usages(&self, item_id: &str) -> &[ItemUsage] {
+    self.item_usages.get(item_id).map_or(&[], Vec::as_slice)
+  }
+}
+
+fn resolve_items<'a>(game_data: &'a GameData, ids: Option<&'a Vec<ItemId>>)
+-> impl Iterator<Item = &'a Item> + DoubleEndedIterator {
+  ids.into_iter().flatten().filter_map(|id| game_data.items.get(id))
+}
@@ -0,0 +1,227 @@
+use std::borrow::Borrow;
+
+#[cfg(feature = "async")]
+use crate::options::Options;
+use crate::options::Region;
+use crate::Map;
+use super::{GameData, ItemId, OperatorId, SkillId};
+
+/// Region dependent text extracted from a [`GameData`], keyed by ID.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Localization {
+  /// Maps operator IDs to operator names.
+  pub operator_names: Map<OperatorId, String>,
+  /// Maps operator IDs to operator trait descriptions.
+  pub operator_descriptions: Map<OperatorId, String>,
+  /// Maps skill IDs to skill names.
+  pub skill_names: Map<SkillId, String>,
+  /// Maps item IDs to item names.
+  pub item_names: Map<ItemId, String>,
+  /// Maps item IDs to item descriptions.
+  pub item_descriptions: Map<ItemId, String>,
+  /// Maps headhunting banner IDs to banner names.
+  #[serde(default)]
+  pub banner_names: Map<String, String>
+}
+
+impl Localization {
+  pub fn new(game_data: &GameData) -> Self {
+    let mut localization = Localization::default();
+    for operator in game_data.operators.values() {
+      localization.operator_names.insert(operator.id.clone(), operator.name.clone());
+      if let Some(description) = &operator.description {
+        localization.operator_descriptions.insert(operator.id.clone(), description.clone());
+      };
+
+      for skill in operator.skills.iter() {
+        localization.skill_names.insert(skill.id.clone(), skill.name.clone());
+      };
+    };
+
+    for item in game_data.items.values() {
+      localization.item_names.insert(item.id.clone(), item.name.clone());
+      if let Some(description) = &item.description {
+        localization.item_descriptions.insert(item.id.clone(), description.clone());
+      };
+    };
+
+    for banner in game_data.headhunting_banners.iter() {
+      localization.banner_names.insert(banner.id.clone(), banner.name.clone());
+    };
+
+    for banner in game_data.beginner_banners.iter() {
+      localization.banner_names.insert(banner.id.clone(), banner.name.clone());
+    };
+
+    localization
+  }
+
+  /// Gets the name of the operator, item, skill or headhunting banner with the given ID.
+  pub fn name_of(&self, id: &str) -> Option<&str> {
+    self.operator_names.get(id)
+      .or_else(|| self.item_names.get(id))
+      .or_else(|| self.skill_names.get(id))
+      .or_else(|| self.banner_names.get(id))
+      .map(String::as_str)
+  }
+}
+
+fn name_of<'a>(localizations: &'a Map<Region, Localization>, id: &str, region: Region, fallback_region: Region) -> Option<&'a str> {
+  [region, fallback_region].into_iter()
+    .filter_map(|region| localizations.get(&region))
+    .find_map(|localization| localization.name_of(id))
+}
+
+/// Localized text from several regions, without the rest of their game data.
+///
+/// This is much smaller than keeping a [`GameData`] for every region, for applications that only need to show names.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LocalizationBundle {
+  /// The region that text falls back to when it is missing from the requested region.
+  pub fallback_region: Region,
+  /// Localized text for every region that has been added.
+  pub localizations: Map<Region, Localization>
+}
+
+impl LocalizationBundle {
+  pub fn new(fallback_region: Region) -> Self {
+    LocalizationBundle { fallback_region, localizations: Map::new() }
+  }
+
+  /// Adds (or replaces) the localized text of the given region, taken from game data of that region.
+  pub fn add_region(&mut self, region: Region, game_data: &GameData) {
+    self.localizations.insert(region, Localization::new(game_data));
+  }
+
+  /// Loads the localized text of several regions of the same repository.
+  /// The first region given is used as the fallback region, the region set in `options` is ignored.
+  ///
+  /// Returns `None` if `regions` is empty.
+  #[cfg(feature = "async")]
+  pub async fn from_remote(options: &Options, regions: &[Region]) -> Result<Option<Self>, crate::Error> {
+    let fallback_region = match regions.first() {
+      Some(&fallback_region) => fallback_region,
+      None => return Ok(None)
+    };
+
+    let mut localization_bundle = LocalizationBundle::new(fallback_region);
+    for &region in regions {
+      let game_data = options.clone().region(region).request_game_data().await?;
+      localization_bundle.add_region(region, &game_data);
+    };
+
+    Ok(Some(localization_bundle))
+  }
+
+  /// Gets the localization for the given region, if it has been added.
+  pub fn get_localization(&self, region: Region) -> Option<&Localization> {
+    self.localizations.get(&region)
+  }
+
+  /// Gets the name of the operator, item, skill or headhunting banner with the given ID in the given region,
+  /// falling back to its name in the fallback region.
+  pub fn name_of(&self, id: &str, region: Region) -> Option<&str> {
+    name_of(&self.localizations, id, region, self.fallback_region)
+  }
+}
+
+/// Game data from one canonical region, along with localized text from other regions.
+///
+/// IDs and numbers always come from the canonical region,
+/// so operators that only exist in the canonical region (such as CN-only operators)
+/// can still be named in other regions once they are released there.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MultiRegionGameData {
+  /// The region [`MultiRegionGameData::game_data`] was loaded from.
+  pub canonical_region: Region,
+  /// The game data from the canonical region.
+  pub game_data: GameData,
+  /// Localized text for every region, including the canonical region.
+  pub localizations: Map<Region, Localization>
+}
+
+impl MultiRegionGameData {
+  pub fn new(canonical_region: Region, game_data: GameData) -> Self {
+    let mut localizations = Map::new();
+    localizations.insert(canonical_region, Localization::new(&game_data));
+    MultiRegionGameData { canonical_region, game_data, localizations }
+  }
+
+  /// Adds (or replaces) the localized text of the given region, taken from game data of that region.
+  pub fn add_region(&mut self, region: Region, game_data: &GameData) {
+    self.localizations.insert(region, Localization::new(game_data));
+  }
+
+  /// Loads game data from several regions of the same repository.
+  /// The first region given is used as the canonical region, the region set in `options` is ignored.
+  ///
+  /// Returns `None` if `regions` is empty.
+  #[cfg(feature = "async")]
+  pub async fn from_remote(options: &Options, regions: &[Region]) -> Result<Option<Self>, crate::Error> {
+    let (&canonical_region, other_regions) = match regions.split_first() {
+      Some(split) => split,
+      None => return Ok(None)
+    };
+
+    let game_data = options.clone().region(canonical_region).request_game_data().await?;
+    let mut multi_region_game_data = MultiRegionGameData::new(canonical_region, game_data);
+    for &region in other_regions {
+      let game_data = options.clone().region(region).request_game_data().await?;
+      multi_region_game_data.add_region(region, &game_data);
+    };
+
+    Ok(Some(multi_region_game_data))
+  }
+
+  /// Gets the localization for the given region, if it has been loaded.
+  pub fn get_localization(&self, region: Region) -> Option<&Localization> {
+    self.localizations.get(&region)
+  }
+
+  /// Gets the name of the operator, item, skill or headhunting banner with the given ID in the given region,
+  /// falling back to its name in the canonical region.
+  pub fn name_of(&self, id: &str, region: Region) -> Option<&str> {
+    name_of(&self.localizations, id, region, self.canonical_region)
+  }
+
+  /// Takes the localized text of every region, without the game data of the canonical region.
+  pub fn into_localization_bundle(self) -> LocalizationBundle {
+    LocalizationBundle { fallback_region: self.canonical_region, localizations: self.localizations }
+  }
+
+  /// Gets an operator's name in the given region, falling back to the canonical region's name.
+  pub fn get_operator_name(&self, operator_id: &str, region: Region) -> Option<&str> {
+    self.get_localized(region, operator_id, |localization| &localization.operator_names)
+  }
+
+  /// Gets an operator's trait description in the given region, falling back to the canonical region's description.
+  pub fn get_operator_description(&self, operator_id: &str, region: Region) -> Option<&str> {
+    self.get_localized(region, operator_id, |localization| &localization.operator_descriptions)
+  }
+
+  /// Gets a skill's name in the given region, falling back to the canonical region's name.
+  pub fn get_skill_name(&self, skill_id: &str, region: Region) -> Option<&str> {
+    self.get_localized(region, skill_id, |localization| &localization.skill_names)
+  }
+
+  /// Gets an item's name in the given region, falling back to the canonical region's name.
+  pub fn get_item_name(&self, item_id: &str, region: Region) -> Option<&str> {
+    self.get_localized(region, item_id, |localization| &localization.item_names)
+  }
+
+  /// Gets an item's description in the given region, falling back to the canonical region's description.
+  pub fn get_item_description(&self, item_id: &str, region: Region) -> Option<&str> {
+    self.get_localized(region, item_id, |localization| &localization.item_descriptions)
+  }
+
+  fn get_localized<'a, K, F>(&'a self, region: Region, id: &str, f: F) -> Option<&'a str>
+  where K: Borrow<str> + Ord + 'a, F: Fn(&'a Localization) -> &'a Map<K, String> {
+    [region, self.canonical_region].into_iter()
+      .filter_map(|region| self.localizations.get(&region))
+      .find_map(|localization| f(localization).get(id))
+      .map(String::as_str)
+  }
+}
@@ -0,0 +1,410 @@
+use mint::Point2;
+
+use std::collections::HashMap;
+use std::mem::size_of;
+
+use crate::{Map, Set};
+use super::{
+  AlterGroup, AttackRange, BannerRules, BeginnerBanner, Building, BuildingType, BuildingUpgrade, ConversionReport,
+  Event, Furniture, GameConstants, GameData, GameDataIndex, HeadhuntingBanner, Item, ItemClass, ItemId, ItemUsage,
+  LoadWarning, MaterialTier, ModuleId, Operator, OperatorBaseSkill, OperatorBaseSkillPhase, OperatorFile,
+  OperatorFileEntry, OperatorFileUnlock, OperatorId, OperatorModule, OperatorModuleMission, OperatorModuleStage,
+  OperatorPotential, OperatorPromotion, OperatorPromotions, OperatorSkill, OperatorSkillLevel,
+  OperatorSkillLevelUpgrade, OperatorSkillMastery, OperatorSkin, OperatorTalent, OperatorTalentPhase,
+  OperatorTraitPhase, Profession, Provenance, RangeId, RecruitmentTimeBracket, SkillId, SkinId, SkipReason,
+  SpecialRecruitmentDuration, SpecialRecruitmentPool, SubProfession
+};
+
+/// The approximate number of bytes used by each part of a [`GameData`], see [`GameData::memory_footprint`].
+///
+/// Each part counts everything its collections allocate, not including any allocator overhead,
+/// so sizes should be treated as a rough lower bound rather than an exact measurement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MemoryFootprint {
+  /// [`GameData::operators`], [`GameData::operators_by_profession`] and [`GameData::operators_by_sub_profession`].
+  pub operators: usize,
+  /// The part of `operators` used by every operator's [`file`][Operator::file].
+  pub operator_files: usize,
+  /// The part of `operators` used by every operator's [`skins`][Operator::skins].
+  pub operator_skins: usize,
+  /// [`GameData::alters`].
+  pub alters: usize,
+  /// [`GameData::items`].
+  pub items: usize,
+  /// [`GameData::buildings`] and [`GameData::furniture`].
+  pub buildings: usize,
+  /// [`GameData::ranges`].
+  pub ranges: usize,
+  /// [`GameData::recruitment_tags`], [`GameData::operators_by_recruitment_tag`], [`GameData::recruitable_operators`]
+  /// and [`GameData::recruitment_time_brackets`].
+  pub recruitment: usize,
+  /// [`GameData::headhunting_banners`], [`GameData::beginner_banners`] and [`GameData::special_pools`].
+  pub banners: usize,
+  /// [`GameData::events`].
+  pub events: usize,
+  /// [`GameData::constants`].
+  pub constants: usize,
+  /// [`GameData::index`].
+  pub index: usize,
+  /// Everything else, such as [`GameData::provenance`], along with the [`GameData`] struct itself.
+  pub other: usize
+}
+
+impl MemoryFootprint {
+  /// The approximate number of bytes used by the whole [`GameData`].
+  pub fn total(&self) -> usize {
+    self.operators + self.alters + self.items + self.buildings + self.ranges +
+    self.recruitment + self.banners + self.events + self.constants + self.index + self.other
+  }
+}
+
+/// Describes which heavy fields [`GameData::shrink`] should drop.
+/// Dropped fields are left empty, the same way they would be if they were never loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ShrinkOptions {
+  /// Whether or not to drop the description text of operators (including their recruitment card text),
+  /// potentials, traits, talents, skill levels, modules, items, buildings and furniture,
+  /// along with item and furniture usage text, and item obtain text.
+  pub descriptions: bool,
+  /// Whether or not to drop the contents of every operator's [`file`][Operator::file],
+  /// as if [`TableSelection::operator_files`][super::TableSelection::operator_files] was not selected.
+  pub operator_files: bool,
+  /// Whether or not to drop every operator's [`skins`][Operator::skins].
+  /// Note that this leaves [`Operator::iter_default_skins`] empty as well.
+  pub skins: bool
+}
+
+impl ShrinkOptions {
+  /// Drops every heavy field.
+  pub const ALL: Self = ShrinkOptions {
+    descriptions: true,
+    operator_files: true,
+    skins: true
+  };
+
+  /// Drops nothing, only useful as a starting point for the builder methods.
+  pub const NONE: Self = ShrinkOptions {
+    descriptions: false,
+    operator_files: false,
+    skins: false
+  };
+
+  pub fn descriptions(self, descriptions: bool) -> Self {
+    ShrinkOptions { descriptions, ..self }
+  }
+
+  pub fn operator_files(self, operator_files: bool) -> Self {
+    ShrinkOptions { operator_files, ..self }
+  }
+
+  pub fn skins(self, skins: bool) -> Self {
+    ShrinkOptions { skins, ..self }
+  }
+}
+
+impl Default for ShrinkOptions {
+  fn default() -> Self {
+    ShrinkOptions::ALL
+  }
+}
+
+impl GameData {
+  /// Estimates the number of bytes used by each part of this game data. See [`MemoryFootprint`].
+  pub fn memory_footprint(&self) -> MemoryFootprint {
+    let (operator_files, operator_skins) = self.operators.values()
+      .fold((0, 0), |(files, skins), operator| {
+        (files + operator.file.heap_size(), skins + operator.skins.heap_size())
+      });
+
+    MemoryFootprint {
+      operators: self.operators.heap_size() + self.operators_by_profession.heap_size() +
+        self.operators_by_sub_profession.heap_size(),
+      operator_files,
+      operator_skins,
+      alters: self.alters.heap_size(),
+      items: self.items.heap_size(),
+      buildings: self.buildings.heap_size() + self.furniture.heap_size(),
+      ranges: self.ranges.heap_size(),
+      recruitment: self.recruitment_tags.heap_size() + self.operators_by_recruitment_tag.heap_size() +
+        self.recruitable_operators.heap_size() + self.recruitment_time_brackets.heap_size(),
+      banners: self.headhunting_banners.heap_size() + self.beginner_banners.heap_size() +
+        self.special_pools.heap_size(),
+      events: self.events.heap_size(),
+      constants: self.constants.heap_size(),
+      index: self.index.heap_size(),
+      other: size_of::<GameData>() + self.provenance.heap_size() + self.data_version.heap_size() +
+        self.load_warnings.heap_size() + self.conversion_report.heap_size()
+    }
+  }
+
+  /// Drops the selected heavy fields of this game data in place, to reduce its memory usage.
+  /// See [`ShrinkOptions`] and [`GameData::memory_footprint`].
+  pub fn shrink(&mut self, options: ShrinkOptions) {
+    for operator in self.operators.values_mut() {
+      if options.descriptions {
+        operator.shrink_descriptions();
+      };
+
+      if options.operator_files {
+        *operator.file = OperatorFile::empty(&operator.id);
+      };
+
+      if options.skins {
+        operator.skins = Map::new();
+      };
+    };
+
+    if options.descriptions {
+      for item in self.items.values_mut() {
+        item.description = None;
+        item.usage = None;
+        item.obtain = None;
+      };
+
+      for building in self.buildings.values_mut() {
+        building.description = None;
+      };
+
+      for furniture in self.furniture.values_mut() {
+        furniture.description = None;
+        furniture.usage = None;
+      };
+
+      for banner in self.beginner_banners.iter_mut() {
+        banner.description = None;
+      };
+    };
+  }
+}
+
+impl Operator {
+  fn shrink_descriptions(&mut self) {
+    self.description = None;
+    self.item_usage = None;
+    self.item_description = None;
+    for trait_phase in self.traits.iter_mut() {
+      trait_phase.description = String::new();
+    };
+
+    for potential in self.potential.iter_mut() {
+      potential.description = String::new();
+    };
+
+    for skill in self.skills.iter_mut() {
+      for level in skill.levels.iter_mut() {
+        level.description = None;
+      };
+
+      for mastery in skill.mastery.iter_mut().flatten() {
+        mastery.level.description = None;
+      };
+    };
+
+    for talent_phase in self.talents.iter_mut().flat_map(|talent| talent.phases.iter_mut()) {
+      talent_phase.description = String::new();
+    };
+
+    for base_skill_phase in self.base_skills.iter_mut().flat_map(|base_skill| base_skill.phases.iter_mut()) {
+      base_skill_phase.description = None;
+    };
+
+    for module in self.modules.iter_mut() {
+      module.description = String::new();
+      for stage in module.stages.iter_mut() {
+        stage.trait_addition = None;
+        stage.trait_override = None;
+      };
+    };
+  }
+}
+
+/// Approximates the number of bytes a value allocates on the heap, not including the value itself.
+pub(crate) trait HeapSize {
+  fn heap_size(&self) -> usize;
+}
+
+fn total_size<T: HeapSize>(value: &T) -> usize {
+  size_of::<T>() + value.heap_size()
+}
+
+macro_rules! impl_heap_size {
+  ($($Type:ty { $($field:tt),* $(,)? }),* $(,)?) => {
+    $(
+      impl HeapSize for $Type {
+        fn heap_size(&self) -> usize {
+          0 $(+ self.$field.heap_size())*
+        }
+      }
+    )*
+  };
+}
+
+macro_rules! impl_heap_size_none {
+  ($($Type:ty),* $(,)?) => {
+    $(
+      impl HeapSize for $Type {
+        #[inline]
+        fn heap_size(&self) -> usize {
+          0
+        }
+      }
+    )*
+  };
+}
+
+impl_heap_size_none!(
+  u32, i32, f32, Point2<i32>,
+  Profession, BuildingType, ItemClass, MaterialTier, RecruitmentTimeBracket
+);
+
+impl_heap_size!(
+  Provenance { host, repository, branch, commit },
+  LoadWarning { table, id, message },
+  ConversionReport { skipped },
+  OperatorId { 0 }, ItemId { 0 }, SkillId { 0 }, SkinId { 0 }, RangeId { 0 }, ModuleId { 0 },
+  Operator {
+    id, name, nation_id, group_id, team_id, display_number, sort_index, appellation, description, traits,
+    item_usage, item_description, recruitment_tags, sub_profession, promotions, potential_item_id, potential, skills,
+    skill_level_upgrades, talents, modules, skins, base_skills, file
+  },
+  OperatorPromotions { none, elite1, elite2 },
+  OperatorPromotion { attack_range_id, upgrade_cost, skin_id },
+  OperatorPotential { description },
+  OperatorSkill { id, name, icon_id, prefab_key, levels, mastery },
+  OperatorSkillLevel { description, attack_range_id, prefab_key },
+  OperatorSkillLevelUpgrade { upgrade_cost },
+  OperatorSkillMastery { upgrade_cost, level },
+  OperatorTraitPhase { description, attack_range_id, effects },
+  OperatorTalent { phases },
+  OperatorTalentPhase { name, description, prefab_key, attack_range_id, effects },
+  OperatorModule { id, name, description, type_name, type_variant, icon_id, upgrade_cost, missions, stages },
+  OperatorModuleStage { trait_addition, trait_override },
+  OperatorModuleMission { description },
+  OperatorBaseSkill { phases },
+  OperatorBaseSkillPhase { name, description },
+  OperatorSkin {
+    id, name, model_id, model_name, illustration_id, illustration_live_id, avatar_id,
+    portrait_id, illustrator, group, dialog, usage, description, obtain
+  },
+  OperatorFile { operator_id, illustrator_name, entries },
+  OperatorFileEntry { title, text, unlock_condition },
+  AlterGroup { id, members },
+  Item { id, name, description, usage, obtain, item_type, icon_id },
+  Building { name, description, category, upgrades },
+  BuildingUpgrade { unlock_condition, construction_cost },
+  Furniture { id, name, description, usage, theme_id },
+  AttackRange { points },
+  HeadhuntingBanner { id, name, summary, item_id, event_id, rules },
+  BeginnerBanner { id, name, description },
+  SpecialRecruitmentPool { id, durations },
+  SpecialRecruitmentDuration { item_cost },
+  BannerRules { limited_operator_ids },
+  Event { id, name, activity_type },
+  GameConstants { max_levels, exp_costs, lmd_costs, promotion_lmd_costs },
+  GameDataIndex {
+    names, appellations, display_numbers, items_by_class, items_by_type, materials_by_tier, item_usages,
+    faction_parents, operators_by_faction
+  }
+);
+
+impl HeapSize for String {
+  #[inline]
+  fn heap_size(&self) -> usize {
+    self.capacity()
+  }
+}
+
+impl<A: HeapSize, B: HeapSize> HeapSize for (A, B) {
+  #[inline]
+  fn heap_size(&self) -> usize {
+    self.0.heap_size() + self.1.heap_size()
+  }
+}
+
+impl<T: HeapSize> HeapSize for Option<T> {
+  #[inline]
+  fn heap_size(&self) -> usize {
+    self.as_ref().map_or(0, T::heap_size)
+  }
+}
+
+impl<T: HeapSize> HeapSize for Box<T> {
+  #[inline]
+  fn heap_size(&self) -> usize {
+    total_size(&**self)
+  }
+}
+
+impl<T: HeapSize, const N: usize> HeapSize for [T; N] {
+  fn heap_size(&self) -> usize {
+    self.iter().map(T::heap_size).sum()
+  }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+  fn heap_size(&self) -> usize {
+    self.capacity() * size_of::<T>() + self.iter().map(T::heap_size).sum::<usize>()
+  }
+}
+
+impl<T: HeapSize> HeapSize for Set<T> {
+  fn heap_size(&self) -> usize {
+    self.iter().map(total_size).sum()
+  }
+}
+
+impl<K: HeapSize, V: HeapSize> HeapSize for Map<K, V> {
+  fn heap_size(&self) -> usize {
+    self.iter().map(|(key, value)| total_size(key) + total_size(value)).sum()
+  }
+}
+
+impl<K: HeapSize, V: HeapSize> HeapSize for HashMap<K, V> {
+  fn heap_size(&self) -> usize {
+    let entries = self.iter().map(|(key, value)| key.heap_size() + value.heap_size());
+    self.capacity() * size_of::<(K, V)>() + entries.sum::<usize>()
+  }
+}
+
+impl HeapSize for SubProfession {
+  fn heap_size(&self) -> usize {
+    match self {
+      SubProfession::Other(game_id) => game_id.heap_size(),
+      _ => 0
+    }
+  }
+}
+
+impl HeapSize for SkipReason {
+  fn heap_size(&self) -> usize {
+    match self {
+      SkipReason::InvalidSkill(skill_id) => skill_id.heap_size(),
+      SkipReason::UnknownValue { field, value } => field.heap_size() + value.heap_size(),
+      SkipReason::Malformed(message) => message.heap_size(),
+      _ => 0
+    }
+  }
+}
+
+impl HeapSize for OperatorFileUnlock {
+  fn heap_size(&self) -> usize {
+    match self {
+      OperatorFileUnlock::OperatorUnlocked(operator_id) => operator_id.heap_size(),
+      _ => 0
+    }
+  }
+}
+
+impl HeapSize for ItemUsage {
+  fn heap_size(&self) -> usize {
+    match self {
+      ItemUsage::Promotion { operator_id, .. } => operator_id.heap_size(),
+      ItemUsage::SkillLevel { operator_id, .. } => operator_id.heap_size(),
+      ItemUsage::SkillMastery { operator_id, skill_id, .. } => operator_id.heap_size() + skill_id.heap_size(),
+      ItemUsage::Module { operator_id, module_id, .. } => operator_id.heap_size() + module_id.heap_size(),
+      ItemUsage::BuildingUpgrade { .. } => 0
+    }
+  }
+}
@@ -0,0 +1,145 @@
+use std::ops::{Bound, RangeBounds};
+
+use super::{GameData, Operator, Position, Profession, SubProfession};
+
+/// Describes how the results of an [`OperatorQuery`] should be ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperatorSort {
+  /// Sorted by operator ID, the same order as [`GameData::operators`].
+  Id,
+  /// Sorted alphabetically by name.
+  Name,
+  /// Sorted from lowest to highest rarity, then by name.
+  RarityAscending,
+  /// Sorted from highest to lowest rarity, then by name.
+  RarityDescending
+}
+
+impl Default for OperatorSort {
+  fn default() -> Self {
+    OperatorSort::Id
+  }
+}
+
+/// A builder for filtering and sorting the operators in a [`GameData`].
+/// Created with [`GameData::query_operators`].
+///
+/// ```no_run
+/// # use ak_data::game_data::{GameData, OperatorSort, Profession};
+/// # fn f(game_data: &GameData) {
+/// let six_star_guards = game_data.query_operators()
+///   .rarity(6..=6)
+///   .profession(Profession::Guard)
+///   .sort_by(OperatorSort::Name)
+///   .iter();
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct OperatorQuery<'a> {
+  game_data: &'a GameData,
+  rarity: (Bound<u8>, Bound<u8>),
+  profession: Option<Profession>,
+  sub_profession: Option<SubProfession>,
+  position: Option<Position>,
+  faction_id: Option<&'a str>,
+  recruitable: Option<bool>,
+  limited: Option<bool>,
+  sort: OperatorSort
+}
+
+impl<'a> OperatorQuery<'a> {
+  pub fn new(game_data: &'a GameData) -> Self {
+    OperatorQuery {
+      game_data,
+      rarity: (Bound::Unbounded, Bound::Unbounded),
+      profession: None,
+      sub_profession: None,
+      position: None,
+      faction_id: None,
+      recruitable: None,
+      limited: None,
+      sort: OperatorSort::Id
+    }
+  }
+
+  /// Only include operators whose rarity (from 1 to 6) falls within the given range.
+  pub fn rarity(self, rarity: impl RangeBounds<u8>) -> Self {
+    let rarity = (rarity.start_bound().cloned(), rarity.end_bound().cloned());
+    OperatorQuery { rarity, ..self }
+  }
+
+  /// Only include operators with the given profession.
+  pub fn profession(self, profession: Profession) -> Self {
+    OperatorQuery { profession: Some(profession), ..self }
+  }
+
+  /// Only include operators with the given sub-profession.
+  pub fn sub_profession(self, sub_profession: SubProfession) -> Self {
+    OperatorQuery { sub_profession: Some(sub_profession), ..self }
+  }
+
+  /// Only include operators with the given position.
+  pub fn position(self, position: Position) -> Self {
+    OperatorQuery { position: Some(position), ..self }
+  }
+
+  /// Only include operators belonging to the given faction, including its sub-groups.
+  /// See [`GameData::iter_operators_by_faction`].
+  pub fn faction(self, faction_id: &'a str) -> Self {
+    OperatorQuery { faction_id: Some(faction_id), ..self }
+  }
+
+  /// Only include operators that can (or cannot) be obtained from recruitment.
+  /// See [`GameData::recruitable_operators`].
+  pub fn recruitable(self, recruitable: bool) -> Self {
+    OperatorQuery { recruitable: Some(recruitable), ..self }
+  }
+
+  /// Only include operators that are (or are not) limited operators.
+  /// See [`GameData::is_operator_limited`].
+  pub fn limited(self, limited: bool) -> Self {
+    OperatorQuery { limited: Some(limited), ..self }
+  }
+
+  /// Sets the order the resulting operators should be returned in.
+  pub fn sort_by(self, sort: OperatorSort) -> Self {
+    OperatorQuery { sort, ..self }
+  }
+
+  /// Returns whether or not the given operator passes every filter of this query.
+  pub fn matches(&self, operator: &Operator) -> bool {
+    let game_data = self.game_data;
+    self.rarity.contains(&operator.rarity.get())
+      && self.profession.map_or(true, |profession| operator.profession == profession)
+      && self.sub_profession.as_ref().map_or(true, |sub_profession| &operator.sub_profession == sub_profession)
+      && self.position.map_or(true, |position| operator.position == position)
+      && self.faction_id.map_or(true, |faction_id| {
+        game_data.iter_faction_hierarchy(operator).any(|id| id == faction_id)
+      })
+      && self.recruitable.map_or(true, |recruitable| {
+        game_data.is_operator_recruitable(&operator.id) == recruitable
+      })
+      && self.limited.map_or(true, |limited| {
+        game_data.is_operator_limited(&operator.id) == limited
+      })
+  }
+
+  /// Runs this query, returning an iterator over every matching operator.
+  pub fn iter(&self) -> impl Iterator<Item = &'a Operator> + DoubleEndedIterator + ExactSizeIterator {
+    let mut operators = self.game_data.operators.values()
+      .filter(|operator| self.matches(operator))
+      .collect::<Vec<&'a Operator>>();
+    match self.sort {
+      OperatorSort::Id => (),
+      OperatorSort::Name => operators.sort_by(|a, b| a.name.cmp(&b.name)),
+      OperatorSort::RarityAscending => operators.sort_by(|a, b| {
+        a.rarity.cmp(&b.rarity).then_with(|| a.name.cmp(&b.name))
+      }),
+      OperatorSort::RarityDescending => operators.sort_by(|a, b| {
+        b.rarity.cmp(&a.rarity).then_with(|| a.name.cmp(&b.name))
+      })
+    };
+
+    operators.into_iter()
+  }
+}
@@ -0,0 +1,171 @@
+use std::fmt;
+
+use crate::Map;
+#[cfg(feature = "async")]
+use crate::options::Options;
+use super::{GameData, ItemId, OperatorId, SkinId};
+
+/// Describes what changed between two versions of [`GameData`], see [`GameData::patch_from_remote_with_report`].
+///
+/// Every list is sorted in the same order as the map it comes from, or from oldest to newest for banners and events.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct UpdateReport {
+  /// Operators that did not exist before.
+  pub new_operators: Vec<OperatorId>,
+  /// Operators that no longer exist.
+  pub removed_operators: Vec<OperatorId>,
+  /// Operators that existed before, but have changed in any way.
+  pub changed_operators: Vec<OperatorId>,
+  /// Operators that existed before, and whose skills or skill upgrades have changed.
+  /// Every operator in this list is also in [`UpdateReport::changed_operators`].
+  pub changed_skills: Vec<OperatorId>,
+  /// Skins that did not exist before, along with the operator they belong to.
+  pub new_skins: Vec<(OperatorId, SkinId)>,
+  /// Items that did not exist before.
+  pub new_items: Vec<ItemId>,
+  /// The IDs of headhunting banners that did not exist before.
+  pub new_banners: Vec<String>,
+  /// The IDs of events that did not exist before.
+  pub new_events: Vec<String>
+}
+
+impl UpdateReport {
+  /// Compares two versions of game data.
+  pub fn between(old: &GameData, new: &GameData) -> Self {
+    let mut report = UpdateReport::default();
+    for (id, operator) in new.operators.iter() {
+      match old.operators.get(id) {
+        None => report.new_operators.push(id.clone()),
+        Some(old_operator) if old_operator != operator => {
+          report.changed_operators.push(id.clone());
+          if old_operator.skills != operator.skills || old_operator.skill_level_upgrades != operator.skill_level_upgrades {
+            report.changed_skills.push(id.clone());
+          };
+        },
+        Some(..) => ()
+      };
+
+      for skin_id in operator.skins.keys() {
+        let existed = old.operators.get(id).is_some_and(|old_operator| old_operator.skins.contains_key(skin_id));
+        if !existed {
+          report.new_skins.push((id.clone(), skin_id.clone()));
+        };
+      };
+    };
+
+    report.removed_operators = old.operators.keys()
+      .filter(|&id| !new.operators.contains_key(id))
+      .cloned().collect();
+    report.new_items = new.items.keys()
+      .filter(|&id| !old.items.contains_key(id))
+      .cloned().collect();
+    report.new_banners = new.headhunting_banners.iter()
+      .filter(|banner| !old.headhunting_banners.iter().any(|old_banner| old_banner.id == banner.id))
+      .map(|banner| banner.id.clone()).collect();
+    report.new_events = new.events.iter()
+      .filter(|event| !old.events.iter().any(|old_event| old_event.id == event.id))
+      .map(|event| event.id.clone()).collect();
+    report
+  }
+
+  /// Returns true if nothing in this report has changed.
+  pub fn is_empty(&self) -> bool {
+    self.new_operators.is_empty() &&
+    self.removed_operators.is_empty() &&
+    self.changed_operators.is_empty() &&
+    self.new_skins.is_empty() &&
+    self.new_items.is_empty() &&
+    self.new_banners.is_empty() &&
+    self.new_events.is_empty()
+  }
+}
+
+impl GameData {
+  /// Equivalent to [`GameData::patch_from_remote`], describing what changed instead of returning the old game data.
+  /// Returns `None` if this game data was already up to date.
+  #[cfg(feature = "async")]
+  pub async fn patch_from_remote_with_report(&mut self, options: &Options) -> Result<Option<UpdateReport>, crate::Error> {
+    let old_game_data = options.patch_game_data(self).await?;
+    Ok(old_game_data.map(|old_game_data| UpdateReport::between(&old_game_data, self)))
+  }
+}
+
+/// Lists every character that was not converted into an [`Operator`][super::Operator] while loading a [`GameData`],
+/// along with the reason it was skipped. See [`GameData::conversion_report`].
+///
+/// Useful for checking that nothing unexpected went missing after a game update.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ConversionReport {
+  /// The IDs of every skipped character, sorted by ID.
+  pub skipped: Map<OperatorId, SkipReason>
+}
+
+impl ConversionReport {
+  /// Returns an iterator over every skipped character whose reason is not expected, see [`SkipReason::is_expected`].
+  pub fn iter_unexpected(&self) -> impl Iterator<Item = (&OperatorId, &SkipReason)> + DoubleEndedIterator {
+    self.skipped.iter().filter(|(_, reason)| !reason.is_expected())
+  }
+
+  /// Returns true if no characters were skipped for unexpected reasons.
+  pub fn is_clean(&self) -> bool {
+    self.iter_unexpected().next().is_none()
+  }
+}
+
+/// Why a character was not converted into an [`Operator`][super::Operator], see [`ConversionReport`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum SkipReason {
+  /// The character cannot be obtained by players, such as tokens, traps and enemy-only characters.
+  Unobtainable,
+  /// The character is not an operator, such as a token or trap that is not marked as unobtainable.
+  NotAnOperator,
+  /// The character has no display number.
+  MissingDisplayNumber,
+  /// The character has no position on the battlefield.
+  MissingPosition,
+  /// The character has no promotions.
+  MissingPromotions,
+  /// The character has no entry in the skin table.
+  MissingSkins,
+  /// The character has no entry in the handbook table.
+  MissingHandbookEntry,
+  /// One of the character's skills (given by ID) is missing from the skill table, or could not be converted.
+  InvalidSkill(String),
+  /// One of the character's talents could not be converted.
+  InvalidTalent,
+  /// One of the character's fields (given by name) has a value (given as it appears in the game files)
+  /// that was added to the game after this crate was last updated.
+  UnknownValue { field: String, value: String },
+  /// The character's entry could not be parsed at all. Only possible when loading leniently,
+  /// see [`GameDataBuilder::lenient`][super::GameDataBuilder::lenient].
+  Malformed(String)
+}
+
+impl SkipReason {
+  /// Returns true if this character is always skipped, rather than being skipped because of a problem with the game data.
+  pub fn is_expected(&self) -> bool {
+    matches!(self, SkipReason::Unobtainable | SkipReason::NotAnOperator)
+  }
+}
+
+impl fmt::Display for SkipReason {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      SkipReason::Unobtainable => f.write_str("character is unobtainable"),
+      SkipReason::NotAnOperator => f.write_str("character is not an operator"),
+      SkipReason::MissingDisplayNumber => f.write_str("missing display number"),
+      SkipReason::MissingPosition => f.write_str("missing position"),
+      SkipReason::MissingPromotions => f.write_str("missing promotions"),
+      SkipReason::MissingSkins => f.write_str("missing skin table entry"),
+      SkipReason::MissingHandbookEntry => f.write_str("missing handbook entry"),
+      SkipReason::InvalidSkill(skill_id) => write!(f, "invalid skill {skill_id}"),
+      SkipReason::InvalidTalent => f.write_str("invalid talent"),
+      SkipReason::UnknownValue { field, value } => write!(f, "unknown {field} {value}"),
+      SkipReason::Malformed(message) => write!(f, "malformed entry: {message}")
+    }
+  }
+}
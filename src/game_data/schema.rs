@@ -0,0 +1,69 @@
+use std::path::Path;
+
+use crate::Map;
+use super::GameData;
+
+/// How closely each data file matches the fields this crate declares for it, see [`GameData::check_schema`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SchemaReport {
+  /// The report for each data file, keyed by its identifier. (Example: `"character_table"`)
+  pub tables: Map<String, TableSchemaReport>
+}
+
+impl SchemaReport {
+  /// Returns true if every data file could be parsed, ignoring unknown fields.
+  pub fn is_compatible(&self) -> bool {
+    self.tables.values().all(|table| table.error.is_none())
+  }
+
+  /// Returns an iterator over the identifier and error of every data file that could not be parsed.
+  pub fn iter_errors(&self) -> impl Iterator<Item = (&str, &str)> + DoubleEndedIterator {
+    self.tables.iter().filter_map(|(identifier, table)| Some((identifier.as_str(), table.error.as_deref()?)))
+  }
+}
+
+/// How closely a single data file matches the fields this crate declares for it, see [`SchemaReport`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TableSchemaReport {
+  /// Fields present in the data file that this crate does not declare, keyed by field name.
+  ///
+  /// This crate only declares the fields it uses, so this is never empty in practice.
+  /// Comparing it against the report of an earlier version of the game data shows which fields were added.
+  pub unknown_fields: Map<String, UnknownField>,
+  /// If the data file could not be parsed, the first field that was missing or had an unexpected type, and its path.
+  pub error: Option<String>
+}
+
+/// A field found in a data file that this crate does not declare, see [`TableSchemaReport::unknown_fields`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct UnknownField {
+  /// The number of times this field appears in the data file.
+  pub occurrences: usize,
+  /// The path to the first occurrence of this field.
+  pub example_path: String
+}
+
+impl GameData {
+  /// Checks every data file in the given path against the fields this crate declares for it,
+  /// reporting unknown fields and parsing errors for each data file instead of stopping at the first error.
+  /// Useful for getting an early warning when the shape of the game data changes.
+  ///
+  /// Note that the provided path should go to the `gamedata` folder, not the root folder of the repository.
+  pub fn check_schema<P: AsRef<Path>>(path: P) -> Result<SchemaReport, crate::Error> {
+    crate::format::DataFiles::check_schema(|location| {
+      std::fs::read(path.as_ref().join(location)).map_err(crate::Error::from)
+    })
+  }
+
+  /// Returns a JSON Schema describing [`GameData`] as it is serialized by this crate,
+  /// for validating or generating bindings for serialized game data in other languages.
+  ///
+  /// The returned schema can be written out as a JSON document with `serde_json`.
+  #[cfg(feature = "schemars")]
+  pub fn json_schema() -> schemars::schema::RootSchema {
+    schemars::schema_for!(GameData)
+  }
+}
@@ -0,0 +1,52 @@
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+/// Results scoring below this are not considered matches.
+pub(super) const SEARCH_THRESHOLD: f32 = 0.6;
+
+/// Normalizes a name for comparison: accents are stripped, letters are lowercased,
+/// a leading "the" is removed, and anything that isn't a letter or digit is discarded.
+/// (Example: `"Pozëmka"` becomes `"pozemka"`, `"The Lord of the Rings"` becomes `"lordoftherings"`)
+pub fn normalize_name(name: &str) -> String {
+  let name = name.nfkd()
+    .filter(|&c| !is_combining_mark(c))
+    .flat_map(char::to_lowercase)
+    .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+    .collect::<String>();
+  let mut words = name.split_whitespace().peekable();
+  if words.peek() == Some(&"the") { words.next(); };
+  words.collect::<String>()
+}
+
+/// Scores how closely a normalized query matches a normalized name, from `0.0` (no match) to `1.0` (exact match).
+pub(super) fn score_normalized(query: &str, name: &str) -> f32 {
+  if query.is_empty() || name.is_empty() {
+    0.0
+  } else if query == name {
+    1.0
+  } else if name.starts_with(query) {
+    0.9
+  } else if name.contains(query) {
+    0.8
+  } else {
+    let query = query.chars().collect::<Vec<char>>();
+    let name = name.chars().collect::<Vec<char>>();
+    let max_len = query.len().max(name.len());
+    1.0 - levenshtein(&query, &name) as f32 / max_len as f32
+  }
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+  let mut row = (0..=b.len()).collect::<Vec<usize>>();
+  for (i, &ca) in a.iter().enumerate() {
+    let mut previous = row[0];
+    row[0] = i + 1;
+    for (j, &cb) in b.iter().enumerate() {
+      let substitution = previous + (ca != cb) as usize;
+      previous = row[j + 1];
+      row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+    };
+  };
+
+  row[b.len()]
+}
@@ -0,0 +1,75 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+#[cfg(feature = "async")]
+use crate::options::Options;
+use super::GameData;
+
+/// A [`GameData`] that can be cloned cheaply, for handing the same game data to many tasks.
+/// Cloning only increments a reference count, and every clone reads from the same game data.
+///
+/// Dereferences to [`GameData`] for reading. Modifying it (for example with [`SharedGameData::patch_from_remote`])
+/// only affects this handle: the game data is copied first if any other clones of it still exist.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct SharedGameData(Arc<GameData>);
+
+impl SharedGameData {
+  pub fn new(game_data: GameData) -> Self {
+    SharedGameData(Arc::new(game_data))
+  }
+
+  /// Gets mutable access to the game data, copying it first if any other clones of this handle exist.
+  pub fn make_mut(&mut self) -> &mut GameData {
+    Arc::make_mut(&mut self.0)
+  }
+
+  /// Takes the game data out of this handle, copying it if any other clones of this handle exist.
+  pub fn into_inner(self) -> GameData {
+    Arc::try_unwrap(self.0).unwrap_or_else(|game_data| GameData::clone(&game_data))
+  }
+
+  /// Returns true if both handles read from the same game data.
+  pub fn ptr_eq(&self, other: &Self) -> bool {
+    Arc::ptr_eq(&self.0, &other.0)
+  }
+
+  /// Equivalent to [`GameData::patch_from_remote`]. Other clones of this handle keep the old game data.
+  /// Replaces the game data in this handle and returns the old one if it was out of date.
+  #[cfg(feature = "async")]
+  pub async fn patch_from_remote(&mut self, options: &Options) -> Result<Option<SharedGameData>, crate::Error> {
+    let new_game_data = options.get_patched_game_data(&self.0).await?;
+    Ok(new_game_data.map(|new_game_data| std::mem::replace(self, SharedGameData::new(new_game_data))))
+  }
+}
+
+impl Deref for SharedGameData {
+  type Target = GameData;
+
+  #[inline]
+  fn deref(&self) -> &GameData {
+    &self.0
+  }
+}
+
+impl AsRef<GameData> for SharedGameData {
+  #[inline]
+  fn as_ref(&self) -> &GameData {
+    &self.0
+  }
+}
+
+impl From<GameData> for SharedGameData {
+  #[inline]
+  fn from(game_data: GameData) -> Self {
+    SharedGameData::new(game_data)
+  }
+}
+
+impl From<Arc<GameData>> for SharedGameData {
+  #[inline]
+  fn from(game_data: Arc<GameData>) -> Self {
+    SharedGameData(game_data)
+  }
+}
@@ -0,0 +1,142 @@
+use crate::Map;
+use super::{GameData, Item, ItemId, ItemsCost, Operator, OperatorId, RangeId, SkinId};
+
+/// A reference between two parts of a [`GameData`] that could not be resolved, see [`GameData::validate`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Error)]
+pub enum Violation {
+  /// An attack range used by one of an operator's promotions or skill levels does not exist in [`GameData::ranges`].
+  #[error("operator \"{operator_id}\" references missing range \"{range_id}\"")]
+  MissingRange { operator_id: OperatorId, range_id: RangeId },
+  /// A skin unlocked by one of an operator's promotions does not exist in that operator's [`Operator::skins`].
+  #[error("operator \"{operator_id}\" references missing skin \"{skin_id}\"")]
+  MissingSkin { operator_id: OperatorId, skin_id: SkinId },
+  /// An item used by a cost, a banner or an operator's potential does not exist in [`GameData::items`].
+  #[error("{referenced_by} references missing item \"{item_id}\"")]
+  MissingItem { referenced_by: String, item_id: ItemId },
+  /// An operator listed by an alter group, a banner or one of the operator lookups
+  /// does not exist in [`GameData::operators`].
+  #[error("{referenced_by} references missing operator \"{operator_id}\"")]
+  MissingOperator { referenced_by: String, operator_id: OperatorId }
+}
+
+impl GameData {
+  /// Cross-checks every reference between operators, items, ranges, skins, alters, banners and buildings,
+  /// returning every reference that could not be resolved. An empty list means the data is consistent.
+  ///
+  /// References into [`GameData::items`], [`GameData::ranges`] or [`Operator::skins`] are not checked
+  /// when that collection is empty, since it was most likely deselected or removed with [`GameData::shrink`].
+  pub fn validate(&self) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    for operator in self.operators.values() {
+      validate_operator(operator, self, &mut violations);
+    };
+
+    for alter_group in self.alters.iter() {
+      let referenced_by = format!("alter group \"{}\"", alter_group.id);
+      validate_operator_ids(&alter_group.members, &referenced_by, &self.operators, &mut violations);
+    };
+
+    for (profession, operator_ids) in self.operators_by_profession.iter() {
+      let referenced_by = format!("operators by profession {profession}");
+      validate_operator_ids(operator_ids, &referenced_by, &self.operators, &mut violations);
+    };
+
+    for (sub_profession, operator_ids) in self.operators_by_sub_profession.iter() {
+      let referenced_by = format!("operators by sub-profession \"{}\"", sub_profession.game_id());
+      validate_operator_ids(operator_ids, &referenced_by, &self.operators, &mut violations);
+    };
+
+    for (tag, operator_ids) in self.operators_by_recruitment_tag.iter() {
+      let referenced_by = format!("operators by recruitment tag {tag}");
+      validate_operator_ids(operator_ids, &referenced_by, &self.operators, &mut violations);
+    };
+
+    validate_operator_ids(&self.recruitable_operators, "recruitable operators", &self.operators, &mut violations);
+
+    for banner in self.headhunting_banners.iter() {
+      let referenced_by = format!("banner \"{}\"", banner.id);
+      validate_operator_ids(&banner.rules.limited_operator_ids, &referenced_by, &self.operators, &mut violations);
+      if let Some(item_id) = &banner.item_id {
+        validate_item_id(item_id, &referenced_by, &self.items, &mut violations);
+      };
+    };
+
+    for (building_type, building) in self.buildings.iter() {
+      let referenced_by = format!("building {building_type:?}");
+      for upgrade in building.upgrades.iter() {
+        validate_items_cost(&upgrade.construction_cost, &referenced_by, &self.items, &mut violations);
+      };
+    };
+
+    violations
+  }
+}
+
+fn validate_operator(operator: &Operator, game_data: &GameData, violations: &mut Vec<Violation>) {
+  let referenced_by = format!("operator \"{}\"", operator.id);
+  let items = &game_data.items;
+
+  if !game_data.ranges.is_empty() {
+    let range_ids = operator.promotions.iter().filter_map(|promotion| promotion.attack_range_id.as_ref())
+      .chain(operator.skills.iter().flat_map(|skill| skill.iter_levels()).filter_map(|level| level.attack_range_id.as_ref()));
+    for range_id in range_ids.filter(|&range_id| !game_data.ranges.contains_key(range_id)) {
+      violations.push(Violation::MissingRange { operator_id: operator.id.clone(), range_id: range_id.clone() });
+    };
+  };
+
+  if !operator.skins.is_empty() {
+    let skin_ids = operator.promotions.iter().filter_map(|promotion| promotion.skin_id.as_ref());
+    for skin_id in skin_ids.filter(|&skin_id| !operator.skins.contains_key(skin_id)) {
+      violations.push(Violation::MissingSkin { operator_id: operator.id.clone(), skin_id: skin_id.clone() });
+    };
+  };
+
+  if let Some(item_id) = &operator.potential_item_id {
+    validate_item_id(item_id, &referenced_by, items, violations);
+  };
+
+  for promotion in operator.promotions.iter() {
+    validate_items_cost(&promotion.upgrade_cost, &referenced_by, items, violations);
+  };
+
+  for skill_level_upgrade in operator.skill_level_upgrades.iter() {
+    validate_items_cost(&skill_level_upgrade.upgrade_cost, &referenced_by, items, violations);
+  };
+
+  for skill_mastery in operator.skills.iter().flat_map(|skill| skill.mastery.iter().flatten()) {
+    validate_items_cost(&skill_mastery.upgrade_cost, &referenced_by, items, violations);
+  };
+
+  for module in operator.modules.iter() {
+    validate_items_cost(&module.upgrade_cost, &referenced_by, items, violations);
+  };
+}
+
+fn validate_operator_ids<'a>(
+  operator_ids: impl IntoIterator<Item = &'a OperatorId>,
+  referenced_by: &str,
+  operators: &Map<OperatorId, Operator>,
+  violations: &mut Vec<Violation>
+) {
+  for operator_id in operator_ids {
+    if !operators.contains_key(operator_id) {
+      let referenced_by = referenced_by.to_owned();
+      violations.push(Violation::MissingOperator { referenced_by, operator_id: operator_id.clone() });
+    };
+  };
+}
+
+fn validate_items_cost(cost: &ItemsCost, referenced_by: &str, items: &Map<ItemId, Item>, violations: &mut Vec<Violation>) {
+  for item_id in cost.keys() {
+    validate_item_id(item_id, referenced_by, items, violations);
+  };
+}
+
+fn validate_item_id(item_id: &ItemId, referenced_by: &str, items: &Map<ItemId, Item>, violations: &mut Vec<Violation>) {
+  if !items.is_empty() && !items.contains_key(item_id) {
+    let referenced_by = referenced_by.to_owned();
+    violations.push(Violation::MissingItem { referenced_by, item_id: item_id.clone() });
+  };
+}
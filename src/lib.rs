@@ -3,6 +3,7 @@
 //! A Rust library for parsing datamined game files from Arknights and
 //! exposing them as easy to understand Rust structures.
 
+#[cfg(feature = "async")]
 extern crate base64;
 extern crate chrono;
 #[macro_use]
@@ -10,36 +11,152 @@ extern crate serde;
 extern crate serde_json;
 #[macro_use]
 extern crate thiserror;
+#[cfg(feature = "async")]
 extern crate tokio;
 extern crate uord;
+#[cfg(feature = "async")]
 pub extern crate octocrab;
 
 #[macro_use]
 mod macros;
+pub mod assets;
+pub mod export;
 mod format;
 pub mod game_data;
 pub mod options;
 
 pub use crate::game_data::GameData;
-pub use crate::options::{Options, Region};
+#[cfg(feature = "async")]
+pub use crate::options::Options;
+pub use crate::options::Region;
 
 pub(crate) type Map<K, V> = std::collections::BTreeMap<K, V>;
 pub(crate) type Set<T> = std::collections::BTreeSet<T>;
 
 #[derive(Debug, Error)]
 pub enum Error {
+  #[cfg(feature = "async")]
   #[error(transparent)]
   Base64Error(#[from] base64::DecodeError),
+  #[cfg(feature = "async")]
   #[error(transparent)]
   OctocrabError(#[from] octocrab::Error),
+  #[cfg(feature = "async")]
   #[error("invalid request contents")]
   InvalidResponseContents,
+  /// Returned when fetching a data file fails outside of the GitHub API,
+  /// such as by custom [`DataSource`][crate::options::DataSource] implementations.
+  #[cfg(feature = "async")]
+  #[error(transparent)]
+  DataSourceError(Box<dyn std::error::Error + Send + Sync>),
+  /// Returned when json that is not part of a data file cannot be parsed or written, such as a cache or a manifest.
+  /// Data files that cannot be parsed are returned as [`Error::Table`] instead.
   #[error(transparent)]
   JsonError(#[from] serde_json::Error),
+  /// Returned when a single data file cannot be fetched, decoded or converted.
+  /// The other data files were not at fault, so only this one needs to be fetched again, see [`Error::table`].
+  #[error("{table}: {source}")]
+  Table {
+    /// The identifier of the data file that failed. (Example: `"character_table"`)
+    table: &'static str,
+    /// What went wrong with the data file.
+    source: TableError
+  },
+  #[cfg(feature = "simd-json")]
+  #[error(transparent)]
+  SimdJsonError(#[from] simd_json::Error),
+  /// Returned when reading or writing a file fails outside of a data file, such as a cache.
+  /// Data files that cannot be read are returned as [`Error::Table`] instead.
   #[error(transparent)]
   IoError(#[from] std::io::Error),
+  #[cfg(feature = "zip")]
+  #[error(transparent)]
+  ZipError(#[from] zip::result::ZipError),
+  #[cfg(feature = "csv")]
+  #[error(transparent)]
+  CsvError(#[from] csv::Error),
+  #[cfg(feature = "sqlite")]
+  #[error(transparent)]
+  SqliteError(#[from] rusqlite::Error),
+  #[cfg(feature = "msgpack")]
+  #[error(transparent)]
+  MsgpackEncodeError(#[from] rmp_serde::encode::Error),
+  #[cfg(feature = "msgpack")]
+  #[error(transparent)]
+  MsgpackDecodeError(#[from] rmp_serde::decode::Error),
+  #[cfg(feature = "cache")]
+  #[error(transparent)]
+  BincodeError(#[from] bincode::Error),
+  /// Returned by [`GameData::load_cache`][crate::game_data::GameData::load_cache] when the file is not a cache,
+  /// or is a cache with a different schema version (included if known).
+  #[cfg(feature = "cache")]
+  #[error("incompatible game data cache")]
+  IncompatibleCache(Option<u32>),
+  /// Returned by [`GameData::load_cache`][crate::game_data::GameData::load_cache] when the cache
+  /// has been damaged, such as by being truncated.
+  #[cfg(feature = "cache")]
+  #[error("corrupt game data cache")]
+  CorruptCache,
   /// Returned when `ak-data` cannot find a commit entry with
   /// a valid date within the first request page from GitHub.
+  #[cfg(feature = "async")]
   #[error("cannot find update time")]
-  CannotFindUpdateTime
+  CannotFindUpdateTime,
+  /// Returned when a request or a whole load takes longer than
+  /// [`Options::timeout`][crate::options::Options::timeout] or [`Options::deadline`][crate::options::Options::deadline] allow.
+  #[cfg(feature = "async")]
+  #[error("timed out after {0:?}")]
+  Timeout(std::time::Duration),
+  /// Returned when GitHub refuses a request because the rate limit has been used up.
+  /// Requests can be made again once the limit resets, see [`Options::rate_limit`][crate::options::Options::rate_limit].
+  #[cfg(feature = "async")]
+  #[error("rate limited by GitHub{}", .reset_at.map_or(String::new(), |reset_at| format!(" until {reset_at}")))]
+  RateLimited {
+    /// The time the rate limit resets, if known.
+    reset_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// The number of requests remaining.
+    remaining: u32
+  },
+  /// Returned by [`Options::verify`] when the options do not point to valid game data.
+  #[cfg(feature = "async")]
+  #[error(transparent)]
+  VerifyError(#[from] crate::options::VerifyError)
+}
+
+impl Error {
+  /// Returns the identifier of the data file this error came from, if it came from a single data file.
+  pub fn table(&self) -> Option<&'static str> {
+    match self {
+      Error::Table { table, .. } => Some(*table),
+      _ => None
+    }
+  }
+}
+
+/// The ways a single data file can fail to load, see [`Error::Table`].
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum TableError {
+  /// The data file could not be read or downloaded. Trying again may succeed if the failure was temporary.
+  #[error("failed to fetch: {0}")]
+  Fetch(#[source] Box<Error>),
+  /// The data file is not valid json, or its layout was changed upstream.
+  #[error("failed to decode at {path}: {source}")]
+  Decode {
+    /// The path to the value that could not be parsed. (Example: `"char_002_amiya.phases[0].maxLevel"`)
+    path: String,
+    /// The error returned by the json parser.
+    source: Box<dyn std::error::Error + Send + Sync>
+  },
+  /// A single entry of the data file could not be converted into game data.
+  /// Entries like these can be skipped instead with [`GameDataBuilder::lenient`][crate::game_data::GameDataBuilder::lenient].
+  #[error("failed to convert {entry} at {path}: {source}")]
+  Convert {
+    /// The ID of the entry that could not be converted. (Example: `"char_002_amiya"`)
+    entry: String,
+    /// The path to the value within the entry that could not be parsed. (Example: `"phases[0].maxLevel"`)
+    path: String,
+    /// The error returned by the json parser.
+    source: Box<dyn std::error::Error + Send + Sync>
+  }
 }
@@ -1,46 +1,72 @@
 //! Options that specify where and how to interpret files as Arknights' game data.
 //! Not applicable when parsing local files.
 //!
-//! Creating gamedata from a remote repository currently uses GitHub's API,
-//! and it's compatible with any other repository hosts right now.
+//! Creating gamedata from a remote repository uses GitHub's API through [`Options`].
+//! Repositories hosted on GitLab or served over plain HTTP can be used with [`Source`],
+//! and anything else (mirrors, object storage, test fixtures) can be used by implementing [`DataSource`].
 //!
 //! If you are not using an authorized application to perform the remote requests,
 //! you may run into 403 Forbidden errors due to GitHub ratelimiting you. You can instead
 //! use [`GameData::from_local`][crate::game_data::GameData::from_local] to parse local game files.
+//!
+//! Everything in this module except for [`Region`] requires the `async` feature.
 
+#[cfg(feature = "async")]
 #[doc(no_inline)] pub use octocrab;
+#[cfg(feature = "async")]
 #[doc(no_inline)] pub use octocrab::{Octocrab, OctocrabBuilder};
 
-use chrono::{DateTime, Utc};
+#[cfg(feature = "async")]
+use chrono::{DateTime, TimeZone, Utc};
+#[cfg(feature = "async")]
 use octocrab::models::repos::RepoCommit;
+#[cfg(feature = "async")]
+use once_cell::sync::Lazy;
 
 use crate::format::DataFile;
-use crate::game_data::GameData;
+#[cfg(feature = "async")]
+use crate::game_data::{GameData, Provenance, TableSelection};
 
+#[cfg(feature = "async")]
+use std::collections::HashMap;
 use std::fmt;
-use std::fs::File;
-use std::io::BufReader;
+#[cfg(feature = "async")]
+use std::io::Read;
 use std::path::Path;
+#[cfg(feature = "async")]
+use std::path::PathBuf;
+#[cfg(feature = "async")]
+use std::process::Command;
 use std::str::FromStr;
-
-
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "async")]
+use std::time::Duration;
 
 #[derive(Debug, Error, Clone, Copy)]
 #[error("expected one of \"en_US\", \"ja_JP\", \"ko_KR\", \"zh_CN\", or \"zh_TW\"")]
 pub struct ParseRegionError;
 
 /// Represents which region folder to pull files from when grabbing game data from a repository.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Region {
   /// `en_US`
+  #[serde(rename = "en_US")]
   EnUS,
   /// `ja_JP`
+  #[serde(rename = "ja_JP")]
   JaJP,
   /// `ko_KR`
+  #[serde(rename = "ko_KR")]
   KoKR,
   /// `zh_CN`
+  #[serde(rename = "zh_CN")]
   ZhCN,
   /// `zh_TW`
+  #[serde(rename = "zh_TW")]
   ZhTW
 }
 
@@ -86,6 +112,7 @@ impl fmt::Display for Region {
 }
 
 /// Options that specify where and how to interpret files as Arknights' game data.
+#[cfg(feature = "async")]
 #[derive(Debug, Clone)]
 pub struct Options {
   /// The owner (`0`) and repository (`1`) of a GitHub repository to grab gamedata from.
@@ -95,9 +122,248 @@ pub struct Options {
   /// The region subfolder of that repository to pull files from.
   pub region: Region,
   /// The octocrab instance used when making API requests to GitHub.
-  pub instance: Octocrab
+  ///
+  /// This is shared between clones of these options, meaning that every `Options` derived from
+  /// another (for example, one per region) uses the same HTTP connection pool and authentication.
+  pub instance: Arc<Octocrab>,
+  /// How data files should be downloaded from the repository.
+  pub fetch_mode: FetchMode,
+  /// Remembers previously downloaded data files, so that unchanged files are not downloaded again.
+  /// Like [`Options::instance`], this is shared between clones of these options.
+  pub cache: Option<Arc<HttpCache>>,
+  /// Where downloaded data files are persisted between runs, if anywhere.
+  pub disk_cache: Option<DiskCache>,
+  /// A specific commit or tag to grab gamedata from. When set, this is used instead of [`Options::branch`].
+  pub revision: Option<Rev>,
+  /// How failed downloads of data files are retried.
+  pub retry: RetryPolicy,
+  /// How long a single attempt at downloading a data file may take before it is abandoned.
+  /// Timed out attempts are retried according to [`Options::retry`].
+  pub timeout: Option<Duration>,
+  /// How long loading a whole [`GameData`] may take, including every download and retry.
+  pub deadline: Option<Duration>,
+  /// Called as data files are downloaded and parsed. Like [`Options::instance`], this is shared between clones of these options.
+  pub progress: Option<Progress>,
+  /// The maximum number of data files downloaded at the same time.
+  pub max_concurrent_requests: usize,
+  /// Where data files are inside of the repository.
+  pub layout: PathLayout
+}
+
+/// Where data files are inside of a repository, since this differs between mirrors.
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathLayout {
+  /// Every region has its own folder, containing a `gamedata` folder. (Example: `en_US/gamedata/excel`)
+  /// This is the layout used by [`Options::kengxxiao`] and [`Options::yostar`].
+  RegionFolder,
+  /// The `gamedata` folder is at the root of the repository, which only holds a single region.
+  /// (Example: `gamedata/excel`)
+  Root,
+  /// A template for the path of each data file, where `{region}` is replaced with the region
+  /// and `{path}` is replaced with the location of the data file relative to the `gamedata` folder.
+  /// (Example: `"{region}/{path}"`)
+  Template(String)
+}
+
+#[cfg(feature = "async")]
+impl PathLayout {
+  /// The template this layout corresponds to, see [`PathLayout::Template`].
+  pub fn template(&self) -> &str {
+    match self {
+      PathLayout::RegionFolder => "{region}/gamedata/{path}",
+      PathLayout::Root => "gamedata/{path}",
+      PathLayout::Template(template) => template
+    }
+  }
+
+  /// Fills in this layout's template for the given region and location.
+  pub fn resolve(&self, region: Region, path: &str) -> String {
+    self.template()
+      .replace("{region}", region.to_str())
+      .replace("{path}", path)
+  }
+}
+
+#[cfg(feature = "async")]
+impl Default for PathLayout {
+  #[inline]
+  fn default() -> Self {
+    PathLayout::RegionFolder
+  }
+}
+
+/// The state of the GitHub API rate limit, see [`Options::rate_limit`].
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+  /// The maximum number of requests allowed per hour.
+  pub limit: u32,
+  /// The number of requests remaining until the limit resets.
+  pub remaining: u32,
+  /// The time the limit resets.
+  pub reset_at: DateTime<Utc>
+}
+
+/// Describes how failed downloads of data files are retried, waiting longer after each failed attempt.
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+  /// The maximum number of attempts, including the first one. `1` disables retrying.
+  pub max_attempts: u32,
+  /// How long to wait after the first failed attempt. Doubles after every failed attempt.
+  pub initial_delay: Duration,
+  /// The longest to wait between two attempts.
+  pub max_delay: Duration,
+  /// Whether or not to randomly shorten each delay by up to half,
+  /// so that many clients failing at once do not all retry at the same moment.
+  pub jitter: bool,
+  /// Decides whether or not an error is worth retrying. Defaults to [`RetryPolicy::is_transient`].
+  pub retryable: fn(&crate::Error) -> bool
+}
+
+#[cfg(feature = "async")]
+impl RetryPolicy {
+  /// Never retries.
+  pub const NONE: Self = RetryPolicy {
+    max_attempts: 1,
+    initial_delay: Duration::ZERO,
+    max_delay: Duration::ZERO,
+    jitter: false,
+    retryable: RetryPolicy::is_transient
+  };
+
+  pub fn max_attempts(self, max_attempts: u32) -> Self {
+    RetryPolicy { max_attempts, ..self }
+  }
+
+  pub fn initial_delay(self, initial_delay: Duration) -> Self {
+    RetryPolicy { initial_delay, ..self }
+  }
+
+  pub fn max_delay(self, max_delay: Duration) -> Self {
+    RetryPolicy { max_delay, ..self }
+  }
+
+  pub fn jitter(self, jitter: bool) -> Self {
+    RetryPolicy { jitter, ..self }
+  }
+
+  pub fn retryable(self, retryable: fn(&crate::Error) -> bool) -> Self {
+    RetryPolicy { retryable, ..self }
+  }
+
+  /// Returns true for errors that are likely to go away by themselves:
+  /// network failures, server errors, and GitHub's secondary rate limits.
+  /// Running out of the (primary) rate limit is not considered transient, see [`Error::RateLimited`][crate::Error::RateLimited].
+  pub fn is_transient(error: &crate::Error) -> bool {
+    match error {
+      crate::Error::OctocrabError(octocrab::Error::Http { .. }) => true,
+      crate::Error::Timeout(..) => true,
+      crate::Error::Table { source: crate::TableError::Fetch(error), .. } => RetryPolicy::is_transient(error),
+      crate::Error::OctocrabError(octocrab::Error::GitHub { source, .. }) => {
+        source.message.to_lowercase().contains("secondary rate limit")
+      },
+      crate::Error::DataSourceError(error) => match error.downcast_ref::<reqwest::Error>() {
+        Some(error) => error.is_timeout() || error.is_connect() || error.status().map_or(false, |status| {
+          status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        }),
+        None => false
+      },
+      _ => false
+    }
+  }
+
+  /// The delay before the given retry, counting from `1`.
+  fn delay(&self, retry: u32) -> Duration {
+    let delay = self.initial_delay.saturating_mul(2u32.saturating_pow(retry - 1)).min(self.max_delay);
+    if self.jitter {
+      // the clock's nanoseconds are random enough to spread retries apart
+      let nanos = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().subsec_nanos();
+      delay.mul_f64(1.0 - (nanos % 1000) as f64 / 2000.0)
+    } else {
+      delay
+    }
+  }
+
+  /// Runs the given operation until it succeeds, fails with an error that is not retryable, or runs out of attempts.
+  pub(crate) async fn run<T, F, Fut>(&self, mut operation: F) -> Result<T, crate::Error>
+  where F: FnMut() -> Fut, Fut: Future<Output = Result<T, crate::Error>> {
+    let mut attempt = 1;
+    loop {
+      match operation().await {
+        Err(error) if attempt < self.max_attempts && (self.retryable)(&error) => {
+          tokio::time::sleep(self.delay(attempt)).await;
+          attempt += 1;
+        },
+        result => return result
+      };
+    }
+  }
+}
+
+#[cfg(feature = "async")]
+impl Default for RetryPolicy {
+  /// Tries up to 4 times, waiting up to 1, 2 and then 4 seconds between attempts.
+  fn default() -> Self {
+    RetryPolicy {
+      max_attempts: 4,
+      initial_delay: Duration::from_secs(1),
+      max_delay: Duration::from_secs(30),
+      jitter: true,
+      retryable: RetryPolicy::is_transient
+    }
+  }
+}
+
+/// A fixed point in a repository's history, see [`Options::revision`].
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Rev {
+  /// A commit, given its SHA.
+  Commit(String),
+  /// A tag, given its name.
+  Tag(String)
+}
+
+#[cfg(feature = "async")]
+impl Rev {
+  pub fn as_str(&self) -> &str {
+    match self {
+      Rev::Commit(sha) => sha,
+      Rev::Tag(name) => name
+    }
+  }
+}
+
+#[cfg(feature = "async")]
+impl fmt::Display for Rev {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+/// Describes how [`Options`] downloads data files from a GitHub repository.
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FetchMode {
+  /// Uses GitHub's contents and blob APIs, which takes two API requests per data file.
+  Api,
+  /// Downloads data files directly from `raw.githubusercontent.com`, which does not count against the API rate limit.
+  /// Finding the time of the latest commit still takes one API request.
+  Raw
 }
 
+#[cfg(feature = "async")]
+impl Default for FetchMode {
+  #[inline]
+  fn default() -> Self {
+    FetchMode::Api
+  }
+}
+
+#[cfg(feature = "async")]
 impl Options {
   /// Defaults to <https://github.com/Kengxxiao/ArknightsGameData>
   pub const DEFAULT_REPOSITORY: (&'static str, &'static str) = ("Kengxxiao", "ArknightsGameData");
@@ -105,22 +371,86 @@ impl Options {
   pub const DEFAULT_BRANCH: &'static str = "master";
   /// Defaults to `en_US`.
   pub const DEFAULT_REGION: Region = Region::EnUS;
+  /// Defaults to `4`, which is fast without looking like abuse to GitHub.
+  pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+  /// Options for <https://github.com/Kengxxiao/ArknightsGameData>, which only holds the `zh_CN` region.
+  pub fn kengxxiao() -> Self {
+    Options::new("Kengxxiao", "ArknightsGameData")
+      .branch("master")
+      .region(Region::ZhCN)
+  }
+
+  /// Options for <https://github.com/Kengxxiao/ArknightsGameData_YoStar>, which holds the `en_US`, `ja_JP` and `ko_KR` regions.
+  /// Defaults to `en_US`, use [`Options::region`] to pick one of the others.
+  pub fn yostar() -> Self {
+    Options::new("Kengxxiao", "ArknightsGameData_YoStar")
+      .branch("main")
+      .region(Region::EnUS)
+  }
+
+  /// Options for <https://github.com/yuanyan3060/ArknightsGameResource>, which only holds the `zh_CN` region
+  /// and keeps its `gamedata` folder at the root of the repository.
+  pub fn arknights_game_resource() -> Self {
+    Options::new("yuanyan3060", "ArknightsGameResource")
+      .branch("main")
+      .region(Region::ZhCN)
+      .layout(PathLayout::Root)
+  }
 
+  /// Creates options for the given repository, using the global octocrab instance (see [`octocrab::instance`]).
   pub fn new(owner: impl Into<String>, repo: impl Into<String>) -> Self {
+    Options::with_instance(owner, repo, octocrab::instance())
+  }
+
+  /// Creates options for the given repository, using the given octocrab instance.
+  /// Passing the same `Arc<Octocrab>` to several `Options` lets them share one HTTP connection pool.
+  pub fn with_instance(owner: impl Into<String>, repo: impl Into<String>, instance: impl Into<Arc<Octocrab>>) -> Self {
     Options {
       repository: (owner.into(), repo.into()),
       branch: Self::DEFAULT_BRANCH.to_owned(),
       region: Region::default(),
-      instance: Octocrab::default()
+      instance: instance.into(),
+      fetch_mode: FetchMode::default(),
+      cache: None,
+      disk_cache: None,
+      revision: None,
+      retry: RetryPolicy::default(),
+      timeout: None,
+      deadline: None,
+      progress: None,
+      max_concurrent_requests: Self::DEFAULT_MAX_CONCURRENT_REQUESTS,
+      layout: PathLayout::RegionFolder
     }
   }
 
+  /// Creates options for the default repository, using a new octocrab instance authenticated with the given personal access token.
+  /// Authenticated requests have a much higher rate limit. See [`Options::builder`] for more control over the instance.
+  pub fn with_token(token: impl Into<String>) -> Result<Self, crate::Error> {
+    Options::builder().token(token).build()
+  }
+
+  /// Creates a builder for options that use a new octocrab instance, without having to use octocrab's own builder.
+  pub fn builder() -> OptionsBuilder {
+    OptionsBuilder::default()
+  }
+
   pub fn branch(self, branch: impl Into<String>) -> Self {
     Options {
       repository: self.repository,
       branch: branch.into(),
       region: self.region,
-      instance: self.instance
+      instance: self.instance,
+      fetch_mode: self.fetch_mode,
+      cache: self.cache,
+      disk_cache: self.disk_cache,
+      revision: self.revision,
+      retry: self.retry,
+      timeout: self.timeout,
+      deadline: self.deadline,
+      progress: self.progress,
+      max_concurrent_requests: self.max_concurrent_requests,
+      layout: self.layout
     }
   }
 
@@ -129,90 +459,1824 @@ impl Options {
       repository: self.repository,
       branch: self.branch,
       region,
-      instance: self.instance
+      instance: self.instance,
+      fetch_mode: self.fetch_mode,
+      cache: self.cache,
+      disk_cache: self.disk_cache,
+      revision: self.revision,
+      retry: self.retry,
+      timeout: self.timeout,
+      deadline: self.deadline,
+      progress: self.progress,
+      max_concurrent_requests: self.max_concurrent_requests,
+      layout: self.layout
+    }
+  }
+
+  pub fn instance(self, instance: impl Into<Arc<Octocrab>>) -> Self {
+    Options {
+      repository: self.repository,
+      branch: self.branch,
+      region: self.region,
+      instance: instance.into(),
+      fetch_mode: self.fetch_mode,
+      cache: self.cache,
+      disk_cache: self.disk_cache,
+      revision: self.revision,
+      retry: self.retry,
+      timeout: self.timeout,
+      deadline: self.deadline,
+      progress: self.progress,
+      max_concurrent_requests: self.max_concurrent_requests,
+      layout: self.layout
+    }
+  }
+
+  pub fn fetch_mode(self, fetch_mode: FetchMode) -> Self {
+    Options {
+      repository: self.repository,
+      branch: self.branch,
+      region: self.region,
+      instance: self.instance,
+      fetch_mode,
+      cache: self.cache,
+      disk_cache: self.disk_cache,
+      revision: self.revision,
+      retry: self.retry,
+      timeout: self.timeout,
+      deadline: self.deadline,
+      progress: self.progress,
+      max_concurrent_requests: self.max_concurrent_requests,
+      layout: self.layout
+    }
+  }
+
+  pub fn cache(self, cache: impl Into<Arc<HttpCache>>) -> Self {
+    Options {
+      repository: self.repository,
+      branch: self.branch,
+      region: self.region,
+      instance: self.instance,
+      fetch_mode: self.fetch_mode,
+      cache: Some(cache.into()),
+      disk_cache: self.disk_cache,
+      revision: self.revision,
+      retry: self.retry,
+      timeout: self.timeout,
+      deadline: self.deadline,
+      progress: self.progress,
+      max_concurrent_requests: self.max_concurrent_requests,
+      layout: self.layout
+    }
+  }
+
+  /// Grabs gamedata from a specific commit or tag, instead of the latest commit on the branch.
+  /// This allows loading the exact same data every time, regardless of later updates to the repository.
+  pub fn revision(self, revision: Rev) -> Self {
+    Options {
+      repository: self.repository,
+      branch: self.branch,
+      region: self.region,
+      instance: self.instance,
+      fetch_mode: self.fetch_mode,
+      cache: self.cache,
+      disk_cache: self.disk_cache,
+      revision: Some(revision),
+      retry: self.retry,
+      timeout: self.timeout,
+      deadline: self.deadline,
+      progress: self.progress,
+      max_concurrent_requests: self.max_concurrent_requests,
+      layout: self.layout
+    }
+  }
+
+  /// The git reference files are fetched from: the revision if one is set, otherwise the branch.
+  pub fn git_ref(&self) -> &str {
+    self.revision.as_ref().map_or(&self.branch, Rev::as_str)
+  }
+
+  pub fn retry(self, retry: RetryPolicy) -> Self {
+    Options {
+      repository: self.repository,
+      branch: self.branch,
+      region: self.region,
+      instance: self.instance,
+      fetch_mode: self.fetch_mode,
+      cache: self.cache,
+      disk_cache: self.disk_cache,
+      revision: self.revision,
+      retry,
+      timeout: self.timeout,
+      deadline: self.deadline,
+      progress: self.progress,
+      max_concurrent_requests: self.max_concurrent_requests,
+      layout: self.layout
+    }
+  }
+
+  pub fn timeout(self, timeout: Duration) -> Self {
+    Options {
+      repository: self.repository,
+      branch: self.branch,
+      region: self.region,
+      instance: self.instance,
+      fetch_mode: self.fetch_mode,
+      cache: self.cache,
+      disk_cache: self.disk_cache,
+      revision: self.revision,
+      retry: self.retry,
+      timeout: Some(timeout),
+      deadline: self.deadline,
+      progress: self.progress,
+      max_concurrent_requests: self.max_concurrent_requests,
+      layout: self.layout
+    }
+  }
+
+  pub fn deadline(self, deadline: Duration) -> Self {
+    Options {
+      repository: self.repository,
+      branch: self.branch,
+      region: self.region,
+      instance: self.instance,
+      fetch_mode: self.fetch_mode,
+      cache: self.cache,
+      disk_cache: self.disk_cache,
+      revision: self.revision,
+      retry: self.retry,
+      timeout: self.timeout,
+      deadline: Some(deadline),
+      progress: self.progress,
+      max_concurrent_requests: self.max_concurrent_requests,
+      layout: self.layout
+    }
+  }
+
+  /// Calls the given function as data files are downloaded and parsed, see [`ProgressEvent`].
+  pub fn progress(self, progress: impl Fn(ProgressEvent) + Send + Sync + 'static) -> Self {
+    Options {
+      repository: self.repository,
+      branch: self.branch,
+      region: self.region,
+      instance: self.instance,
+      fetch_mode: self.fetch_mode,
+      cache: self.cache,
+      disk_cache: self.disk_cache,
+      revision: self.revision,
+      retry: self.retry,
+      timeout: self.timeout,
+      deadline: self.deadline,
+      progress: Some(Progress::new(progress)),
+      max_concurrent_requests: self.max_concurrent_requests,
+      layout: self.layout
+    }
+  }
+
+  pub fn max_concurrent_requests(self, max_concurrent_requests: usize) -> Self {
+    Options {
+      repository: self.repository,
+      branch: self.branch,
+      region: self.region,
+      instance: self.instance,
+      fetch_mode: self.fetch_mode,
+      cache: self.cache,
+      disk_cache: self.disk_cache,
+      revision: self.revision,
+      retry: self.retry,
+      timeout: self.timeout,
+      deadline: self.deadline,
+      progress: self.progress,
+      max_concurrent_requests,
+      layout: self.layout
+    }
+  }
+
+  pub fn layout(self, layout: PathLayout) -> Self {
+    Options {
+      repository: self.repository,
+      branch: self.branch,
+      region: self.region,
+      instance: self.instance,
+      fetch_mode: self.fetch_mode,
+      cache: self.cache,
+      disk_cache: self.disk_cache,
+      revision: self.revision,
+      retry: self.retry,
+      timeout: self.timeout,
+      deadline: self.deadline,
+      progress: self.progress,
+      max_concurrent_requests: self.max_concurrent_requests,
+      layout
+    }
+  }
+
+  /// The path of the given location inside of the repository, see [`Options::layout`].
+  pub(crate) fn resolve_path(&self, path: &str) -> String {
+    self.layout.resolve(self.region, path)
+  }
+
+  /// Persists downloaded data files in the given directory, see [`DiskCache`].
+  pub fn cache_dir(self, directory: impl Into<PathBuf>) -> Self {
+    self.disk_cache(DiskCache::new(directory))
+  }
+
+  pub fn disk_cache(self, disk_cache: DiskCache) -> Self {
+    Options {
+      repository: self.repository,
+      branch: self.branch,
+      region: self.region,
+      instance: self.instance,
+      fetch_mode: self.fetch_mode,
+      cache: self.cache,
+      disk_cache: Some(disk_cache),
+      revision: self.revision,
+      retry: self.retry,
+      timeout: self.timeout,
+      deadline: self.deadline,
+      progress: self.progress,
+      max_concurrent_requests: self.max_concurrent_requests,
+      layout: self.layout
+    }
+  }
+
+  /// Returns a handle to the octocrab instance used by these options,
+  /// which can be passed to [`Options::with_instance`] or [`Options::instance`] to share it.
+  pub fn shared_instance(&self) -> Arc<Octocrab> {
+    Arc::clone(&self.instance)
+  }
+
+  /// Creates a [`Watcher`] that checks for new game data every `interval`.
+  /// Must be called from inside of a tokio runtime.
+  ///
+  /// The watcher still stores files in the [`Options::disk_cache`], but ignores its [`DiskCache::ttl`].
+  pub fn watch(&self, interval: Duration) -> Watcher {
+    let mut interval = tokio::time::interval(interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // a commit remembered by the disk cache would hand back the game data the watcher already has
+    let disk_cache = self.disk_cache.clone().map(|disk_cache| DiskCache { ttl: None, ..disk_cache });
+    Watcher { options: Options { disk_cache, ..self.clone() }, interval, data_version: None }
+  }
+
+  /// Gets the current state of the GitHub API rate limit for the octocrab instance used by these options.
+  /// Checking the rate limit does not count against it.
+  pub async fn rate_limit(&self) -> Result<RateLimit, crate::Error> {
+    let rate = self.instance.ratelimit().get().await?.resources.core;
+    Ok(RateLimit {
+      limit: rate.limit as u32,
+      remaining: rate.remaining as u32,
+      reset_at: Utc.timestamp_opt(rate.reset as i64, 0).single().unwrap_or_else(Utc::now)
+    })
+  }
+
+  /// Turns errors caused by GitHub's (primary) API rate limit into [`Error::RateLimited`][crate::Error::RateLimited].
+  async fn check_rate_limit<T>(&self, result: Result<T, crate::Error>) -> Result<T, crate::Error> {
+    match result {
+      Err(error) if is_rate_limit_error(&error) => match self.rate_limit().await {
+        Ok(rate_limit) => Err(crate::Error::RateLimited {
+          reset_at: Some(rate_limit.reset_at),
+          remaining: rate_limit.remaining
+        }),
+        Err(..) => Err(error)
+      },
+      result => result
     }
   }
 
+  /// Gets the contents of `excel/data_version.txt`, which changes whenever the game data does.
+  /// This only downloads a single small file, see [`GameData::get_outdated_version`].
+  pub async fn get_data_version(&self) -> Result<String, crate::Error> {
+    let contents = self.fetch(DATA_VERSION_LOCATION).await?;
+    Ok(parse_data_version(&contents))
+  }
+
   pub async fn get_last_updated(&self) -> Result<DateTime<Utc>, crate::Error> {
-    let Options { repository: (owner, repo), branch, .. } = self;
+    self.get_last_commit().await.map(|(_, last_updated)| last_updated)
+  }
+
+  /// Gets the SHA and date of the most recent commit on the branch (or the revision) specified by these options.
+  pub(crate) async fn get_last_commit(&self) -> Result<(String, DateTime<Utc>), crate::Error> {
+    let Options { repository: (owner, repo), .. } = self;
     let repo_handle = self.instance.repos(owner, repo);
-    let commits_list = repo_handle.list_commits().branch(branch).send().await?;
+    let commits_list = repo_handle.list_commits().branch(self.git_ref()).send().await;
+    let commits_list = self.check_rate_limit(commits_list.map_err(crate::Error::from)).await?;
     commits_list.into_iter()
-      .find_map(get_commit_last_updated)
+      .find_map(|repo_commit| {
+        let sha = repo_commit.sha.clone();
+        get_commit_last_updated(repo_commit).map(|last_updated| (sha, last_updated))
+      })
       .ok_or(crate::Error::CannotFindUpdateTime)
   }
 
+  /// Makes a single attempt at fetching a data file, see [`Options::retry`].
+  async fn fetch_once(&self, relative_path: &str) -> Result<Vec<u8>, crate::Error> {
+    let Options { repository: (owner, repo), .. } = self;
+    let path = self.resolve_path(relative_path);
+    let branch = self.git_ref();
+    if let Some(cache) = &self.cache {
+      return self.fetch_cached(cache, relative_path).await;
+    };
+
+    if self.fetch_mode == FetchMode::Raw {
+      let url = format!("https://raw.githubusercontent.com/{owner}/{repo}/{branch}/{path}");
+      return get_raw(&self.instance, url).await;
+    };
+
+    let repo_handle = self.instance.repos(owner, repo);
+    let content_items = repo_handle.get_content().r#ref(branch)
+      .path(path)
+      .send().await?;
+    let content = content_items.items.into_iter().next()
+      .ok_or(crate::Error::InvalidResponseContents)?;
+    let blob: Blob = self.instance.get(content.links.git, None::<&()>).await?;
+    Ok(blob.into_bytes()?)
+  }
+
+  /// Fetches a data file with a conditional request, returning the cached contents if they have not changed.
+  async fn fetch_cached(&self, cache: &HttpCache, relative_path: &str) -> Result<Vec<u8>, crate::Error> {
+    use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+
+    let Options { repository: (owner, repo), .. } = self;
+    let path = self.resolve_path(relative_path);
+    let branch = self.git_ref();
+    let url = match self.fetch_mode {
+      FetchMode::Api => format!("https://api.github.com/repos/{owner}/{repo}/contents/{path}?ref={branch}"),
+      FetchMode::Raw => format!("https://raw.githubusercontent.com/{owner}/{repo}/{branch}/{path}")
+    };
+
+    let cached = cache.get(&url).await?;
+    let mut request = self.instance.request_builder(&url, reqwest::Method::GET);
+    if let Some(cached) = &cached {
+      if let Some(etag) = &cached.etag {
+        request = request.header(IF_NONE_MATCH, etag.as_str());
+      };
+
+      if let Some(last_modified) = &cached.last_modified {
+        request = request.header(IF_MODIFIED_SINCE, last_modified.as_str());
+      };
+    };
+
+    let response = self.instance.execute(request).await?;
+    if let Some(cached) = cached.filter(|_| response.status() == reqwest::StatusCode::NOT_MODIFIED) {
+      return Ok(cached.contents);
+    };
+
+    if let Some(error) = get_rate_limited_error(&response) {
+      return Err(error);
+    };
+
+    let response = response.error_for_status().map_err(data_source_error)?;
+    let etag = get_header(&response, ETAG);
+    let last_modified = get_header(&response, LAST_MODIFIED);
+    let contents = match self.fetch_mode {
+      FetchMode::Api => {
+        let content: ContentFile = response.json().await.map_err(data_source_error)?;
+        let blob: Blob = self.instance.get(content.git_url, None::<&()>).await?;
+        blob.into_bytes()?
+      },
+      FetchMode::Raw => response.bytes().await.map_err(data_source_error)?.to_vec()
+    };
+
+    cache.insert(url, CacheEntry { etag, last_modified, contents: contents.clone() }).await?;
+    Ok(contents)
+  }
+
+  /// Creates a [`Provenance`] describing game data fetched with these options from the given commit.
+  pub(crate) fn provenance_at(&self, commit: Option<String>) -> Provenance {
+    Provenance {
+      host: None,
+      repository: Some(self.repository.clone()),
+      // a pinned revision is not on any particular branch
+      branch: self.revision.is_none().then(|| self.branch.clone()),
+      commit,
+      region: Some(self.region),
+      fetched_at: Utc::now()
+    }
+  }
+
+  /// Checks that the repository, branch and region folder specified by these options exist,
+  /// and that every file needed to construct a [`GameData`] is present.
+  ///
+  /// This only makes a few small requests, so it can be used to catch mistakes in the options
+  /// before starting a full download with [`GameData::from_remote`].
+  pub async fn verify(&self) -> Result<(), crate::Error> {
+    let Options { repository: (owner, repo), branch, .. } = self;
+    let repo_handle = self.instance.repos(owner, repo);
+    repo_handle.get().await.map_err(|error| match is_not_found(&error) {
+      true => VerifyError::RepositoryNotFound(owner.clone(), repo.clone()).into(),
+      false => crate::Error::from(error)
+    })?;
+
+    match &self.revision {
+      None => {
+        let reference = octocrab::params::repos::Reference::Branch(branch.clone());
+        repo_handle.get_ref(&reference).await.map_err(|error| match is_not_found(&error) {
+          true => VerifyError::BranchNotFound(branch.clone()).into(),
+          false => crate::Error::from(error)
+        })?;
+      },
+      Some(Rev::Tag(tag)) => {
+        let reference = octocrab::params::repos::Reference::Tag(tag.clone());
+        repo_handle.get_ref(&reference).await.map_err(|error| match is_not_found(&error) {
+          true => VerifyError::TagNotFound(tag.clone()).into(),
+          false => crate::Error::from(error)
+        })?;
+      },
+      // commits cannot be looked up as references, a missing commit shows up as missing paths below
+      Some(Rev::Commit(..)) => ()
+    };
+
+    let branch = self.git_ref();
+
+    let mut directories = std::collections::BTreeMap::<&str, Vec<&str>>::new();
+    for location in crate::format::DataFiles::LOCATIONS {
+      let (directory, file_name) = location.rsplit_once('/').unwrap_or(("", location));
+      directories.entry(directory).or_default().push(file_name);
+    };
+
+    for (directory, file_names) in directories {
+      let path = self.resolve_path(directory);
+      let content_items = repo_handle.get_content().r#ref(branch)
+        .path(&path).send().await
+        .map_err(|error| match is_not_found(&error) {
+          true => VerifyError::PathNotFound(path.clone()).into(),
+          false => crate::Error::from(error)
+        })?;
+
+      for file_name in file_names {
+        if !content_items.items.iter().any(|content| content.name == file_name) {
+          return Err(VerifyError::PathNotFound(format!("{path}/{file_name}")).into());
+        };
+      };
+    };
+
+    Ok(())
+  }
+
   /// Equivalent to [`GameData::from_remote`]
   pub async fn request_game_data(&self) -> Result<GameData, crate::Error> {
-    let (data_files, last_updated) = tokio::try_join!(
-      crate::format::DataFiles::from_remote(self),
-      self.get_last_updated()
+    self.request_game_data_with(&TableSelection::ALL, false).await
+  }
+
+  pub(crate) async fn request_game_data_with(&self, tables: &TableSelection, lenient: bool) -> Result<GameData, crate::Error> {
+    match self.deadline {
+      Some(deadline) => with_timeout(deadline, self.request_game_data_uncapped(tables, lenient)).await,
+      None => self.request_game_data_uncapped(tables, lenient).await
+    }
+  }
+
+  async fn request_game_data_uncapped(&self, tables: &TableSelection, lenient: bool) -> Result<GameData, crate::Error> {
+    if let Some(disk_cache) = &self.disk_cache {
+      let (commit, last_updated) = match disk_cache.read_head(self).await? {
+        Some(head) => (head.commit, head.last_updated),
+        None => {
+          let (commit, last_updated) = self.get_last_commit().await?;
+          disk_cache.write_head(self, &commit, last_updated).await?;
+          (commit, last_updated)
+        }
+      };
+
+      return self.request_game_data_cached(disk_cache, commit, last_updated, tables, lenient).await;
+    };
+
+    let (data_files, (commit, last_updated), data_version) = tokio::try_join!(
+      crate::format::DataFiles::from_source(self, tables),
+      self.get_last_commit(),
+      fetch_data_version(self)
+    )?;
+
+    let provenance = self.provenance_at(Some(commit));
+    let mut game_data = data_files.into_game_data(tables, Some(last_updated), provenance, lenient)?;
+    game_data.data_version = data_version;
+    Ok(game_data)
+  }
+
+  /// Loads the data files of the given commit from the disk cache, downloading any that are missing.
+  async fn request_game_data_cached(
+    &self, disk_cache: &DiskCache, commit: String, last_updated: DateTime<Utc>, tables: &TableSelection, lenient: bool
+  ) -> Result<GameData, crate::Error> {
+    let source = DiskCacheSource {
+      directory: disk_cache.repository_dir(self).join(&commit),
+      force_refresh: disk_cache.force_refresh,
+      // pinned to the commit, in case the branch moves while files are being downloaded
+      source: self.clone().revision(Rev::Commit(commit.clone()))
+    };
+
+    let (data_files, data_version) = tokio::try_join!(
+      crate::format::DataFiles::from_source(&source, tables),
+      fetch_data_version(&source)
+    )?;
+
+    let mut game_data = data_files.into_game_data(tables, Some(last_updated), self.provenance_at(Some(commit)), lenient)?;
+    game_data.data_version = data_version;
+    Ok(game_data)
+  }
+
+  /// Downloads a snapshot of the whole branch as a single tarball, and constructs a [`GameData`]
+  /// from the data files inside of it, ignoring [`Options::fetch_mode`].
+  ///
+  /// This only takes two API requests in total (one for the archive, one for the latest commit),
+  /// at the cost of downloading the entire repository at once.
+  pub async fn fetch_archive(&self) -> Result<GameData, crate::Error> {
+    let Options { repository: (owner, repo), .. } = self;
+    let url = format!("https://api.github.com/repos/{owner}/{repo}/tarball/{}", self.git_ref());
+    let (files, (commit, last_updated)) = tokio::try_join!(
+      get_archive(&self.instance, url, self.resolve_path("")),
+      self.get_last_commit()
     )?;
 
-    Ok(data_files.into_game_data(Some(last_updated)))
+    let source = ArchiveSource {
+      files,
+      last_updated,
+      provenance: self.provenance_at(Some(commit))
+    };
+
+    request_game_data_from(&source, &TableSelection::ALL, false).await
   }
 
   /// Patches the given `GameData` if the data it is based on is out of date.
   /// Replaces `self` and returns it if it was out of date.
+  ///
+  /// If the commit the given `GameData` was fetched from is known (see [`Provenance::commit`]),
+  /// only the data files that changed since that commit are downloaded again.
   pub async fn patch_game_data(&self, game_data: &mut GameData) -> Result<Option<GameData>, crate::Error> {
-    let last_updated = self.get_last_updated().await?;
-    if game_data.is_outdated(last_updated) {
-      let data_files = crate::format::DataFiles::from_remote(self).await?;
-      let game_data = std::mem::replace(game_data, data_files.into_game_data(Some(last_updated)));
-      Ok(Some(game_data))
-    } else {
-      Ok(None)
-    }
+    let new_game_data = self.get_patched_game_data(game_data).await?;
+    Ok(new_game_data.map(|new_game_data| std::mem::replace(game_data, new_game_data)))
   }
-}
 
-impl Default for Options {
-  fn default() -> Self {
-    let (owner, repo) = Self::DEFAULT_REPOSITORY;
-    Options::new(owner, repo)
+  /// Builds a patched copy of the given `GameData` if the data it is based on is out of date, see [`Options::patch_game_data`].
+  pub(crate) async fn get_patched_game_data(&self, game_data: &GameData) -> Result<Option<GameData>, crate::Error> {
+    let (commit, last_updated) = self.get_last_commit().await?;
+    if !game_data.is_outdated(last_updated) {
+      return Ok(None);
+    };
+
+    let tables = match game_data.provenance.commit.as_deref() {
+      Some(old_commit) => TableSelection::affected_by(self.get_changed_tables(old_commit, &commit).await?),
+      None => TableSelection::ALL
+    };
+
+    if let Some(disk_cache) = &self.disk_cache {
+      disk_cache.write_head(self, &commit, last_updated).await?;
+    };
+
+    let mut new_game_data = game_data.clone();
+    if tables != TableSelection::NONE {
+      let changed_game_data = if let Some(disk_cache) = &self.disk_cache {
+        self.request_game_data_cached(disk_cache, commit.clone(), last_updated, &tables, false).await?
+      } else {
+        // pinned to the commit, in case the branch moves while files are being downloaded
+        let options = self.clone().revision(Rev::Commit(commit.clone()));
+        let data_files = crate::format::DataFiles::from_source(&options, &tables).await?;
+        data_files.into_game_data(&tables, Some(last_updated), self.provenance_at(Some(commit.clone())), false)?
+      };
+
+      new_game_data.merge_selected(changed_game_data, &tables);
+    };
+
+    let options = self.clone().revision(Rev::Commit(commit.clone()));
+    new_game_data.data_version = fetch_data_version(&options).await?;
+
+    new_game_data.last_updated = Some(last_updated);
+    new_game_data.provenance = self.provenance_at(Some(commit));
+    Ok(Some(new_game_data))
   }
-}
 
-fn get_commit_last_updated(repo_commit: RepoCommit) -> Option<DateTime<Utc>> {
-  repo_commit.commit.author.and_then(|author| author.date)
-}
+  /// Gets the identifiers of every data file that differs between two commits, by comparing their blob SHAs.
+  async fn get_changed_tables(&self, old_commit: &str, new_commit: &str) -> Result<Vec<&'static str>, crate::Error> {
+    let (old_shas, new_shas) = tokio::try_join!(
+      self.get_data_file_shas(old_commit),
+      self.get_data_file_shas(new_commit)
+    )?;
 
-pub(crate) async fn get_data_file_remote<T: DataFile>(options: &Options) -> Result<T, crate::Error> {
-  let Options { repository: (owner, repo), branch, region, .. } = options;
-  let repo_handle = options.instance.repos(owner, repo);
-  let content_items = repo_handle.get_content().r#ref(branch)
-    .path(format!("{region}/gamedata/{}", T::LOCATION))
-    .send().await?;
-  let content = content_items.items.into_iter().next()
-    .ok_or(crate::Error::InvalidResponseContents)?;
-  let blob: Blob = options.instance.get(content.links.git, None::<&()>).await?;
-  let value = serde_json::from_slice(&blob.into_bytes()?)?;
-  Ok(value)
-}
+    let locations = crate::format::DataFiles::LOCATIONS.iter();
+    let identifiers = crate::format::DataFiles::IDENTIFIERS.iter();
+    let changed_tables = Iterator::zip(locations, identifiers)
+      // files missing from either commit are treated as changed
+      .filter(|&(&location, _)| match (old_shas.get(location), new_shas.get(location)) {
+        (Some(old_sha), Some(new_sha)) => old_sha != new_sha,
+        _ => true
+      })
+      .map(|(_, &identifier)| identifier)
+      .collect();
+    Ok(changed_tables)
+  }
 
-pub(crate) async fn get_data_file_local<T: DataFile + Send + 'static>(gamedata_dir: &Path) -> Result<T, crate::Error> {
-  let path = gamedata_dir.join(T::LOCATION);
-  tokio::task::spawn_blocking(move || -> Result<T, crate::Error> {
-    let reader = BufReader::new(File::open(path)?);
-    let item = serde_json::from_reader(reader)?;
-    Ok(item)
-  }).await.unwrap()
+  /// Gets the blob SHA of every data file at the given commit, keyed by location.
+  async fn get_data_file_shas(&self, commit: &str) -> Result<HashMap<String, String>, crate::Error> {
+    let Options { repository: (owner, repo), .. } = self;
+    let repo_handle = self.instance.repos(owner, repo);
+    let mut directories = crate::format::DataFiles::LOCATIONS.iter()
+      .map(|location| location.rsplit_once('/').map_or("", |(directory, _)| directory))
+      .collect::<Vec<&str>>();
+    directories.sort_unstable();
+    directories.dedup();
+
+    let mut shas = HashMap::new();
+    for directory in directories {
+      let content_items = repo_handle.get_content().r#ref(commit)
+        .path(self.resolve_path(directory))
+        .send().await?;
+      for content in content_items.items {
+        let location = if directory.is_empty() { content.name } else { format!("{directory}/{}", content.name) };
+        shas.insert(location, content.sha);
+      };
+    };
+
+    Ok(shas)
+  }
 }
 
+/// A place that game data files can be fetched from.
+///
+/// [`Options`] fetches files from a GitHub repository, other implementations can fetch them
+/// from mirrors, object storage or test fixtures instead. Use [`GameData::from_source`] to load from one.
+#[cfg(feature = "async")]
+pub trait DataSource {
+  /// Fetches the raw contents of a data file, given its path relative to the `gamedata` folder.
+  /// (Example: `"excel/character_table.json"`)
+  fn fetch(&self, relative_path: &str) -> impl Future<Output = Result<Vec<u8>, crate::Error>> + Send;
 
+  /// Gets the time the data in this source was last updated, if it is known.
+  fn last_updated(&self) -> impl Future<Output = Result<Option<DateTime<Utc>>, crate::Error>> + Send;
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Blob {
-  sha: String,
-  node_id: String,
-  size: u64,
+  /// Describes this source, for attributing game data loaded from it.
+  fn provenance(&self) -> impl Future<Output = Provenance> + Send;
+
+  /// Where to report progress while loading from this source, if anywhere.
+  fn progress(&self) -> Option<&Progress> {
+    None
+  }
+
+  /// The maximum number of data files fetched from this source at the same time.
+  fn max_concurrent_requests(&self) -> usize {
+    Options::DEFAULT_MAX_CONCURRENT_REQUESTS
+  }
+}
+
+/// Something that happened while loading game data, reported to [`Options::progress`].
+/// Each event carries the location of the data file it concerns, relative to the `gamedata` folder.
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgressEvent {
+  /// A data file has started downloading.
+  FileStarted {
+    location: &'static str
+  },
+  /// A data file has finished downloading.
+  FileDownloaded {
+    location: &'static str,
+    bytes: usize
+  },
+  /// A data file has been parsed, and `completed` of the `total` data files being loaded are now done.
+  FileParsed {
+    location: &'static str,
+    completed: usize,
+    total: usize
+  }
+}
+
+/// A shared callback that receives [`ProgressEvent`]s.
+#[cfg(feature = "async")]
+#[derive(Clone)]
+pub struct Progress(Arc<dyn Fn(ProgressEvent) + Send + Sync>);
+
+#[cfg(feature = "async")]
+impl Progress {
+  pub fn new(callback: impl Fn(ProgressEvent) + Send + Sync + 'static) -> Self {
+    Progress(Arc::new(callback))
+  }
+
+  #[inline]
+  pub fn report(&self, event: ProgressEvent) {
+    (self.0)(event)
+  }
+}
+
+#[cfg(feature = "async")]
+impl fmt::Debug for Progress {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("Progress")
+  }
+}
+
+/// Counts the data files completed during a single load, see [`ProgressEvent::FileParsed`].
+#[cfg(feature = "async")]
+pub(crate) struct ProgressTracker<'a> {
+  progress: Option<&'a Progress>,
+  completed: std::sync::atomic::AtomicUsize,
+  total: usize
+}
+
+#[cfg(feature = "async")]
+impl<'a> ProgressTracker<'a> {
+  pub(crate) fn new(progress: Option<&'a Progress>, total: usize) -> Self {
+    ProgressTracker { progress, completed: std::sync::atomic::AtomicUsize::new(0), total }
+  }
+
+  fn report(&self, event: ProgressEvent) {
+    if let Some(progress) = self.progress {
+      progress.report(event);
+    };
+  }
+
+  fn complete(&self, location: &'static str) {
+    let completed = self.completed.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+    self.report(ProgressEvent::FileParsed { location, completed, total: self.total });
+  }
+}
+
+#[cfg(feature = "async")]
+impl DataSource for Options {
+  async fn fetch(&self, relative_path: &str) -> Result<Vec<u8>, crate::Error> {
+    let result = self.retry.run(|| async {
+      match self.timeout {
+        Some(timeout) => with_timeout(timeout, self.fetch_once(relative_path)).await,
+        None => self.fetch_once(relative_path).await
+      }
+    }).await;
+    self.check_rate_limit(result).await
+  }
+
+  async fn last_updated(&self) -> Result<Option<DateTime<Utc>>, crate::Error> {
+    self.get_last_updated().await.map(Some)
+  }
+
+  async fn provenance(&self) -> Provenance {
+    self.provenance_at(None)
+  }
+
+  fn progress(&self) -> Option<&Progress> {
+    self.progress.as_ref()
+  }
+
+  fn max_concurrent_requests(&self) -> usize {
+    self.max_concurrent_requests
+  }
+}
+
+#[cfg(feature = "async")]
+impl Default for Options {
+  fn default() -> Self {
+    let (owner, repo) = Self::DEFAULT_REPOSITORY;
+    Options::new(owner, repo)
+  }
+}
+
+/// Builds [`Options`] along with the octocrab instance they use, see [`Options::builder`].
+///
+/// ```no_run
+/// # use ak_data::options::Options;
+/// # fn f() -> Result<(), ak_data::Error> {
+/// let options = Options::builder()
+///   .token(std::env::var("GITHUB_TOKEN").unwrap())
+///   .user_agent("my-arknights-bot")
+///   .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionsBuilder {
+  repository: (String, String),
+  token: Option<String>,
+  base_url: Option<String>,
+  user_agent: Option<String>
+}
+
+#[cfg(feature = "async")]
+impl OptionsBuilder {
+  /// Sets the repository to grab gamedata from. Defaults to [`Options::DEFAULT_REPOSITORY`].
+  pub fn repository(self, owner: impl Into<String>, repo: impl Into<String>) -> Self {
+    OptionsBuilder { repository: (owner.into(), repo.into()), ..self }
+  }
+
+  /// Sets the personal access token to authenticate with.
+  pub fn token(self, token: impl Into<String>) -> Self {
+    OptionsBuilder { token: Some(token.into()), ..self }
+  }
+
+  /// Sets the base URL of the GitHub API, for GitHub Enterprise servers. (Example: `"https://github.example.com/api/v3/"`)
+  /// Note that [`Options::fetch_archive`], [`HttpCache`] and [`FetchMode::Raw`] always use the public GitHub hosts.
+  pub fn base_url(self, base_url: impl Into<String>) -> Self {
+    OptionsBuilder { base_url: Some(base_url.into()), ..self }
+  }
+
+  /// Sets the user agent sent with every request.
+  pub fn user_agent(self, user_agent: impl Into<String>) -> Self {
+    OptionsBuilder { user_agent: Some(user_agent.into()), ..self }
+  }
+
+  /// Builds the octocrab instance, and options that use it.
+  pub fn build(self) -> Result<Options, crate::Error> {
+    let mut instance = Octocrab::builder();
+    if let Some(token) = self.token {
+      instance = instance.personal_token(token);
+    };
+
+    if let Some(base_url) = self.base_url {
+      instance = instance.base_url(base_url)?;
+    };
+
+    if let Some(user_agent) = self.user_agent {
+      instance = instance.add_header(reqwest::header::USER_AGENT, user_agent);
+    };
+
+    let (owner, repo) = self.repository;
+    Ok(Options::with_instance(owner, repo, instance.build()?))
+  }
+}
+
+#[cfg(feature = "async")]
+impl Default for OptionsBuilder {
+  fn default() -> Self {
+    let (owner, repo) = Options::DEFAULT_REPOSITORY;
+    OptionsBuilder {
+      repository: (owner.to_owned(), repo.to_owned()),
+      token: None,
+      base_url: None,
+      user_agent: None
+    }
+  }
+}
+
+/// Periodically checks a repository for new game data, see [`Options::watch`].
+///
+/// Each check only downloads `excel/data_version.txt` (see [`Options::get_data_version`]),
+/// the full game data is only downloaded once that has changed.
+///
+/// ```no_run
+/// # use ak_data::options::Options;
+/// # use std::time::Duration;
+/// # async fn f() {
+/// let mut watcher = Options::default().watch(Duration::from_secs(600));
+/// loop {
+///   match watcher.next().await {
+///     Ok(game_data) => println!("game data updated: {:?}", game_data.data_version),
+///     Err(error) => eprintln!("failed to check for game data: {error}")
+///   };
+/// };
+/// # }
+/// ```
+#[cfg(feature = "async")]
+#[derive(Debug)]
+pub struct Watcher {
+  options: Options,
+  interval: tokio::time::Interval,
+  data_version: Option<String>
+}
+
+#[cfg(feature = "async")]
+impl Watcher {
+  /// Skips game data up to and including the given one, so that [`Watcher::next`] only returns newer game data.
+  /// Without this, the first call to [`Watcher::next`] returns the current game data straight away.
+  pub fn since(self, game_data: &GameData) -> Self {
+    Watcher { data_version: game_data.data_version.clone(), ..self }
+  }
+
+  /// Waits until the repository has new game data, then downloads and returns it.
+  ///
+  /// Errors do not stop the watcher, calling this again after an error continues checking as normal.
+  pub async fn next(&mut self) -> Result<GameData, crate::Error> {
+    loop {
+      self.interval.tick().await;
+      let data_version = self.options.get_data_version().await?;
+      if self.data_version.as_deref() != Some(data_version.as_str()) {
+        let game_data = self.options.request_game_data().await?;
+        // the branch may have moved between the two requests, in which case the
+        // game data is not returned until a later check sees a version that matches it
+        if game_data.data_version.as_deref().map_or(false, |version| version != data_version) {
+          continue;
+        };
+
+        self.data_version = Some(data_version);
+        return Ok(game_data);
+      };
+    }
+  }
+}
+
+/// A directory that downloaded data files are persisted in, so that they can be reused across process restarts.
+/// Files are stored per repository, branch, region and commit, so they are only downloaded again once the branch has moved.
+///
+/// The latest commit of the branch is normally checked every time game data is requested.
+/// If a TTL is set, the commit found by a previous check is reused until the TTL expires,
+/// allowing game data to be loaded without making any requests at all.
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskCache {
+  /// The directory to store data files in.
+  pub directory: PathBuf,
+  /// How long the latest commit of the branch is remembered for, if at all.
+  pub ttl: Option<Duration>,
+  /// When true, cached files are ignored and downloaded again (replacing the cached ones).
+  pub force_refresh: bool
+}
+
+#[cfg(feature = "async")]
+impl DiskCache {
+  pub fn new(directory: impl Into<PathBuf>) -> Self {
+    DiskCache { directory: directory.into(), ttl: None, force_refresh: false }
+  }
+
+  pub fn ttl(self, ttl: Duration) -> Self {
+    DiskCache { ttl: Some(ttl), ..self }
+  }
+
+  pub fn force_refresh(self, force_refresh: bool) -> Self {
+    DiskCache { force_refresh, ..self }
+  }
+
+  /// Removes every file in the cache directory, including the ones from other repositories and commits.
+  pub fn clear(&self) -> Result<(), crate::Error> {
+    if self.directory.exists() {
+      std::fs::remove_dir_all(&self.directory)?;
+    };
+
+    Ok(())
+  }
+
+  fn repository_dir(&self, options: &Options) -> PathBuf {
+    let Options { repository: (owner, repo), region, .. } = options;
+    self.directory.join(owner).join(repo).join(options.git_ref()).join(region.to_str())
+  }
+
+  /// Reads the last known commit of the branch, if it is still within the TTL.
+  /// A head that cannot be read is treated like an expired one, so that the branch is checked again.
+  async fn read_head(&self, options: &Options) -> Result<Option<DiskCacheHead>, crate::Error> {
+    let Some(ttl) = self.ttl.filter(|_| !self.force_refresh) else { return Ok(None) };
+    let path = self.repository_dir(options).join("HEAD.json");
+    let head = tokio::task::spawn_blocking(move || -> Result<Option<DiskCacheHead>, std::io::Error> {
+      if !path.exists() { return Ok(None) };
+      Ok(serde_json::from_slice(&std::fs::read(path)?).ok())
+    }).await.unwrap()?;
+
+    Ok(head.filter(|head| (Utc::now() - head.checked_at).to_std().unwrap_or_default() < ttl))
+  }
+
+  async fn write_head(&self, options: &Options, commit: &str, last_updated: DateTime<Utc>) -> Result<(), crate::Error> {
+    let head = DiskCacheHead { commit: commit.to_owned(), last_updated, checked_at: Utc::now() };
+    let contents = serde_json::to_vec(&head)?;
+    let path = self.repository_dir(options).join("HEAD.json");
+    tokio::task::spawn_blocking(move || write_atomic(&path, &contents)).await.unwrap()?;
+    Ok(())
+  }
+}
+
+#[cfg(feature = "async")]
+#[derive(Debug, Serialize, Deserialize)]
+struct DiskCacheHead {
+  commit: String,
+  last_updated: DateTime<Utc>,
+  checked_at: DateTime<Utc>
+}
+
+/// Reads data files from a [`DiskCache`], fetching and storing the ones that are missing from `source`.
+#[cfg(feature = "async")]
+#[derive(Debug)]
+struct DiskCacheSource<S = Options> {
+  directory: PathBuf,
+  force_refresh: bool,
+  source: S
+}
+
+#[cfg(feature = "async")]
+impl<S: DataSource + Sync> DataSource for DiskCacheSource<S> {
+  async fn fetch(&self, relative_path: &str) -> Result<Vec<u8>, crate::Error> {
+    let path = self.directory.join(relative_path);
+    if !self.force_refresh {
+      let cached_path = path.clone();
+      let cached = tokio::task::spawn_blocking(move || read_cached_file(&cached_path)).await.unwrap()?;
+      if let Some(contents) = cached {
+        return Ok(contents);
+      };
+    };
+
+    let contents = self.source.fetch(relative_path).await?;
+    tokio::task::spawn_blocking(move || {
+      write_atomic(&path, &contents).map(|()| contents)
+    }).await.unwrap().map_err(From::from)
+  }
+
+  async fn last_updated(&self) -> Result<Option<DateTime<Utc>>, crate::Error> {
+    self.source.last_updated().await
+  }
+
+  async fn provenance(&self) -> Provenance {
+    self.source.provenance().await
+  }
+
+  fn progress(&self) -> Option<&Progress> {
+    self.source.progress()
+  }
+
+  fn max_concurrent_requests(&self) -> usize {
+    self.source.max_concurrent_requests()
+  }
+}
+
+/// Reads a data file from a [`DiskCache`], if it is there. JSON files that no longer parse
+/// (such as ones truncated by a full disk) are ignored, so that they are fetched again.
+#[cfg(feature = "async")]
+fn read_cached_file(path: &Path) -> Result<Option<Vec<u8>>, std::io::Error> {
+  if !path.exists() { return Ok(None) };
+  let contents = std::fs::read(path)?;
+  let is_json = path.extension().is_some_and(|extension| extension == "json");
+  if is_json && serde_json::from_slice::<serde::de::IgnoredAny>(&contents).is_err() {
+    return Ok(None);
+  };
+
+  Ok(Some(contents))
+}
+
+/// Writes to a temporary file first, so that an interrupted write never leaves a partial file behind.
+#[cfg(feature = "async")]
+fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), std::io::Error> {
+  let temp_path = path.with_extension("tmp");
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent)?;
+  };
+
+  std::fs::write(&temp_path, contents)?;
+  std::fs::rename(temp_path, path)
+}
+
+/// Remembers the contents of previously downloaded data files along with their `ETag` and `Last-Modified` headers,
+/// so that later requests for the same files can be made conditional. Files that have not changed
+/// since they were cached are not downloaded again, which makes [`GameData::patch_from_remote`] much cheaper.
+///
+/// Entries are kept in memory, and additionally written to a directory if one is given.
+#[cfg(feature = "async")]
+#[derive(Default)]
+pub struct HttpCache {
+  directory: Option<PathBuf>,
+  entries: Mutex<HashMap<String, CacheEntry>>
+}
+
+#[cfg(feature = "async")]
+impl HttpCache {
+  /// Creates a cache that is only kept in memory.
+  pub fn new() -> Self {
+    HttpCache::default()
+  }
+
+  /// Creates a cache that is also written to (and read from) the given directory,
+  /// allowing it to persist between runs.
+  pub fn with_directory(directory: impl Into<PathBuf>) -> Self {
+    HttpCache { directory: Some(directory.into()), entries: Mutex::default() }
+  }
+
+  /// Removes every entry from the cache, including the ones written to its directory.
+  pub fn clear(&self) -> Result<(), crate::Error> {
+    self.entries.lock().unwrap().clear();
+    if let Some(directory) = &self.directory {
+      if directory.exists() {
+        std::fs::remove_dir_all(directory)?;
+      };
+    };
+
+    Ok(())
+  }
+
+  async fn get(&self, url: &str) -> Result<Option<CacheEntry>, crate::Error> {
+    if let Some(entry) = self.entries.lock().unwrap().get(url) {
+      return Ok(Some(entry.clone()));
+    };
+
+    let Some(path) = self.entry_path(url) else { return Ok(None) };
+    let entry = tokio::task::spawn_blocking(move || read_cache_entry(&path)).await.unwrap()?;
+    if let Some(entry) = &entry {
+      self.entries.lock().unwrap().insert(url.to_owned(), entry.clone());
+    };
+
+    Ok(entry)
+  }
+
+  async fn insert(&self, url: String, entry: CacheEntry) -> Result<(), crate::Error> {
+    let entry = match self.entry_path(&url) {
+      Some(path) => tokio::task::spawn_blocking(move || {
+        write_cache_entry(&path, &entry)?;
+        Ok::<CacheEntry, crate::Error>(entry)
+      }).await.unwrap()?,
+      None => entry
+    };
+
+    self.entries.lock().unwrap().insert(url, entry);
+    Ok(())
+  }
+
+  // urls are turned into file names by replacing anything that could be a path separator or extension
+  fn entry_path(&self, url: &str) -> Option<PathBuf> {
+    let file_name = url.replace(|c: char| !c.is_ascii_alphanumeric() && c != '-', "_");
+    self.directory.as_ref().map(|directory| directory.join(file_name))
+  }
+}
+
+#[cfg(feature = "async")]
+impl fmt::Debug for HttpCache {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    // the cached contents are far too large to print
+    f.debug_struct("HttpCache")
+      .field("directory", &self.directory)
+      .field("entries", &self.entries.lock().map(|entries| entries.len()))
+      .finish()
+  }
+}
+
+#[cfg(feature = "async")]
+#[derive(Debug, Clone)]
+struct CacheEntry {
+  etag: Option<String>,
+  last_modified: Option<String>,
+  contents: Vec<u8>
+}
+
+/// Entries with unreadable metadata, or whose contents do not match the length they were written with,
+/// are treated as missing so that they are downloaded again.
+#[cfg(feature = "async")]
+fn read_cache_entry(path: &Path) -> Result<Option<CacheEntry>, crate::Error> {
+  let metadata_path = path.with_extension("meta.json");
+  if !path.exists() || !metadata_path.exists() { return Ok(None) };
+  let Ok(metadata) = serde_json::from_slice::<CacheMetadata>(&std::fs::read(metadata_path)?) else { return Ok(None) };
+  let contents = std::fs::read(path)?;
+  if metadata.length.is_some_and(|length| length != contents.len()) { return Ok(None) };
+  Ok(Some(CacheEntry { etag: metadata.etag, last_modified: metadata.last_modified, contents }))
+}
+
+#[cfg(feature = "async")]
+fn write_cache_entry(path: &Path, entry: &CacheEntry) -> Result<(), crate::Error> {
+  let metadata = CacheMetadata {
+    etag: entry.etag.clone(),
+    last_modified: entry.last_modified.clone(),
+    length: Some(entry.contents.len())
+  };
+  write_atomic(path, &entry.contents)?;
+  write_atomic(&path.with_extension("meta.json"), &serde_json::to_vec(&metadata)?)?;
+  Ok(())
+}
+
+#[cfg(feature = "async")]
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheMetadata {
+  etag: Option<String>,
+  last_modified: Option<String>,
+  /// Missing from entries written by older versions of this crate.
+  length: Option<usize>
+}
+
+#[cfg(feature = "async")]
+#[derive(Debug, Deserialize)]
+struct ContentFile {
+  git_url: String
+}
+
+/// Shared by every [`Source`] that does not go through GitHub's API.
+#[cfg(feature = "async")]
+static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
+
+/// A repository host to download game data from.
+///
+/// ```no_run
+/// # use ak_data::game_data::GameData;
+/// # use ak_data::options::{Region, Source};
+/// # async fn f() -> Result<(), ak_data::Error> {
+/// let source = Source::GitLab {
+///   host: "gitlab.com".to_owned(),
+///   project: "owner/ArknightsGameData".to_owned(),
+///   branch: "master".to_owned(),
+///   region: Region::EnUS
+/// };
+///
+/// let game_data = GameData::from_source(&source).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async")]
+#[derive(Debug, Clone)]
+pub enum Source {
+  /// A GitHub repository, see [`Options`].
+  GitHub(Options),
+  /// A repository on a GitLab instance.
+  GitLab {
+    /// The domain of the GitLab instance. (Example: `"gitlab.com"`)
+    host: String,
+    /// The full path of the project, including its namespace. (Example: `"owner/ArknightsGameData"`)
+    project: String,
+    /// The branch of that project to grab gamedata from.
+    branch: String,
+    /// The region subfolder of that project to pull files from.
+    region: Region
+  },
+  /// Any host serving the repository's files over plain HTTP.
+  /// Data files are downloaded from `{base_url}/{region}/gamedata/{location}`.
+  RawUrl {
+    /// The URL of the root folder of the repository, without a trailing slash.
+    base_url: String,
+    /// The region subfolder to pull files from.
+    region: Region
+  }
+}
+
+#[cfg(feature = "async")]
+impl DataSource for Source {
+  async fn fetch(&self, relative_path: &str) -> Result<Vec<u8>, crate::Error> {
+    let url = match self {
+      Source::GitHub(options) => return options.fetch(relative_path).await,
+      Source::GitLab { host, project, branch, region } => {
+        format!("https://{host}/{project}/-/raw/{branch}/{region}/gamedata/{relative_path}")
+      },
+      Source::RawUrl { base_url, region } => {
+        format!("{base_url}/{region}/gamedata/{relative_path}")
+      }
+    };
+
+    get_http(&HTTP_CLIENT, url).await
+  }
+
+  async fn last_updated(&self) -> Result<Option<DateTime<Utc>>, crate::Error> {
+    match self {
+      Source::GitHub(options) => options.last_updated().await,
+      Source::GitLab { host, project, branch, .. } => {
+        // the API expects slashes in the project path and branch name to be escaped
+        let url = format!(
+          "https://{host}/api/v4/projects/{}/repository/branches/{}",
+          project.replace('/', "%2F"), branch.replace('/', "%2F")
+        );
+        let bytes = get_http(&HTTP_CLIENT, url).await?;
+        let branch: GitLabBranch = serde_json::from_slice(&bytes)?;
+        Ok(Some(branch.commit.committed_date))
+      },
+      Source::RawUrl { .. } => Ok(None)
+    }
+  }
+
+  async fn provenance(&self) -> Provenance {
+    match self {
+      Source::GitHub(options) => options.provenance().await,
+      Source::GitLab { host, project, branch, region } => {
+        let (namespace, name) = project.rsplit_once('/').unwrap_or(("", project));
+        Provenance {
+          host: Some(host.clone()),
+          repository: Some((namespace.to_owned(), name.to_owned())),
+          branch: Some(branch.clone()),
+          commit: None,
+          region: Some(*region),
+          fetched_at: Utc::now()
+        }
+      },
+      Source::RawUrl { base_url, region } => Provenance {
+        host: Some(base_url.clone()),
+        repository: None,
+        branch: None,
+        commit: None,
+        region: Some(*region),
+        fetched_at: Utc::now()
+      }
+    }
+  }
+
+  fn progress(&self) -> Option<&Progress> {
+    match self {
+      Source::GitHub(options) => options.progress(),
+      _ => None
+    }
+  }
+
+  fn max_concurrent_requests(&self) -> usize {
+    match self {
+      Source::GitHub(options) => options.max_concurrent_requests(),
+      _ => Options::DEFAULT_MAX_CONCURRENT_REQUESTS
+    }
+  }
+}
+
+#[cfg(feature = "async")]
+impl From<Options> for Source {
+  #[inline]
+  fn from(options: Options) -> Self {
+    Source::GitHub(options)
+  }
+}
+
+/// A local clone of a game data repository, kept up to date by running `git`.
+///
+/// Reading files from disk is much faster than downloading them, and keeps working while offline.
+/// Call [`LocalClone::pull`] to create or update the clone before loading from it.
+/// Requires `git` to be installed and available on the `PATH`.
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalClone {
+  /// The folder the repository is (or will be) cloned into.
+  pub path: PathBuf,
+  /// The URL of the repository to clone.
+  pub url: String,
+  /// The branch of that repository to grab gamedata from.
+  pub branch: String,
+  /// The region subfolder of that repository to pull files from.
+  pub region: Region
+}
+
+#[cfg(feature = "async")]
+impl LocalClone {
+  /// Creates a clone of the given repository URL in the given folder,
+  /// using the default branch and region of [`Options`].
+  pub fn new(path: impl Into<PathBuf>, url: impl Into<String>) -> Self {
+    LocalClone {
+      path: path.into(),
+      url: url.into(),
+      branch: Options::DEFAULT_BRANCH.to_owned(),
+      region: Options::DEFAULT_REGION
+    }
+  }
+
+  pub fn branch(self, branch: impl Into<String>) -> Self {
+    LocalClone { branch: branch.into(), ..self }
+  }
+
+  pub fn region(self, region: Region) -> Self {
+    LocalClone { region, ..self }
+  }
+
+  /// The path to the `gamedata` folder of the selected region inside of the clone.
+  pub fn gamedata_dir(&self) -> PathBuf {
+    self.path.join(self.region.to_str()).join("gamedata")
+  }
+
+  /// Clones the repository if it has not been cloned yet, otherwise pulls the latest changes of the branch.
+  /// Only the latest commit is downloaded when cloning.
+  pub async fn pull(&self) -> Result<(), crate::Error> {
+    let clone = self.clone();
+    tokio::task::spawn_blocking(move || {
+      if clone.path.join(".git").exists() {
+        run_git(Command::new("git").arg("-C").arg(&clone.path)
+          .args(["pull", "--ff-only", "origin", clone.branch.as_str()]))
+      } else {
+        run_git(Command::new("git")
+          .args(["clone", "--depth", "1", "--branch", clone.branch.as_str(), "--", clone.url.as_str()])
+          .arg(&clone.path))
+      }
+    }).await.unwrap()?;
+
+    Ok(())
+  }
+}
+
+#[cfg(feature = "async")]
+impl DataSource for LocalClone {
+  async fn fetch(&self, relative_path: &str) -> Result<Vec<u8>, crate::Error> {
+    let path = self.gamedata_dir().join(relative_path);
+    tokio::task::spawn_blocking(move || std::fs::read(path)).await.unwrap().map_err(From::from)
+  }
+
+  async fn last_updated(&self) -> Result<Option<DateTime<Utc>>, crate::Error> {
+    let path = self.path.clone();
+    let output = tokio::task::spawn_blocking(move || {
+      run_git(Command::new("git").arg("-C").arg(&path).args(["log", "-1", "--format=%cI"]))
+    }).await.unwrap()?;
+
+    Ok(DateTime::parse_from_rfc3339(&output).ok().map(|date_time| date_time.with_timezone(&Utc)))
+  }
+
+  async fn provenance(&self) -> Provenance {
+    let path = self.path.clone();
+    let commit = tokio::task::spawn_blocking(move || {
+      run_git(Command::new("git").arg("-C").arg(&path).args(["rev-parse", "HEAD"])).ok()
+    }).await.unwrap();
+
+    Provenance {
+      host: Some(self.url.clone()),
+      repository: None,
+      branch: Some(self.branch.clone()),
+      commit,
+      region: Some(self.region),
+      fetched_at: Utc::now()
+    }
+  }
+}
+
+/// Runs a git command, returning its trimmed standard output if it succeeded.
+#[cfg(feature = "async")]
+fn run_git(command: &mut Command) -> Result<String, std::io::Error> {
+  let output = command.output()?;
+  if output.status.success() {
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+  } else {
+    let message = format!("git exited with {}: {}", output.status, String::from_utf8_lossy(&output.stderr).trim());
+    Err(std::io::Error::other(message))
+  }
+}
+
+#[cfg(feature = "async")]
+#[derive(Debug, Deserialize)]
+struct GitLabBranch {
+  commit: GitLabCommit
+}
+
+#[cfg(feature = "async")]
+#[derive(Debug, Deserialize)]
+struct GitLabCommit {
+  committed_date: DateTime<Utc>
+}
+
+/// Describes why [`Options::verify`] failed.
+#[cfg(feature = "async")]
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum VerifyError {
+  #[error("repository {0}/{1} does not exist or is not accessible")]
+  RepositoryNotFound(String, String),
+  #[error("branch {0:?} does not exist")]
+  BranchNotFound(String),
+  #[error("tag {0:?} does not exist")]
+  TagNotFound(String),
+  #[error("path {0:?} does not exist in the repository")]
+  PathNotFound(String)
+}
+
+#[cfg(feature = "async")]
+fn is_not_found(error: &octocrab::Error) -> bool {
+  matches!(error, octocrab::Error::GitHub { source, .. } if source.message == "Not Found")
+}
+
+/// Data files extracted from a repository archive, see [`Options::fetch_archive`].
+#[cfg(feature = "async")]
+struct ArchiveSource {
+  files: HashMap<String, Vec<u8>>,
+  last_updated: DateTime<Utc>,
+  provenance: Provenance
+}
+
+#[cfg(feature = "async")]
+impl DataSource for ArchiveSource {
+  async fn fetch(&self, relative_path: &str) -> Result<Vec<u8>, crate::Error> {
+    self.files.get(relative_path).cloned().ok_or_else(|| {
+      let message = format!("{relative_path} is not present in the archive");
+      std::io::Error::new(std::io::ErrorKind::NotFound, message).into()
+    })
+  }
+
+  async fn last_updated(&self) -> Result<Option<DateTime<Utc>>, crate::Error> {
+    Ok(Some(self.last_updated))
+  }
+
+  async fn provenance(&self) -> Provenance {
+    self.provenance.clone()
+  }
+}
+
+/// Downloads a gzipped tarball and extracts every needed data file under `prefix` from it, keyed by their location.
+/// The response body is decompressed and unpacked while it is being downloaded, it is never held in memory as a whole.
+#[cfg(feature = "async")]
+async fn get_archive(instance: &Octocrab, url: String, prefix: String) -> Result<HashMap<String, Vec<u8>>, crate::Error> {
+  let response = instance._get(url, None::<&()>).await?;
+  if let Some(error) = get_rate_limited_error(&response) {
+    return Err(error);
+  };
+
+  let mut response = response.error_for_status().map_err(data_source_error)?;
+  let (sender, receiver) = tokio::sync::mpsc::channel(16);
+  let extraction = tokio::task::spawn_blocking(move || {
+    extract_archive(ChannelReader::new(receiver), &prefix)
+  });
+
+  while let Some(chunk) = response.chunk().await.map_err(data_source_error)? {
+    // the receiver is only dropped if extraction failed, that error is returned below
+    if sender.send(chunk.to_vec()).await.is_err() { break };
+  };
+
+  drop(sender);
+  Ok(extraction.await.unwrap()?)
+}
+
+/// Reads the chunks sent through a channel one after another, blocking until the next one arrives.
+#[cfg(feature = "async")]
+struct ChannelReader {
+  receiver: tokio::sync::mpsc::Receiver<Vec<u8>>,
+  chunk: Vec<u8>,
+  position: usize
+}
+
+#[cfg(feature = "async")]
+impl ChannelReader {
+  fn new(receiver: tokio::sync::mpsc::Receiver<Vec<u8>>) -> Self {
+    ChannelReader { receiver, chunk: Vec::new(), position: 0 }
+  }
+}
+
+#[cfg(feature = "async")]
+impl Read for ChannelReader {
+  fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    while self.position == self.chunk.len() {
+      match self.receiver.blocking_recv() {
+        Some(chunk) => {
+          self.chunk = chunk;
+          self.position = 0;
+        },
+        None => return Ok(0)
+      };
+    };
+
+    let len = buf.len().min(self.chunk.len() - self.position);
+    buf[..len].copy_from_slice(&self.chunk[self.position..self.position + len]);
+    self.position += len;
+    Ok(len)
+  }
+}
+
+/// Extracts every needed data file under `prefix` from a gzipped tarball, keyed by their location.
+#[cfg(feature = "async")]
+fn extract_archive(archive: impl Read, prefix: &str) -> Result<HashMap<String, Vec<u8>>, std::io::Error> {
+  let mut files = HashMap::new();
+  let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+  for entry in archive.entries()? {
+    let mut entry = entry?;
+    // the first component of every path is a folder named after the repository and commit
+    let location = entry.path()?.components().skip(1)
+      .map(|component| component.as_os_str().to_str())
+      .collect::<Option<Vec<&str>>>()
+      .map(|components| components.join("/"))
+      .and_then(|path| path.strip_prefix(prefix).map(str::to_owned))
+      .filter(|location| crate::format::DataFiles::LOCATIONS.contains(&location.as_str()));
+    if let Some(location) = location {
+      let mut contents = Vec::new();
+      entry.read_to_end(&mut contents)?;
+      files.insert(location, contents);
+    };
+  };
+
+  Ok(files)
+}
+
+#[cfg(feature = "async")]
+fn get_header(response: &reqwest::Response, name: reqwest::header::HeaderName) -> Option<String> {
+  response.headers().get(name).and_then(|value| value.to_str().ok()).map(str::to_owned)
+}
+
+#[cfg(feature = "async")]
+fn data_source_error(error: impl std::error::Error + Send + Sync + 'static) -> crate::Error {
+  crate::Error::DataSourceError(Box::new(error))
+}
+
+#[cfg(feature = "async")]
+async fn get_http(client: &reqwest::Client, url: String) -> Result<Vec<u8>, crate::Error> {
+  let response = client.get(url).send().await
+    .and_then(reqwest::Response::error_for_status)
+    .map_err(data_source_error)?;
+  let bytes = response.bytes().await.map_err(data_source_error)?;
+  Ok(bytes.to_vec())
+}
+
+#[cfg(feature = "async")]
+async fn get_raw(instance: &Octocrab, url: String) -> Result<Vec<u8>, crate::Error> {
+  let response = instance._get(url, None::<&()>).await?;
+  if let Some(error) = get_rate_limited_error(&response) {
+    return Err(error);
+  };
+
+  let response = response.error_for_status().map_err(data_source_error)?;
+  let bytes = response.bytes().await.map_err(data_source_error)?;
+  Ok(bytes.to_vec())
+}
+
+/// Returns [`Error::RateLimited`][crate::Error::RateLimited] if the response says the rate limit has been used up.
+#[cfg(feature = "async")]
+fn get_rate_limited_error(response: &reqwest::Response) -> Option<crate::Error> {
+  let status = response.status();
+  if status != reqwest::StatusCode::FORBIDDEN && status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+    return None;
+  };
+
+  let remaining = get_header(response, reqwest::header::HeaderName::from_static("x-ratelimit-remaining"))?
+    .parse::<u32>().ok().filter(|&remaining| remaining == 0)?;
+  let reset_at = get_header(response, reqwest::header::HeaderName::from_static("x-ratelimit-reset"))
+    .and_then(|reset| reset.parse::<i64>().ok())
+    .and_then(|reset| Utc.timestamp_opt(reset, 0).single());
+  Some(crate::Error::RateLimited { reset_at, remaining })
+}
+
+/// Whether or not the error was caused by GitHub's primary rate limit.
+/// Secondary rate limits only last a short while, so they are left to [`RetryPolicy`] instead.
+#[cfg(feature = "async")]
+fn is_rate_limit_error(error: &crate::Error) -> bool {
+  match error {
+    crate::Error::OctocrabError(octocrab::Error::GitHub { source, .. }) => {
+      let message = source.message.to_lowercase();
+      message.contains("rate limit") && !message.contains("secondary")
+    },
+    _ => false
+  }
+}
+
+#[cfg(feature = "async")]
+fn get_commit_last_updated(repo_commit: RepoCommit) -> Option<DateTime<Utc>> {
+  repo_commit.commit.author.and_then(|author| author.date)
+}
+
+/// Runs the given future, failing with [`Error::Timeout`][crate::Error::Timeout] if it does not finish in time.
+#[cfg(feature = "async")]
+async fn with_timeout<T>(duration: Duration, future: impl Future<Output = Result<T, crate::Error>>) -> Result<T, crate::Error> {
+  tokio::time::timeout(duration, future).await.unwrap_or(Err(crate::Error::Timeout(duration)))
+}
+
+/// Loads the selected tables from the given source.
+#[cfg(feature = "async")]
+pub(crate) async fn request_game_data_from<S: DataSource>(
+  source: &S, tables: &TableSelection, lenient: bool
+) -> Result<GameData, crate::Error> {
+  let (data_files, last_updated, data_version) = tokio::try_join!(
+    crate::format::DataFiles::from_source(source, tables),
+    source.last_updated(),
+    fetch_data_version(source)
+  )?;
+
+  let provenance = source.provenance().await;
+  let mut game_data = data_files.into_game_data(tables, last_updated, provenance, lenient)?;
+  game_data.data_version = data_version;
+  Ok(game_data)
+}
+
+/// The location of the file holding the version of the game data, relative to the `gamedata` folder.
+pub(crate) const DATA_VERSION_LOCATION: &str = "excel/data_version.txt";
+
+pub(crate) fn parse_data_version(contents: &[u8]) -> String {
+  String::from_utf8_lossy(contents).trim().to_owned()
+}
+
+/// Fetches the data version from the given source, if it has one.
+/// Not every mirror includes the file, so failing to fetch it is not an error.
+#[cfg(feature = "async")]
+async fn fetch_data_version<S: DataSource>(source: &S) -> Result<Option<String>, crate::Error> {
+  Ok(source.fetch(DATA_VERSION_LOCATION).await.ok().map(|contents| parse_data_version(&contents)))
+}
+
+pub(crate) fn read_data_version(gamedata_dir: &Path) -> Option<String> {
+  std::fs::read(gamedata_dir.join(DATA_VERSION_LOCATION)).ok().map(|contents| parse_data_version(&contents))
+}
+
+#[cfg(feature = "async")]
+pub(crate) async fn get_data_file_from_source<T: DataFile + Send + 'static, S: DataSource>(
+  source: &S, tracker: &ProgressTracker<'_>, semaphore: &tokio::sync::Semaphore
+) -> Result<T, crate::Error> {
+  let permit = semaphore.acquire().await.expect("semaphore is never closed");
+  tracker.report(ProgressEvent::FileStarted { location: T::LOCATION });
+  let bytes = source.fetch(T::LOCATION).await
+    .map_err(|error| crate::format::fetch_error(T::IDENTIFIER, error))?;
+  // parsing does not need to hold up other downloads
+  drop(permit);
+  tracker.report(ProgressEvent::FileDownloaded { location: T::LOCATION, bytes: bytes.len() });
+  // parsed on a blocking thread, so that other files keep downloading (and parsing) in the meantime
+  let value = tokio::task::spawn_blocking(move || crate::format::parse_data_file::<T>(&bytes)).await.unwrap()?;
+  tracker.complete(T::LOCATION);
+  Ok(value)
+}
+
+#[cfg(feature = "async")]
+pub(crate) async fn get_data_file_local<T: DataFile + Send + 'static>(gamedata_dir: &Path) -> Result<T, crate::Error> {
+  let gamedata_dir = gamedata_dir.to_owned();
+  tokio::task::spawn_blocking(move || {
+    get_data_file_local_sync::<T>(&gamedata_dir)
+  }).await.unwrap()
+}
+
+pub(crate) fn get_data_file_local_sync<T: DataFile>(gamedata_dir: &Path) -> Result<T, crate::Error> {
+  let path = gamedata_dir.join(T::LOCATION);
+  let bytes = std::fs::read(path)
+    .map_err(|error| crate::format::fetch_error(T::IDENTIFIER, error.into()))?;
+  crate::format::parse_data_file(&bytes)
+}
+
+
+
+#[cfg(feature = "async")]
+#[derive(Debug, Serialize, Deserialize)]
+struct Blob {
+  sha: String,
+  node_id: String,
+  size: u64,
   url: String,
   content: String,
   encoding: String
 }
 
+#[cfg(feature = "async")]
 impl Blob {
   fn into_bytes(self) -> Result<Vec<u8>, base64::DecodeError> {
     base64::decode(self.content.replace(char::is_whitespace, ""))
   }
 }
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+  use super::*;
+
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  const URL: &str = "https://raw.githubusercontent.com/Kengxxiao/ArknightsGameData/master/en_US/gamedata/excel/item_table.json";
+  const CONTENTS: &[u8] = br#"{"items":{},"expItems":{}}"#;
+
+  fn temp_dir(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("ak-data-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&directory);
+    directory
+  }
+
+  #[derive(Default)]
+  struct CountingSource {
+    fetches: AtomicUsize
+  }
+
+  impl DataSource for CountingSource {
+    async fn fetch(&self, _relative_path: &str) -> Result<Vec<u8>, crate::Error> {
+      self.fetches.fetch_add(1, Ordering::SeqCst);
+      Ok(CONTENTS.to_vec())
+    }
+
+    async fn last_updated(&self) -> Result<Option<DateTime<Utc>>, crate::Error> {
+      Ok(None)
+    }
+
+    async fn provenance(&self) -> Provenance {
+      Provenance::unknown()
+    }
+  }
+
+  #[tokio::test]
+  async fn disk_cache_head_round_trip() {
+    let disk_cache = DiskCache::new(temp_dir("head-round-trip")).ttl(Duration::from_secs(3600));
+    let options = Options::default();
+    let last_updated = Utc::now();
+    disk_cache.write_head(&options, "abc123", last_updated).await.unwrap();
+
+    let head = disk_cache.read_head(&options).await.unwrap().unwrap();
+    assert_eq!(head.commit, "abc123");
+    assert_eq!(head.last_updated, last_updated);
+    disk_cache.clear().unwrap();
+  }
+
+  #[tokio::test]
+  async fn disk_cache_head_expires() {
+    let disk_cache = DiskCache::new(temp_dir("head-expires")).ttl(Duration::from_secs(60));
+    let options = Options::default();
+    let checked_at = Utc::now() - chrono::Duration::minutes(5);
+    let head = DiskCacheHead { commit: "abc123".to_owned(), last_updated: checked_at, checked_at };
+    write_atomic(&disk_cache.repository_dir(&options).join("HEAD.json"), &serde_json::to_vec(&head).unwrap()).unwrap();
+
+    // an expired head is not returned, so the latest commit of the branch is requested again
+    assert!(disk_cache.read_head(&options).await.unwrap().is_none());
+    disk_cache.clear().unwrap();
+  }
+
+  #[tokio::test]
+  async fn disk_cache_corrupt_head() {
+    let disk_cache = DiskCache::new(temp_dir("corrupt-head")).ttl(Duration::from_secs(3600));
+    let options = Options::default();
+    write_atomic(&disk_cache.repository_dir(&options).join("HEAD.json"), br#"{"commit":"abc"#).unwrap();
+
+    assert!(disk_cache.read_head(&options).await.unwrap().is_none());
+    disk_cache.clear().unwrap();
+  }
+
+  #[tokio::test]
+  async fn disk_cache_source_refetches_corrupt_files() {
+    let directory = temp_dir("corrupt-file");
+    let source = DiskCacheSource { directory: directory.clone(), force_refresh: false, source: CountingSource::default() };
+    assert_eq!(source.fetch("excel/item_table.json").await.unwrap(), CONTENTS);
+    assert_eq!(source.fetch("excel/item_table.json").await.unwrap(), CONTENTS);
+    assert_eq!(source.source.fetches.load(Ordering::SeqCst), 1);
+
+    std::fs::write(directory.join("excel/item_table.json"), &CONTENTS[..CONTENTS.len() / 2]).unwrap();
+    assert_eq!(source.fetch("excel/item_table.json").await.unwrap(), CONTENTS);
+    assert_eq!(source.source.fetches.load(Ordering::SeqCst), 2);
+    std::fs::remove_dir_all(directory).unwrap();
+  }
+
+  #[tokio::test]
+  async fn http_cache_round_trip() {
+    let directory = temp_dir("http-round-trip");
+    let entry = CacheEntry { etag: Some("\"abc123\"".to_owned()), last_modified: None, contents: CONTENTS.to_vec() };
+    HttpCache::with_directory(directory.clone()).insert(URL.to_owned(), entry).await.unwrap();
+
+    // a new cache starts with nothing in memory, so the entry is read back from the directory
+    let cache = HttpCache::with_directory(directory);
+    let entry = cache.get(URL).await.unwrap().unwrap();
+    assert_eq!(entry.etag.as_deref(), Some("\"abc123\""));
+    assert_eq!(entry.contents, CONTENTS);
+    cache.clear().unwrap();
+  }
+
+  #[tokio::test]
+  async fn http_cache_corrupt_entries() {
+    let directory = temp_dir("http-corrupt");
+    let cache = HttpCache::with_directory(directory.clone());
+    let entry = CacheEntry { etag: Some("\"abc123\"".to_owned()), last_modified: None, contents: CONTENTS.to_vec() };
+    cache.insert(URL.to_owned(), entry).await.unwrap();
+    let path = cache.entry_path(URL).unwrap();
+
+    std::fs::write(&path, &CONTENTS[..CONTENTS.len() / 2]).unwrap();
+    assert!(HttpCache::with_directory(directory.clone()).get(URL).await.unwrap().is_none());
+
+    std::fs::write(&path, CONTENTS).unwrap();
+    std::fs::write(path.with_extension("meta.json"), b"{\"etag\":").unwrap();
+    assert!(HttpCache::with_directory(directory.clone()).get(URL).await.unwrap().is_none());
+    cache.clear().unwrap();
+  }
+}
@@ -0,0 +1,30 @@
+#![cfg(test)]
+
+use ak_data::game_data::AttackRange;
+use mint::Point2;
+
+fn attack_range(points: &[(i32, i32)]) -> AttackRange {
+  AttackRange { points: points.iter().map(|&(x, y)| Point2 { x, y }).collect() }
+}
+
+#[test]
+fn attack_range_geometry() {
+  // a typical 3x4 sniper-style range, including the operator's own tile
+  let range = attack_range(&[
+    (0, -1), (1, -1), (2, -1), (3, -1),
+    (0, 0), (1, 0), (2, 0), (3, 0),
+    (0, 1), (1, 1), (2, 1), (3, 1)
+  ]);
+
+  assert_eq!(range.bounding_box(), (Point2 { x: 0, y: -1 }, Point2 { x: 3, y: 1 }));
+  assert_eq!(range.width(), 4);
+  assert_eq!(range.height(), 3);
+  assert_eq!(range.render_ascii(), "####\nO###\n####");
+
+  // ranges that don't include the operator's tile still have it in their bounding box
+  let range = attack_range(&[(1, 0), (2, 0)]);
+  assert_eq!(range.width(), 3);
+  assert_eq!(range.height(), 1);
+  assert_eq!(range.to_grid(), vec![vec![false, true, true]]);
+  assert_eq!(range.render_ascii(), "O##");
+}
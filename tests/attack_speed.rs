@@ -0,0 +1,46 @@
+#![cfg(test)]
+
+use ak_data::game_data::OperatorPromotionAttributes;
+
+fn attributes(atk: u32, attack_speed: f32, base_attack_time: f32) -> OperatorPromotionAttributes {
+  OperatorPromotionAttributes {
+    level: 1,
+    max_hp: 1000,
+    atk,
+    def: 100,
+    magic_resistance: 0.0,
+    deployment_cost: 10,
+    block_count: 1,
+    move_speed: 1.0,
+    attack_speed,
+    base_attack_time,
+    redeploy_time: 70,
+    hp_recovery_per_sec: 0.0,
+    sp_recovery_per_sec: 1.0,
+    max_deploy_count: 1,
+    max_deck_stack_count: 0,
+    taunt_level: 0,
+    is_stun_immune: false,
+    is_silence_immune: false,
+    is_sleep_immune: false,
+    is_frozen_immune: false
+  }
+}
+
+#[test]
+fn attack_interval() {
+  let attributes = attributes(500, 100.0, 1.6);
+  assert_eq!(attributes.attack_interval(0.0), 1.6);
+  assert_eq!(attributes.attack_interval(100.0), 0.8);
+  // attack speed cannot go below 10
+  assert_eq!(attributes.attack_interval(-1000.0), 16.0);
+}
+
+#[test]
+fn damage_per_hit() {
+  let attributes = attributes(500, 100.0, 1.0);
+  assert_eq!(attributes.physical_damage_per_hit(200.0), 300.0);
+  assert_eq!(attributes.physical_damage_per_hit(1000.0), 25.0);
+  assert_eq!(attributes.arts_damage_per_hit(50.0), 250.0);
+  assert_eq!(attributes.dps(300.0, 0.0), 300.0);
+}
@@ -0,0 +1,164 @@
+#![cfg(test)]
+
+use ak_data::game_data::{
+  BaseLayout, BaseSkillEffect, Building, BuildingType, BuildingUpgrade, GameData, OperatorBaseSkill,
+  OperatorBaseSkillCategory, OperatorBaseSkillPhase, OperatorId, PlacementError, Promotion, PromotionAndLevel,
+  StationedOperator
+};
+
+use std::collections::BTreeMap;
+
+fn effect(efficiency: f32, morale_drain: f32) -> Option<BaseSkillEffect> {
+  Some(BaseSkillEffect { efficiency, morale_drain })
+}
+
+#[test]
+fn base_skill_effects_from_descriptions() {
+  assert_eq!(
+    BaseSkillEffect::from_description("When this Operator is assigned to a Factory, Productivity +15%"),
+    effect(0.15, 0.0)
+  );
+  assert_eq!(
+    BaseSkillEffect::from_description("When this Operator is assigned to a Trading Post, order acquisition efficiency +30%"),
+    effect(0.30, 0.0)
+  );
+  assert_eq!(
+    BaseSkillEffect::from_description("When this Operator is assigned to a Factory, Productivity +25%, Morale consumption per hour +0.25"),
+    effect(0.25, 0.25)
+  );
+  assert_eq!(
+    BaseSkillEffect::from_description("When this Operator is assigned to a Trading Post, Morale consumption per hour -0.25"),
+    effect(0.0, -0.25)
+  );
+}
+
+#[test]
+fn base_skill_effects_not_guessed() {
+  // morale recovery for the whole dormitory is not a change to this operator's drain
+  assert_eq!(BaseSkillEffect::from_description(
+    "When this Operator is assigned to a Dormitory, all Operators in the Dormitory recover an additional +0.1 Morale per hour"
+  ), None);
+  // conditional and stacking effects depend on more than the description says
+  assert_eq!(BaseSkillEffect::from_description(
+    "When this Operator is assigned to a Trading Post, order acquisition efficiency +20%, and an additional +5% for each Penguin Logistics Operator in the Trading Post"
+  ), None);
+  assert_eq!(BaseSkillEffect::from_description(
+    "When this Operator is assigned to a Factory, Productivity +10% when producing Gold"
+  ), None);
+  // storage capacity is not an efficiency bonus
+  assert_eq!(BaseSkillEffect::from_description(
+    "When this Operator is assigned to a Factory, warehouse capacity limit +8"
+  ), None);
+  // descriptions in other languages are not read
+  assert_eq!(BaseSkillEffect::from_description("进驻制造站时，生产力+15%"), None);
+}
+
+// (size, max count, [(power, operator capacity, manpower cost, construction drones)] for each level)
+fn buildings() -> BTreeMap<BuildingType, Building> {
+  fn building(building_type: BuildingType, size: (u32, u32), max_count: Option<u32>, upgrades: &[(i32, u32, u32, u32)]) -> Building {
+    Building {
+      building_type,
+      name: format!("{building_type:?}"),
+      description: None,
+      max_count,
+      category: String::new(),
+      size,
+      upgrades: upgrades.iter().map(|&(power, operator_capacity, manpower_cost, construction_drones)| BuildingUpgrade {
+        unlock_condition: String::new(),
+        construction_cost: BTreeMap::new(),
+        construction_drones,
+        power,
+        operator_capacity,
+        manpower_cost
+      }).collect()
+    }
+  }
+
+  [
+    building(BuildingType::ControlCenter, (3, 1), Some(1), &[(0, 1, 0, 0), (0, 2, 0, 0)]),
+    building(BuildingType::PowerPlant, (3, 1), Some(3), &[(60, 1, 0, 0), (130, 1, 0, 12), (270, 1, 0, 60)]),
+    building(BuildingType::Factory, (3, 1), Some(9), &[(-10, 1, 2, 0), (-30, 2, 4, 12), (-60, 3, 6, 60)]),
+    building(BuildingType::Elevator, (1, 1), None, &[(0, 0, 0, 0)])
+  ].into_iter().map(|building| (building.building_type, building)).collect()
+}
+
+#[test]
+fn base_layout() {
+  let buildings = buildings();
+  let mut layout = BaseLayout::new();
+  assert_eq!(layout.place(&buildings, BuildingType::ControlCenter, 1, (0, 0)), Ok(0));
+  assert_eq!(layout.place(&buildings, BuildingType::Elevator, 1, (3, 0)), Ok(1));
+  assert_eq!(layout.place(&buildings, BuildingType::PowerPlant, 3, (4, 0)), Ok(2));
+  assert_eq!(layout.place(&buildings, BuildingType::Elevator, 1, (3, 1)), Ok(3));
+  assert_eq!(layout.place(&buildings, BuildingType::Factory, 2, (4, 1)), Ok(4));
+  // nothing connects the third floor to the rest of the base
+  assert_eq!(layout.place(&buildings, BuildingType::Factory, 1, (8, 2)), Ok(5));
+
+  assert_eq!(
+    layout.place(&buildings, BuildingType::ControlCenter, 1, (0, 3)),
+    Err(PlacementError::TooMany { building_type: BuildingType::ControlCenter, max_count: 1 })
+  );
+  assert_eq!(layout.place(&buildings, BuildingType::Factory, 1, (1, 0)), Err(PlacementError::Overlapping(0)));
+  assert_eq!(
+    layout.place(&buildings, BuildingType::PowerPlant, 4, (0, 3)),
+    Err(PlacementError::InvalidLevel { building_type: BuildingType::PowerPlant, level: 4 })
+  );
+  assert_eq!(layout.place(&buildings, BuildingType::Dormitory, 1, (0, 3)), Err(PlacementError::UnknownBuilding(BuildingType::Dormitory)));
+
+  assert_eq!(layout.validate(), [PlacementError::Disconnected(5)]);
+  assert_eq!(layout.power_balance(&buildings), 230);
+  assert_eq!(layout.construction_drones(&buildings), 84);
+  assert_eq!(layout.operator_capacity(&buildings), 5);
+  assert_eq!(layout.manpower_cost(&buildings), 6);
+
+  assert!(layout.remove(5).is_some());
+  assert!(layout.validate().is_empty());
+  assert_eq!(BaseLayout::new().validate(), [PlacementError::MissingControlCenter]);
+}
+
+#[test]
+fn simulate_factory() {
+  fn phase(name: &str, description: &str, promotion: Promotion, building_type: BuildingType) -> OperatorBaseSkillPhase {
+    OperatorBaseSkillPhase {
+      name: name.to_owned(),
+      description: Some(description.to_owned()),
+      condition: PromotionAndLevel { promotion, level: 1 },
+      sort: 0,
+      category: OperatorBaseSkillCategory::Output,
+      building_type,
+      effect: BaseSkillEffect::from_description(description)
+    }
+  }
+
+  let mut game_data: GameData = serde_json::from_slice(include_bytes!("samples/game_data.json")).unwrap();
+  game_data.operators.get_mut("char_102_texas").unwrap().base_skills = vec![
+    OperatorBaseSkill { phases: vec![
+      phase("Standardization α", "When this Operator is assigned to a Factory, Productivity +15%, Morale consumption per hour -0.25", Promotion::None, BuildingType::Factory),
+      phase("Standardization β", "When this Operator is assigned to a Factory, Productivity +25%, Morale consumption per hour -0.25", Promotion::Elite2, BuildingType::Factory)
+    ] },
+    OperatorBaseSkill { phases: vec![
+      phase("Tailoring α", "When this Operator is assigned to a Trading Post, order acquisition efficiency +10%", Promotion::None, BuildingType::TradingPost)
+    ] },
+    OperatorBaseSkill { phases: vec![
+      phase("Gold Production", "When this Operator is assigned to a Factory, Productivity +10% when producing Gold", Promotion::None, BuildingType::Factory)
+    ] }
+  ];
+
+  let stationed = |operator_id: &str, promotion| StationedOperator {
+    operator_id: OperatorId::new(operator_id),
+    promotion_and_level: PromotionAndLevel { promotion, level: 1 }
+  };
+
+  // the elite 2 phase is still locked, and operators missing from the game data have no base skills
+  let simulation = game_data.simulate_room(BuildingType::Factory, &[
+    stationed("char_102_texas", Promotion::Elite1),
+    stationed("char_9999_unknown", Promotion::Elite2)
+  ]);
+
+  assert!((simulation.efficiency - 1.17).abs() < 1e-6);
+  assert_eq!(simulation.morale_drain, [0.75, 1.0]);
+  assert_eq!(simulation.applied_skills, ["Standardization α"]);
+  assert_eq!(simulation.unknown_skills, ["Gold Production"]);
+  assert_eq!(simulation.hours_until_exhausted(), [32.0, 24.0]);
+  assert!((simulation.production_per_hour(100.0) - 117.0).abs() < 1e-4);
+}
@@ -0,0 +1,60 @@
+#![cfg(test)]
+
+use ak_data::game_data::GameData;
+
+fn sample() -> GameData {
+  serde_json::from_slice(include_bytes!("samples/game_data.json")).unwrap()
+}
+
+#[test]
+fn shared_names_resolve_to_lowest_id() {
+  // Amiya's guard alter shares her name, the original should still be found first
+  let mut game_data = sample();
+  game_data.rebuild_index();
+  assert_eq!(game_data.index.names["amiya"], "char_002_amiya");
+  assert_eq!(game_data.find_operator("Amiya").unwrap().id, "char_002_amiya");
+  assert_eq!(game_data.lookup_operator("amiya").unwrap().id, "char_002_amiya");
+  assert_eq!(game_data.lookup_operator("pl03").unwrap().id, "char_102_texas");
+}
+
+#[test]
+fn find_operator_without_index() {
+  // the sample has no index, like game data deserialized from an older cache
+  let game_data = sample();
+  assert!(game_data.index.names.is_empty());
+  assert_eq!(game_data.find_operator("Amiya").unwrap().id, "char_002_amiya");
+  assert_eq!(game_data.find_operator("TEXAS").unwrap().id, "char_102_texas");
+  assert_eq!(game_data.lookup_operator("pl03").unwrap().id, "char_102_texas");
+  assert!(game_data.find_operator("Exusiai").is_none());
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn index_rebuilt_after_msgpack() {
+  let bytes = sample().to_msgpack().unwrap();
+  let game_data = GameData::from_msgpack(&bytes).unwrap();
+  assert_eq!(game_data.index.names["amiya"], "char_002_amiya");
+}
+
+#[test]
+fn faction_lookups() {
+  let mut game_data = sample();
+  game_data.rebuild_index();
+  assert_eq!(game_data.get_faction_parent("penguin"), Some("siracusa"));
+  assert_eq!(game_data.get_faction_parent("siracusa"), None);
+
+  let texas = &game_data.operators["char_102_texas"];
+  let hierarchy = game_data.iter_faction_hierarchy(texas).collect::<Vec<&str>>();
+  assert_eq!(&hierarchy[..2], ["penguin", "siracusa"]);
+
+  let ids = |faction_id, include_sub_groups| {
+    game_data.iter_operators_by_faction(faction_id, include_sub_groups)
+      .map(|operator| operator.id.as_str())
+      .collect::<Vec<&str>>()
+  };
+
+  assert_eq!(ids("rhodes", false), ["char_002_amiya", "char_1037_amiya3"]);
+  assert_eq!(ids("siracusa", true), ["char_102_texas"]);
+  assert!(ids("siracusa", false).is_empty());
+  assert_eq!(ids("penguin", false), ["char_102_texas"]);
+}
@@ -0,0 +1,50 @@
+#![cfg(test)]
+#![cfg(feature = "msgpack")]
+
+use ak_data::game_data::GameData;
+
+fn sample_game_data() -> GameData {
+  serde_json::from_value(serde_json::json!({
+    "last_updated": "2023-01-01T00:00:00Z",
+    "provenance": {
+      "host": null,
+      "repository": ["Kengxxiao", "ArknightsGameData"],
+      "branch": "master",
+      "commit": "0123456789abcdef",
+      "region": "zh_CN",
+      "fetched_at": "2023-01-01T00:00:00Z"
+    },
+    "data_version": "23-01-01-00-00-00-000000",
+    "alters": [],
+    "operators": {},
+    "operators_by_profession": {},
+    "operators_by_sub_profession": { "funnel": [], "newarchetype": [] },
+    "items": {},
+    "buildings": {},
+    "ranges": {},
+    "recruitment_tags": { "Healing": 1 },
+    "operators_by_recruitment_tag": {},
+    "recruitable_operators": [],
+    "recruitment_time_brackets": [{ "min_hours": 1, "min_rarity": 1, "max_rarity": 4 }],
+    "headhunting_banners": [],
+    "events": [{
+      "id": "act1sre",
+      "name": "Heart of Surging Flame",
+      "event_type": "SideStory",
+      "activity_type": "TYPE_ACT9D0",
+      "open_time": "2023-01-01T00:00:00Z",
+      "close_time": "2023-01-15T00:00:00Z",
+      "close_time_rewards": "2023-01-22T00:00:00Z",
+      "is_rerun": false
+    }],
+    "constants": { "max_levels": [[30]], "exp_costs": [[100]], "lmd_costs": [[30]], "promotion_lmd_costs": [[]] },
+    "load_warnings": [{ "table": "item_table", "id": "item", "kind": "Unrecognized", "message": "unknown classify type" }]
+  })).unwrap()
+}
+
+#[test]
+fn msgpack_round_trip() {
+  let game_data = sample_game_data();
+  let bytes = game_data.to_msgpack().unwrap();
+  assert_eq!(GameData::from_msgpack(&bytes).unwrap(), game_data);
+}
@@ -0,0 +1,34 @@
+#![cfg(test)]
+
+use ak_data::game_data::{Profession, SubProfession};
+
+#[test]
+fn parse_professions() {
+  assert_eq!("vanguard".parse::<Profession>().unwrap(), Profession::Vanguard);
+  assert_eq!("PIONEER".parse::<Profession>().unwrap(), Profession::Vanguard);
+  assert!("defender?".parse::<Profession>().is_err());
+  for profession in Profession::iter() {
+    assert_eq!(profession.to_string().parse::<Profession>().unwrap(), profession);
+  };
+}
+
+#[test]
+fn parse_sub_professions() {
+  assert_eq!("funnel".parse::<SubProfession>().unwrap(), SubProfession::MechAccordCaster);
+  assert_eq!("Mech-Accord Caster".parse::<SubProfession>().unwrap(), SubProfession::MechAccordCaster);
+  assert_eq!("MechAccordCaster".parse::<SubProfession>().unwrap(), SubProfession::MechAccordCaster);
+  for sub_profession in SubProfession::iter() {
+    assert_eq!(sub_profession.to_string().parse::<SubProfession>().unwrap(), sub_profession);
+    assert_eq!(sub_profession.game_id().parse::<SubProfession>().unwrap(), sub_profession);
+  };
+}
+
+#[test]
+fn sub_professions_as_map_keys() {
+  let mut map = std::collections::BTreeMap::new();
+  map.insert(SubProfession::MechAccordCaster, 1);
+  map.insert(SubProfession::Other("newarchetype".to_owned()), 2);
+  let json = serde_json::to_string(&map).unwrap();
+  assert_eq!(json, r#"{"funnel":1,"newarchetype":2}"#);
+  assert_eq!(serde_json::from_str::<std::collections::BTreeMap<SubProfession, i32>>(&json).unwrap(), map);
+}
@@ -0,0 +1,22 @@
+#![cfg(test)]
+
+use ak_data::game_data::{Promotion, PromotionAndLevel};
+
+#[test]
+fn parse_promotion_and_level() {
+  assert_eq!("E2 40".parse::<PromotionAndLevel>().unwrap(), Promotion::Elite2.with_level(40));
+  assert_eq!("e1 55".parse::<PromotionAndLevel>().unwrap(), Promotion::Elite1.with_level(55));
+  assert_eq!("e2l90".parse::<PromotionAndLevel>().unwrap(), Promotion::Elite2.with_level(90));
+  assert_eq!("Elite 1 Level 30".parse::<PromotionAndLevel>().unwrap(), Promotion::Elite1.with_level(30));
+  assert_eq!("40".parse::<PromotionAndLevel>().unwrap(), Promotion::None.with_level(40));
+  assert_eq!("max".parse::<PromotionAndLevel>().unwrap(), PromotionAndLevel::MAX);
+  assert!("e3 10".parse::<PromotionAndLevel>().is_err());
+  assert!("e2".parse::<PromotionAndLevel>().is_err());
+}
+
+#[test]
+fn display_promotion_and_level() {
+  for s in ["E0 1", "E1 55", "E2 90", "E1 max", "max"] {
+    assert_eq!(s.parse::<PromotionAndLevel>().unwrap().to_string(), s);
+  };
+}
@@ -0,0 +1,12 @@
+#![cfg(test)]
+
+use ak_data::game_data::normalize_name;
+
+#[test]
+fn normalize_names() {
+  assert_eq!(normalize_name("Pozëmka"), "pozemka");
+  assert_eq!(normalize_name("  SilverAsh "), "silverash");
+  assert_eq!(normalize_name("Ch'en the Holungday"), "chentheholungday");
+  assert_eq!(normalize_name("The Dreamchaser"), "dreamchaser");
+  assert_eq!(normalize_name("Гум"), "гум");
+}
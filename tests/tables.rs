@@ -0,0 +1,74 @@
+#![cfg(test)]
+
+use ak_data::game_data::{GameData, GameDataBuilder, ItemClass, TableSelection};
+
+// only the item table is present in this folder, any other table fails to load if it is read
+const GAMEDATA: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/samples/gamedata");
+
+#[test]
+fn deselected_tables_are_left_empty() {
+  let game_data = GameDataBuilder::new()
+    .tables(TableSelection::NONE.items(true))
+    .load_local_sync(GAMEDATA)
+    .unwrap();
+
+  assert_eq!(game_data.items.len(), 2);
+  assert_eq!(game_data.items["30011"].name, "Orirock");
+  assert!(game_data.operators.is_empty());
+  assert!(game_data.alters.is_empty());
+  assert!(game_data.buildings.is_empty());
+  assert!(game_data.headhunting_banners.is_empty());
+  assert!(game_data.events.is_empty());
+  assert!(game_data.constants.max_levels.is_empty());
+  assert!(game_data.load_warnings.is_empty());
+}
+
+#[test]
+fn selected_tables_must_exist() {
+  let result = GameDataBuilder::new()
+    .tables(TableSelection::NONE.ranges(true))
+    .load_local_sync(GAMEDATA);
+  assert!(result.is_err());
+}
+
+#[cfg(feature = "async")]
+mod source {
+  use ak_data::game_data::Provenance;
+  use ak_data::options::DataSource;
+  use chrono::{DateTime, Utc};
+
+  pub struct SampleSource;
+
+  impl DataSource for SampleSource {
+    async fn fetch(&self, relative_path: &str) -> Result<Vec<u8>, ak_data::Error> {
+      Ok(std::fs::read(std::path::Path::new(super::GAMEDATA).join(relative_path))?)
+    }
+
+    async fn last_updated(&self) -> Result<Option<DateTime<Utc>>, ak_data::Error> {
+      Ok(None)
+    }
+
+    async fn provenance(&self) -> Provenance {
+      Provenance::unknown()
+    }
+  }
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn refresh_keeps_unselected_tables() {
+  let mut game_data: GameData = serde_json::from_slice(include_bytes!("samples/game_data.json")).unwrap();
+  let operators = game_data.operators.clone();
+  let alters = game_data.alters.clone();
+  let provenance = game_data.provenance.clone();
+
+  game_data.refresh_tables_from_source(&source::SampleSource, TableSelection::NONE.items(true)).await.unwrap();
+  assert_eq!(game_data.items.len(), 2);
+  assert_eq!(game_data.operators, operators);
+  assert_eq!(game_data.alters, alters);
+  assert_eq!(game_data.provenance, provenance);
+
+  // the index is rebuilt to cover both the kept and the refreshed tables
+  assert_eq!(game_data.index.names["texas"], "char_102_texas");
+  assert_eq!(game_data.index.items_by_class[&ItemClass::Material], ["30011"]);
+}
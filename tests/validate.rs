@@ -0,0 +1,35 @@
+#![cfg(test)]
+
+use ak_data::game_data::{GameData, ItemId, OperatorId, RangeId, Violation};
+
+#[test]
+fn sample_is_consistent() {
+  let game_data: GameData = serde_json::from_slice(include_bytes!("samples/game_data.json")).unwrap();
+  assert!(game_data.validate().is_empty());
+}
+
+#[test]
+fn dangling_references() {
+  // Amiya's module costs an item that does not exist, Texas uses a range that does not exist,
+  // and the alter group lists an operator that does not exist
+  let game_data: GameData = serde_json::from_slice(include_bytes!("samples/game_data_dangling.json")).unwrap();
+  assert_eq!(game_data.validate(), [
+    Violation::MissingItem {
+      referenced_by: "operator \"char_002_amiya\"".to_owned(),
+      item_id: ItemId::new("mod_update_token_1")
+    },
+    Violation::MissingRange {
+      operator_id: OperatorId::new("char_102_texas"),
+      range_id: RangeId::new("1-1")
+    },
+    Violation::MissingOperator {
+      referenced_by: "alter group \"char_002_amiya\"".to_owned(),
+      operator_id: OperatorId::new("char_1001_amiya2")
+    }
+  ]);
+
+  assert_eq!(
+    game_data.validate()[2].to_string(),
+    "alter group \"char_002_amiya\" references missing operator \"char_1001_amiya2\""
+  );
+}